@@ -0,0 +1,311 @@
+use super::*;
+use integral::{Definite, Method};
+use simplify::simplify;
+
+// where intersections are looked for when no bounds are given
+const WINDOW: (f64, f64) = (-100.0, 100.0);
+
+/// Which variable the slices are taken along. With Y the bank functions are read as x = f(y),
+/// they are still written in terms of x in the bank
+#[derive(PartialEq)]
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+pub enum Respect {
+    X,
+    Y,
+}
+
+/// A line to revolve around, Horizontal is y = c and Vertical is x = c
+#[derive(Debug)]
+pub enum Axis {
+    Horizontal(Expression),
+    Vertical(Expression),
+}
+
+/// Shapes for known cross sections, the side or diameter is the distance between the curves
+#[derive(PartialEq)]
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+pub enum Shape {
+    Square,
+    Semicircle,
+    EquilateralTriangle,
+}
+
+/// What was set up
+#[derive(PartialEq)]
+#[derive(Debug)]
+pub enum Kind {
+    Area,
+    Disk,
+    Washer,
+    Shell,
+    CrossSection(Shape),
+}
+
+/// One end of a slice of the region, exact when it snapped to a nice number
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bound {
+    #[cfg_attr(feature = "serde", serde(with = "crate::schema::number"))]
+    pub value: f64,
+    pub exact: Option<Expression>,
+}
+
+impl fmt::Display for Bound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.exact {
+            Some(exact) => write!(f,"{}",exact),
+            None => write!(f,"{}",decimal(self.value)),
+        }
+    }
+}
+
+/// The region between bank functions f and g (the axis when g is None) split at the points
+/// where the curves cross, so each piece has one curve on top
+#[derive(Debug)]
+pub struct Region {
+    pub f: char,
+    pub g: Option<char>,
+    pub respect: Respect,
+    pub points: Vec<Bound>,
+}
+
+/// An integral that was set up and its value, integral is the same thing text shows
+#[derive(Debug)]
+pub struct Application {
+    pub kind: Kind,
+    pub integral: Expression,
+    pub text: String,
+    pub value: Definite,
+}
+
+/// The region enclosed by f and g, bounded by the first and last places they meet. None when
+/// they meet fewer than twice, so nothing is enclosed
+pub fn region(bank: &Workspace, f: char, g: Option<char>, respect: Respect) -> Option<Region> {
+    let difference = difference(bank, f, g);
+    let roots = numeric::roots(|x| difference.approximate(bank, x), WINDOW.0, WINDOW.1);
+    if roots.len() < 2 {
+        return None;
+    }
+    Some(Region { f, g, respect, points: roots.into_iter().map(bound).collect() })
+}
+
+/// The region between f and g from a to b, split wherever they cross in between
+pub fn region_on(bank: &Workspace, f: char, g: Option<char>, respect: Respect, a: &Expression, b: &Expression) -> Region {
+    let difference = difference(bank, f, g);
+    let (low, high) = (a.approximate_with(bank, &[]), b.approximate_with(bank, &[]));
+    let mut points = vec![Bound { value: low, exact: Some(copy_expression(a)) }];
+    points.extend(numeric::roots(|x| difference.approximate(bank, x), low, high).into_iter()
+        .filter(|x| *x > low + 1e-9 && *x < high - 1e-9)
+        .map(bound));
+    points.push(Bound { value: high, exact: Some(copy_expression(b)) });
+    Region { f, g, respect, points }
+}
+
+fn difference(bank: &Workspace, f: char, g: Option<char>) -> Expression {
+    expand(bank, &Expression::from(&format!("({})-({})", call(f), g.map_or(String::from("0"), call))))
+}
+
+fn call(f: char) -> String {
+    format!("{}(x)", f)
+}
+
+/// x as a Bound, exact when it snaps to a nice number
+pub fn bound(x: f64) -> Bound {
+    let exact = limits::exact_value(x, 1e-9);
+    let value = exact.as_ref().map_or(x, |e| e.approximate_with(&Workspace::new(), &[]));
+    Bound { value, exact }
+}
+
+// six places is plenty for a bound that didn't come out nice
+fn decimal(x: f64) -> String {
+    let text = format!("{:.6}", x);
+    String::from(text.trim_end_matches('0').trim_end_matches('.'))
+}
+
+impl Region {
+    fn variable(&self) -> char {
+        if self.respect == Respect::X { 'x' } else { 'y' }
+    }
+
+    // (upper, lower) as strings of bank calls on the piece from points[i] to points[i+1]
+    fn sides(&self, bank: &Workspace, i: usize) -> (String, String) {
+        let middle = (self.points[i].value + self.points[i+1].value) / 2.0;
+        let (f, g) = (call(self.f), self.g.map_or(String::from("0"), call));
+        if difference(bank, self.f, self.g).approximate(bank, middle) >= 0.0 { (f, g) } else { (g, f) }
+    }
+
+    /// ∫ (upper - lower), piece by piece
+    pub fn area(&self, bank: &Workspace) -> Application {
+        self.set_up(bank, Kind::Area, ("1", ""), |upper, lower, _| format!("({})-({})", upper, lower))
+    }
+
+    /// The solid made by revolving the region around axis. An axis running along the slices'
+    /// variable (y = c for Respect::X) uses washers, or disks when the region touches it, and
+    /// one running across uses shells. None when the axis passes through the region
+    pub fn volume(&self, bank: &Workspace, axis: &Axis) -> Option<Application> {
+        let (c, along) = match (axis, self.respect) {
+            (Axis::Horizontal(c), Respect::X) | (Axis::Vertical(c), Respect::Y) => (c, true),
+            (Axis::Horizontal(c), Respect::Y) | (Axis::Vertical(c), Respect::X) => (c, false),
+        };
+        let k = c.approximate_with(bank, &[]);
+
+        if along {
+            // the axis has to sit on one side of every piece all the way across it,
+            // (top, bottom) at the 1/64ths of each piece with the middle first
+            let heights: Vec<Vec<(f64, f64)>> = (0..self.points.len() - 1).map(|i| {
+                let (upper, lower) = self.sides(bank, i);
+                let (upper, lower) = (expand(bank, &Expression::from(&upper)), expand(bank, &Expression::from(&lower)));
+                let (a, b) = (self.points[i].value, self.points[i+1].value);
+                std::iter::once(32).chain(1..64).map(|j| {
+                    let x = a + (b - a) * j as f64 / 64.0;
+                    (upper.approximate(bank, x), lower.approximate(bank, x))
+                }).collect()
+            }).collect();
+            if heights.iter().flatten().any(|(top, bottom)| k > bottom + 1e-9 && k < top - 1e-9) {
+                return None;
+            }
+            let touching = heights.iter().all(|piece| {
+                let (top, bottom) = piece[0];
+                (bottom - k).abs() < 1e-9 || (top - k).abs() < 1e-9
+            });
+            let kind = if touching { Kind::Disk } else { Kind::Washer };
+            Some(self.set_up(bank, kind, ("π", "π"), |upper, lower, middle| {
+                // R reaches the far curve and r the near one
+                let bottom = expand(bank, &Expression::from(lower)).approximate(bank, middle);
+                let (far, near) = if k <= bottom + 1e-9 { (upper, lower) } else { (lower, upper) };
+                format!("((({})-({}))^(2))-((({})-({}))^(2))", far, c, near, c)
+            }))
+        } else {
+            // the axis has to be left or right of the whole region
+            let (left, right) = (self.points[0].value, self.points[self.points.len() - 1].value);
+            if k > left + 1e-9 && k < right - 1e-9 {
+                return None;
+            }
+            // written in x like everything else until it is shown
+            let radius = if k <= left + 1e-9 { format!("(x)-({})", c) } else { format!("({})-(x)", c) };
+            Some(self.set_up(bank, Kind::Shell, ("2π", "2π"), |upper, lower, _| {
+                format!("({})((({})-({})))", radius, upper, lower)
+            }))
+        }
+    }
+
+    /// A solid whose cross sections across the region are shape
+    pub fn cross_section(&self, bank: &Workspace, shape: Shape) -> Application {
+        // the semicircle's radius is half the distance, (π/2)(s/2)^2
+        let coefficient = match shape {
+            Shape::Square => ("1", ""),
+            Shape::Semicircle => ("π/8", "π/8"),
+            Shape::EquilateralTriangle => ("(3^(1/2))/4", "√3/4"),
+        };
+        self.set_up(bank, Kind::CrossSection(shape), coefficient, |upper, lower, _| format!("(({})-({}))^(2)", upper, lower))
+    }
+
+    // coefficient times the sum over the pieces of ∫ integrand, where integrand gets the upper
+    // and lower curves and the middle of the piece. The label is how the coefficient is written
+    fn set_up<F: Fn(&str, &str, f64) -> String>(&self, bank: &Workspace, kind: Kind, (coefficient, label): (&str, &str), integrand: F) -> Application {
+        let v = self.variable();
+        let mut integrals = Vec::new();
+        let mut texts = Vec::new();
+        let mut values = Vec::new();
+
+        for i in 0..self.points.len() - 1 {
+            let (upper, lower) = self.sides(bank, i);
+            let (start, end) = (&self.points[i], &self.points[i+1]);
+            let middle = (start.value + end.value) / 2.0;
+            // worked out in x since that's what the bank functions take
+            let inside = simplify(&expand(bank, &Expression::from(&integrand(&upper, &lower, middle))));
+            let shown = if v == 'x' { copy_expression(&inside) } else { substitute(&inside, 'x', &Expression::Variable('y')) };
+
+            let bound_expression = |b: &Bound| b.exact.as_ref().map_or_else(|| Expression::from(&decimal(b.value)), copy_expression);
+            integrals.push(format!("(int({},{},{},({})))", v, bound_expression(start), bound_expression(end), shown));
+            texts.push(format!("∫_{}^{} {} d{}", start, end, shown, v));
+            values.push(match (&start.exact, &end.exact) {
+                (Some(a), Some(b)) => integral::integrate(bank, &inside, a, b),
+                _ => Definite {
+                    value: numeric::simpson(|x| inside.approximate(bank, x), start.value, end.value, 1000),
+                    exact: None,
+                    method: Method::Numeric,
+                },
+            });
+        }
+
+        let coefficient = simplify(&Expression::from(coefficient));
+        let scale = coefficient.approximate_with(bank, &[]);
+        let sum = texts.join(" + ");
+        let text = match (label, texts.len()) {
+            ("", _) => sum,
+            (label, 1) => format!("{} {}", label, sum),
+            (label, _) => format!("{} ({})", label, sum),
+        };
+        let exact = values.iter()
+            .map(|value| value.exact.as_ref().map(|e| format!("({})", e)))
+            .collect::<Option<Vec<String>>>()
+            .map(|parts| simplify(&Expression::from(&format!("({})({})", coefficient, parts.join("+")))));
+        let value = Definite {
+            value: scale * values.iter().map(|value| value.value).sum::<f64>(),
+            method: if exact.is_some() { Method::FundamentalTheorem } else { Method::Numeric },
+            exact,
+        };
+
+        Application {
+            kind,
+            integral: if label.is_empty() {
+                Expression::from(&integrals.join("+"))
+            } else {
+                Expression::from(&format!("({})({})", coefficient, integrals.join("+")))
+            },
+            text,
+            value,
+        }
+    }
+}
+
+/// The length of f from a to b, ∫ (1+f'(x)^2)^(1/2) dx
+pub fn arc_length(bank: &Workspace, f: char, a: &Expression, b: &Expression) -> Definite {
+    let slope = derivative::derivative(bank, &Expression::from(&call(f)));
+    scaled(bank, "1", &format!("(1+({})^(2))^(1/2)", slope), a, b)
+}
+
+/// The length of the curve (x(t), y(t)) from t = a to b, x and y are bank functions
+/// written in terms of x like the rest of the bank
+pub fn parametric_arc_length(bank: &Workspace, x: char, y: char, a: &Expression, b: &Expression) -> Definite {
+    let (dx, dy) = (derivative::derivative(bank, &Expression::from(&call(x))), derivative::derivative(bank, &Expression::from(&call(y))));
+    scaled(bank, "1", &format!("(({})^(2)+({})^(2))^(1/2)", dx, dy), a, b)
+}
+
+/// The average value of f on [a, b], (1/(b-a)) ∫ f(x) dx
+pub fn average_value(bank: &Workspace, f: char, a: &Expression, b: &Expression) -> Definite {
+    scaled(bank, &format!("1/(({})-({}))", b, a), &call(f), a, b)
+}
+
+/// The area of the surface made by revolving f from a to b around axis,
+/// 2π ∫ r (1+f'(x)^2)^(1/2) dx where r is the distance to the axis
+pub fn surface_area(bank: &Workspace, f: char, a: &Expression, b: &Expression, axis: &Axis) -> Definite {
+    let slope = derivative::derivative(bank, &Expression::from(&call(f)));
+    let middle = (a.approximate_with(bank, &[]) + b.approximate_with(bank, &[])) / 2.0;
+    // the curve stays on one side of the axis, which side is checked in the middle
+    let radius = match axis {
+        Axis::Horizontal(c) => {
+            let above = expand(bank, &Expression::from(&call(f))).approximate(bank, middle) >= c.approximate_with(bank, &[]);
+            if above { format!("({})-({})", call(f), c) } else { format!("({})-({})", c, call(f)) }
+        },
+        Axis::Vertical(c) => {
+            if middle >= c.approximate_with(bank, &[]) { format!("(x)-({})", c) } else { format!("({})-(x)", c) }
+        },
+    };
+    scaled(bank, "2π", &format!("({})((1+({})^(2))^(1/2))", radius, slope), a, b)
+}
+
+// coefficient times ∫ integrand from a to b, exact when the integral was
+fn scaled(bank: &Workspace, coefficient: &str, integrand: &str, a: &Expression, b: &Expression) -> Definite {
+    let integrand = simplify(&expand(bank, &Expression::from(integrand)));
+    let definite = integral::integrate(bank, &integrand, a, b);
+    let coefficient = simplify(&Expression::from(coefficient));
+    Definite {
+        value: coefficient.approximate_with(bank, &[]) * definite.value,
+        exact: definite.exact.map(|exact| simplify(&Expression::from(&format!("({})({})", coefficient, exact)))),
+        method: definite.method,
+    }
+}
@@ -0,0 +1,255 @@
+use super::*;
+use applications::Bound;
+use limits::Side;
+use simplify::simplify;
+
+// how close two limits have to be to count as the same
+const AGREE: f64 = 1e-6;
+
+// the most whole numbers a floor or ceil is checked for steps at
+const MAX_STEPS: f64 = 1000.0;
+
+/// The ways a function can fail to be continuous at a point
+#[derive(PartialEq)]
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Discontinuity {
+    // the limit exists but f is undefined there or has a different value
+    Removable,
+    // the one sided limits are finite and different
+    Jump,
+    // at least one side blows up, a vertical asymptote
+    Infinite,
+    // a side has no limit at all like sin(1/x) at 0
+    Oscillating,
+}
+
+/// A point where e isn't continuous. value is NaN or infinite where e is undefined
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point {
+    pub x: Bound,
+    pub kind: Discontinuity,
+    #[cfg_attr(feature = "serde", serde(with = "crate::schema::number"))]
+    pub left: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::schema::number"))]
+    pub right: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::schema::number"))]
+    pub value: f64,
+}
+
+/// Lines the graph of e gets close to, y = mx+b for Slant(m, b)
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Asymptote {
+    Vertical(Bound),
+    Horizontal(Bound),
+    Slant(Bound, Bound),
+}
+
+impl fmt::Display for Asymptote {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Asymptote::Vertical(x) => write!(f,"x = {}",x),
+            Asymptote::Horizontal(y) => write!(f,"y = {}",y),
+            Asymptote::Slant(m, b) => {
+                let slope = match m.to_string().as_str() {
+                    "1" => String::from("x"),
+                    "-1" => String::from("-x"),
+                    m => format!("{}x", m),
+                };
+                if b.value == 0.0 {
+                    write!(f,"y = {}",slope)
+                } else if b.value < 0.0 {
+                    write!(f,"y = {} - {}",slope,applications::bound(-b.value))
+                } else {
+                    write!(f,"y = {} + {}",slope,b)
+                }
+            },
+        }
+    }
+}
+
+fn agree(p: f64, q: f64) -> bool {
+    p == q || (p - q).abs() < AGREE * p.abs().max(1.0)
+}
+
+/// How e fails to be continuous at a, None when it is continuous there
+pub fn classify(bank: &Workspace, e: &Expression, a: f64) -> Option<Discontinuity> {
+    let e = simplify(&expand(bank, e));
+    let (left, right) = sides(bank, &e, a);
+    kind(left, right, e.approximate(bank, a))
+}
+
+// the one sided limits at a. An infinite limit has to come from |e| growing all the way in,
+// the extrapolation can mistake something like sin(1/x) for a blow up
+fn sides(bank: &Workspace, e: &Expression, a: f64) -> (f64, f64) {
+    let side = |side: Side, direction: f64| {
+        let limit = limits::limit(bank, e, a, side);
+        let sizes: Vec<f64> = (2..=8).map(|k| e.approximate(bank, a + direction * 10f64.powi(-k)).abs()).collect();
+        if limit.is_infinite() && !sizes.windows(2).all(|pair| pair[1] > pair[0]) { f64::NAN } else { limit }
+    };
+    (side(Side::Left, -1.0), side(Side::Right, 1.0))
+}
+
+// e is undefined on both sides of a, a is outside the domain rather than a discontinuity
+fn outside(bank: &Workspace, e: &Expression, a: f64) -> bool {
+    (3..=5).all(|k| [-1.0, 1.0].iter().all(|direction| e.approximate(bank, a + direction * 10f64.powi(-k)).is_nan()))
+}
+
+// the graph is the line itself out at the ends, like (x^2-1)/(x-1) and x+1
+fn on_line(f: &dyn Fn(f64) -> f64, m: f64, b: f64, infinity: f64) -> bool {
+    [10.0, 20.0].iter().all(|x| {
+        let x = infinity.signum() * x;
+        (f(x) - (m * x + b)).abs() < 1e-12 * f(x).abs().max(1.0)
+    })
+}
+
+fn kind(left: f64, right: f64, value: f64) -> Option<Discontinuity> {
+    if left.is_infinite() || right.is_infinite() {
+        Some(Discontinuity::Infinite)
+    } else if left.is_nan() || right.is_nan() {
+        Some(Discontinuity::Oscillating)
+    } else if !agree(left, right) {
+        Some(Discontinuity::Jump)
+    } else if !value.is_finite() || !agree(left, value) {
+        Some(Discontinuity::Removable)
+    } else {
+        None
+    }
+}
+
+/// Whether e is continuous at a: defined there, with a limit equal to the value
+pub fn continuous_at(bank: &Workspace, e: &Expression, a: f64) -> bool {
+    classify(bank, e, a).is_none()
+}
+
+// the places strictly between low and high where e can break: where it blows up or is
+// undefined, where a piecewise e switches pieces and where floor and ceil step
+fn candidates(bank: &Workspace, e: &Expression, low: f64, high: f64) -> Vec<f64> {
+    let mut found = integral::singularities(bank, e, 'x', low, high);
+    found.extend(piecewise::breaks(bank, e, 'x').into_iter().map(|x| x.value).filter(|x| *x > low && *x < high));
+    steps(bank, e, low, high, &mut found);
+    found.sort_by(f64::total_cmp);
+    found.dedup_by(|a, b| (*a - *b).abs() < 1e-9);
+    found
+}
+
+// where the argument of a floor or ceil in e is a whole number
+fn steps(bank: &Workspace, e: &Expression, low: f64, high: f64, found: &mut Vec<f64>) {
+    let Expression::Equa(equation) = e else { return };
+    if matches!(equation.operation, Operation::Floor | Operation::Ceil) {
+        let g = |x: f64| equation.element2.approximate(bank, x);
+        // infinite ends get a finite stretch scanned, like singularities does
+        let scan_low = if low.is_finite() { low } else { high.min(0.0) - 100.0 };
+        let scan_high = if high.is_finite() { high } else { low.max(0.0) + 100.0 };
+        let values: Vec<f64> = (0..=1000).map(|i| g(scan_low + (scan_high - scan_low) * i as f64 / 1000.0)).filter(|y| y.is_finite()).collect();
+        let least = values.iter().copied().fold(f64::INFINITY, f64::min).ceil();
+        let most = values.iter().copied().fold(f64::NEG_INFINITY, f64::max).floor();
+        if least <= most && most - least < MAX_STEPS {
+            for k in (least as i64)..=(most as i64) {
+                found.extend(numeric::roots(|x| g(x) - k as f64, scan_low, scan_high).into_iter()
+                    .map(|c| limits::exact_value(c, 1e-9).map_or(c, |exact| exact.approximate(bank, 0.0)))
+                    .filter(|c| *c > low && *c < high));
+            }
+        }
+    }
+    steps(bank, &equation.element1, low, high, found);
+    steps(bank, &equation.element2, low, high, found);
+}
+
+// where the argument of an abs in e is 0, the corners it can put in a graph
+fn corners(bank: &Workspace, e: &Expression, low: f64, high: f64, found: &mut Vec<f64>) {
+    let Expression::Equa(equation) = e else { return };
+    if equation.operation == Operation::Abs {
+        found.extend(numeric::roots(|x| equation.element2.approximate(bank, x), low, high).into_iter().filter(|c| *c > low && *c < high));
+    }
+    corners(bank, &equation.element1, low, high, found);
+    corners(bank, &equation.element2, low, high, found);
+}
+
+/// Whether e is continuous on [low, high]: no discontinuities in between, and defined at
+/// each end with the limit from inside equal to the value there
+pub fn continuous_on(bank: &Workspace, e: &Expression, low: f64, high: f64) -> bool {
+    let e = simplify(&expand(bank, e));
+    let end = |a: f64, side: Side| {
+        let value = e.approximate(bank, a);
+        value.is_finite() && agree(limits::limit(bank, &e, a, side), value)
+    };
+    end(low, Side::Right) && end(high, Side::Left) && discontinuities(bank, &e, low, high).is_empty()
+}
+
+/// Whether e is differentiable on (low, high): continuous there, every piecewise breakpoint
+/// differentiable, and the one sided limits of e' finite and equal wherever e could break
+/// and at the corners of abs
+pub fn differentiable_on(bank: &Workspace, e: &Expression, low: f64, high: f64) -> bool {
+    let e = simplify(&expand(bank, e));
+    if !discontinuities(bank, &e, low, high).is_empty() {
+        return false;
+    }
+    if piecewise::breakpoints(bank, &e).iter().any(|point| point.x.value > low && point.x.value < high && !point.differentiable) {
+        return false;
+    }
+    let slope = derivative::derivative(bank, &e);
+    let mut points = candidates(bank, &e, low, high);
+    points.extend(candidates(bank, &slope, low, high));
+    corners(bank, &e, low, high, &mut points);
+    points.into_iter().all(|c| {
+        let (left, right) = (limits::limit(bank, &slope, c, Side::Left), limits::limit(bank, &slope, c, Side::Right));
+        left.is_finite() && right.is_finite() && agree(left, right)
+    })
+}
+
+/// The discontinuities of e strictly between low and high. Candidates are the zeros of
+/// denominators, log arguments and cos under tan, anything undefined on a grid, the
+/// breakpoints of piecewise functions and the steps of floor and ceil, each is classified
+/// with one sided limits
+pub fn discontinuities(bank: &Workspace, e: &Expression, low: f64, high: f64) -> Vec<Point> {
+    let e = simplify(&expand(bank, e));
+    candidates(bank, &e, low, high).into_iter()
+        .filter(|a| !outside(bank, &e, *a))
+        .filter_map(|a| {
+            let (left, right) = sides(bank, &e, a);
+            let value = e.approximate(bank, a);
+            kind(left, right, value).map(|kind| Point { x: applications::bound(a), kind, left, right, value })
+        })
+        .collect()
+}
+
+/// The vertical asymptotes between low and high and the horizontal or slant asymptotes at
+/// ±inf. An end with no asymptote is left out and both ends sharing one only list it once
+pub fn asymptotes(bank: &Workspace, e: &Expression, low: f64, high: f64) -> Vec<Asymptote> {
+    let e = simplify(&expand(bank, e));
+    let mut found: Vec<Asymptote> = discontinuities(bank, &e, low, high).into_iter()
+        .filter(|point| point.kind == Discontinuity::Infinite)
+        .map(|point| Asymptote::Vertical(point.x))
+        .collect();
+
+    let f = |x: f64| e.approximate(bank, x);
+    let mut ends: Vec<(f64, f64)> = Vec::new();
+    for infinity in [f64::NEG_INFINITY, f64::INFINITY] {
+        let end = limits::limit_of(f, infinity, Side::Both);
+        let line = if end.is_finite() {
+            Some((0.0, end))
+        } else if end.is_infinite() {
+            // y = mx+b with m = lim f(x)/x and b = lim f(x)-mx
+            let m = limits::limit_of(|x| f(x) / x, infinity, Side::Both);
+            let b = limits::limit_of(|x| f(x) - m * x, infinity, Side::Both);
+            if m.is_finite() && m != 0.0 && b.is_finite() { Some((m, b)) } else { None }
+        } else {
+            None
+        };
+        if let Some((m, b)) = line.filter(|(m, b)| !on_line(&f, *m, *b, infinity)) {
+            if !ends.iter().any(|(m2, b2)| agree(m, *m2) && agree(b, *b2)) {
+                ends.push((m, b));
+            }
+        }
+    }
+    found.extend(ends.into_iter().map(|(m, b)| {
+        if m == 0.0 { Asymptote::Horizontal(applications::bound(b)) } else { Asymptote::Slant(applications::bound(m), applications::bound(b)) }
+    }));
+    found
+}
@@ -0,0 +1,145 @@
+use super::*;
+use applications::Bound;
+use derivative::derivative;
+use integral::Definite;
+use simplify::simplify;
+
+/// A curve (x(t), y(t)). Like the bank, the parameter is written as x
+#[derive(Debug)]
+pub struct ParametricCurve {
+    pub x: Expression,
+    pub y: Expression,
+}
+
+/// A curve r(θ), θ is written as x
+#[derive(Debug)]
+pub struct PolarCurve {
+    pub r: Expression,
+}
+
+/// Where a curve has a horizontal or vertical tangent, t is the parameter and point is (x, y)
+#[derive(Debug)]
+pub struct Tangent {
+    pub t: Bound,
+    pub point: (f64, f64),
+}
+
+impl ParametricCurve {
+    /// The curve traced by bank functions x and y
+    pub fn new(bank: &Workspace, x: char, y: char) -> ParametricCurve {
+        ParametricCurve {
+            x: expand(bank, &Expression::from(&format!("{}(x)", x))),
+            y: expand(bank, &Expression::from(&format!("{}(x)", y))),
+        }
+    }
+
+    /// (x(t), y(t))
+    pub fn point(&self, bank: &Workspace, t: f64) -> (f64, f64) {
+        (self.x.approximate(bank, t), self.y.approximate(bank, t))
+    }
+
+    /// dy/dx = (dy/dt)/(dx/dt)
+    pub fn dydx(&self, bank: &Workspace) -> Expression {
+        simplify(&Expression::from(&format!("({})/({})", derivative(bank, &self.y), derivative(bank, &self.x))))
+    }
+
+    /// d²y/dx² = (d/dt dy/dx)/(dx/dt)
+    pub fn second_derivative(&self, bank: &Workspace) -> Expression {
+        let slope = self.dydx(bank);
+        simplify(&Expression::from(&format!("({})/({})", derivative(bank, &slope), derivative(bank, &self.x))))
+    }
+
+    /// Where dy/dt = 0 and dx/dt isn't for t in [a, b]
+    pub fn horizontal_tangents(&self, bank: &Workspace, a: f64, b: f64) -> Vec<Tangent> {
+        self.tangents(bank, &derivative(bank, &self.y), &derivative(bank, &self.x), a, b)
+    }
+
+    /// Where dx/dt = 0 and dy/dt isn't for t in [a, b]
+    pub fn vertical_tangents(&self, bank: &Workspace, a: f64, b: f64) -> Vec<Tangent> {
+        self.tangents(bank, &derivative(bank, &self.x), &derivative(bank, &self.y), a, b)
+    }
+
+    // both being 0 leaves the slope undetermined so those are skipped
+    fn tangents(&self, bank: &Workspace, zero: &Expression, other: &Expression, a: f64, b: f64) -> Vec<Tangent> {
+        numeric::roots(|t| zero.approximate(bank, t), a, b).into_iter()
+            .filter(|t| other.approximate(bank, *t).abs() > 1e-6)
+            .map(|t| {
+                let t = applications::bound(t);
+                let point = self.point(bank, t.value);
+                Tangent { t, point }
+            })
+            .collect()
+    }
+
+    /// The speed ((dx/dt)^2+(dy/dt)^2)^(1/2)
+    pub fn speed(&self, bank: &Workspace) -> Expression {
+        simplify(&Expression::from(&format!("(({})^(2)+({})^(2))^(1/2)", derivative(bank, &self.x), derivative(bank, &self.y))))
+    }
+
+    /// The distance travelled along the curve from t = a to b, ∫ speed dt
+    pub fn arc_length(&self, bank: &Workspace, a: &Expression, b: &Expression) -> Definite {
+        integral::integrate(bank, &self.speed(bank), a, b)
+    }
+}
+
+impl PolarCurve {
+    /// The curve traced by bank function r
+    pub fn new(bank: &Workspace, r: char) -> PolarCurve {
+        PolarCurve { r: expand(bank, &Expression::from(&format!("{}(x)", r))) }
+    }
+
+    /// The same curve as x = r cos(θ), y = r sin(θ)
+    pub fn parametric(&self) -> ParametricCurve {
+        ParametricCurve {
+            x: Expression::from(&format!("({})(cos(x))", self.r)),
+            y: Expression::from(&format!("({})(sin(x))", self.r)),
+        }
+    }
+
+    /// dy/dx = (r'sin(θ)+r cos(θ))/(r'cos(θ)-r sin(θ))
+    pub fn dydx(&self, bank: &Workspace) -> Expression {
+        self.parametric().dydx(bank)
+    }
+
+    /// d²y/dx²
+    pub fn second_derivative(&self, bank: &Workspace) -> Expression {
+        self.parametric().second_derivative(bank)
+    }
+
+    /// Where dy/dθ = 0 and dx/dθ isn't for θ in [a, b]
+    pub fn horizontal_tangents(&self, bank: &Workspace, a: f64, b: f64) -> Vec<Tangent> {
+        self.parametric().horizontal_tangents(bank, a, b)
+    }
+
+    /// Where dx/dθ = 0 and dy/dθ isn't for θ in [a, b]
+    pub fn vertical_tangents(&self, bank: &Workspace, a: f64, b: f64) -> Vec<Tangent> {
+        self.parametric().vertical_tangents(bank, a, b)
+    }
+
+    /// The speed (r^2+(dr/dθ)^2)^(1/2), the same as the parametric speed but easier to integrate
+    pub fn speed(&self, bank: &Workspace) -> Expression {
+        simplify(&Expression::from(&format!("(({})^(2)+({})^(2))^(1/2)", self.r, derivative(bank, &self.r))))
+    }
+
+    /// The length of the curve from θ = a to b
+    pub fn arc_length(&self, bank: &Workspace, a: &Expression, b: &Expression) -> Definite {
+        integral::integrate(bank, &self.speed(bank), a, b)
+    }
+
+    /// The area swept out from θ = a to b, (1/2)∫ r^2 dθ
+    pub fn area(&self, bank: &Workspace, a: &Expression, b: &Expression) -> Definite {
+        integral::integrate(bank, &Expression::from(&format!("(1/2)(({})^(2))", self.r)), a, b)
+    }
+
+    /// The area outside inner and inside self from θ = a to b, (1/2)∫ (R^2-r^2) dθ
+    pub fn area_between(&self, bank: &Workspace, inner: &PolarCurve, a: &Expression, b: &Expression) -> Definite {
+        integral::integrate(bank, &Expression::from(&format!("(1/2)((({})^(2))-(({})^(2)))", self.r, inner.r)), a, b)
+    }
+
+    /// The θ in [a, b] where the two curves meet with the same θ, the pole isn't included
+    pub fn intersections(&self, bank: &Workspace, other: &PolarCurve, a: f64, b: f64) -> Vec<Bound> {
+        numeric::roots(|t| self.r.approximate(bank, t) - other.r.approximate(bank, t), a, b).into_iter()
+            .map(applications::bound)
+            .collect()
+    }
+}
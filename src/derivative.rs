@@ -2,10 +2,21 @@ use super::*;
 
 pub fn ddx(expression: &Expression) -> Expression {
     if let Expression::Equa(equation) = expression {
-        let fs = format!("{}",equation.element1);
-        let gs = format!("{}",equation.element2);
-        let f = Expression::from(&fs);
-        let g = Expression::from(&gs);
+        // the bounds of a sum don't round trip on their own so sums go term by term here
+        if let Operation::Sum(n) = equation.operation {
+            return Expression::from(&format!("(sum({n},{},({})))", equation.element1, ddx(&equation.element2)));
+        }
+        if let Operation::Integral(t) = equation.operation {
+            return accumulation(t, &equation.element1, &equation.element2);
+        }
+        // piece by piece, the conditions stay as they are
+        match equation.operation {
+            Operation::Piecewise => return equa(Operation::Piecewise, ddx(&equation.element1), ddx(&equation.element2)),
+            Operation::Piece => return equa(Operation::Piece, copy_expression(&equation.element1), ddx(&equation.element2)),
+            _ => (),
+        }
+        let f = copy_expression(&equation.element1);
+        let g = copy_expression(&equation.element2);
         let fp = ddx(&f);
         let gp = ddx(&g);
         //println!("====g'&f' test======\nf' = {fp}\ng' = {gp}\n====================");
@@ -21,16 +32,22 @@ pub fn ddx(expression: &Expression) -> Expression {
                 format!("((({g})({fp}))-(({f})({gp})))/(({g})^2)"),
             Operation::Func =>
                 format!("({fp}({g}))({gp})"),
+            // the power and exponential rules on their own keep ln of a negative base out of x^2
+            Operation::Exp if !contains(&g, 'x') =>
+               format!("(({g})(({f})^(({g})-(1))))({fp})"),
+            Operation::Exp if !contains(&f, 'x') =>
+               format!("((({f})^({g}))(ln({f})))({gp})"),
             Operation::Exp =>
                format!("(({f})^({g}))((({gp})(ln({f})))+((({fp})({g}))/({f})))"),
             Operation::Trig =>
                 if let Expression::Variable(name) = f {
                     match name {
-                        's' => format!("((cos({g}))({gp})))"),
-                        'c' => format!("(0-(sin({g})))({gp})))"),
-                        't' => format!("((1/(cos({g})^2))({gp})))"),
-                        'S' => format!("((1/((1-({g})^2))))^(1/2))({gp})))"),
-                        'T' => format!("((1/(1+(({g})^2)))({gp})))"),
+                        's' => format!("(cos({g}))({gp})"),
+                        'c' => format!("(0-(sin({g})))({gp})"),
+                        't' => format!("((1)/((cos({g}))^(2)))({gp})"),
+                        'S' => format!("((1)/(((1)-(({g})^(2)))^(1/2)))({gp})"),
+                        'C' => format!("(0-((1)/(((1)-(({g})^(2)))^(1/2))))({gp})"),
+                        'T' => format!("((1)/((1)+(({g})^(2))))({gp})"),
                         _ => panic!("wrong!!!!"),
                     }
                 } else {
@@ -38,16 +55,28 @@ pub fn ddx(expression: &Expression) -> Expression {
                 },
             Operation::Log => 
                 match f {
-                    Expression::Variable('e') => format!("(1/({g}))({gp}))"),
-                    Expression::Variable(a) => format!("(1/(({g})(ln({a})))({gp}))"),
-                    Expression::Constant(a) => format!("(1/(({g})(ln({a})))({gp}))"),
+                    Expression::Variable('e') => format!("((1)/({g}))({gp})"),
+                    Expression::Variable(a) => format!("((1)/(({g})(ln({a}))))({gp})"),
+                    Expression::Constant(a) => format!("((1)/(({g})(ln({a}))))({gp})"),
                     _ => panic!("OOPS, broken in log of ddx"),
                 },
             Operation::Deriv => 
                 format!("{f}''"),
+            // g/|g| is sign(g), which like the derivative doesn't exist where g = 0
+            Operation::Abs =>
+                format!("(({g})/(|{g}|))({gp})"),
+            // flat between the jumps and undefined on them, where g is a whole number
+            Operation::Floor | Operation::Ceil =>
+                format!("{{0 if floor({g}) < {g}}}"),
+            Operation::Root =>
+                return ddx(&equa(Operation::Exp, g, Expression::from(&format!("(1)/({f})")))),
             _ => panic!("Operation not covered by ddx in match expression"),
         };
         Expression::from(&s)
+    } else if let Expression::Constant(_) = expression {
+        Expression::from("0")
+    } else if *expression == Expression::Variable('x') {
+        Expression::from("1")
     } else{
         let es = format!("{}",expression);
         let e  = Expression::from(&es);
@@ -59,7 +88,8 @@ pub fn ddx(expression: &Expression) -> Expression {
     }
 }
 
-pub fn eval_deriv(exp: Expression, bank: &Bank, x: Fraction) -> Expression {
+#[allow(clippy::only_used_in_recursion)]
+pub fn eval_deriv(exp: Expression, bank: &Workspace, x: Fraction) -> Expression {
     // exp = element1  of prev what you came from
    
     let exp_cpy = copy_expression(&exp);
@@ -72,16 +102,57 @@ pub fn eval_deriv(exp: Expression, bank: &Bank, x: Fraction) -> Expression {
     match exp {
         Expression::Constant(_c)  => Expression::from("0"),
         Expression::Variable('x') => Expression::from("1"),
-        Expression::Variable(f)   => ddx(&get_expression(bank, &exp_cpy)),
+        // letters missing from the bank (a sum index, e, π) are constants
+        Expression::Variable(f) if !bank.contains(&f) => Expression::from("0"),
+        Expression::Variable(_f)  => ddx(&get_expression(bank, &exp_cpy)),
         Expression::Equa(e)       => {
-            let e1_cpy = copy_expression(&e.element1);
             if e.operation == Operation::Deriv {
+                let e1_cpy = copy_expression(&e.element1);
                 eval_deriv(eval_deriv(e1_cpy,bank,x),bank,x)
             } else {
                 ddx(&exp_cpy) 
             }
         },
-        _ => panic!("its in the match of the eval_deriv")
     }
 }
 
+
+// FTC part 1, d/dx ∫_a(x)^b(x) f(t) dt = f(b(x))b'(x) - f(a(x))a'(x)
+fn accumulation(t: char, bounds: &Expression, integrand: &Expression) -> Expression {
+    let (lower, upper) = match bounds {
+        Expression::Equa(bounds) => (&bounds.element1, &bounds.element2),
+        _ => panic!("an integral's first element is its bounds"),
+    };
+    Expression::from(&format!("(({})({}))-(({})({}))",
+        substitute(integrand, t, upper), ddx(upper), substitute(integrand, t, lower), ddx(lower)))
+}
+
+/// The derivative of e with bank functions written out and simplified, letters other than
+/// x are treated as constants. This is the one the applications want, ddx leaves f' nodes
+/// for the bank to fill in later
+pub fn derivative(bank: &Workspace, e: &Expression) -> Expression {
+    let e = expand(bank, e);
+    let slope = simplify::simplify(&resolve(&ddx(&e)));
+    // a piecewise derivative doesn't exist at corners and jumps
+    if contains_piece(&slope) { piecewise::corners_removed(bank, &e, &slope) } else { slope }
+}
+
+fn contains_piece(e: &Expression) -> bool {
+    match e {
+        Expression::Equa(equation) => matches!(equation.operation, Operation::Piecewise | Operation::Piece)
+            || contains_piece(&equation.element1) || contains_piece(&equation.element2),
+        _ => false,
+    }
+}
+
+// the f' nodes ddx leaves for letters, which are all constants once expanded
+fn resolve(e: &Expression) -> Expression {
+    match e {
+        Expression::Equa(equation) if equation.operation == Operation::Deriv => match &*equation.element1 {
+            Expression::Variable(_) | Expression::Constant(_) => Expression::from("0"),
+            inner => resolve(&ddx(inner)),
+        },
+        Expression::Equa(equation) => equa(equation.operation, resolve(&equation.element1), resolve(&equation.element2)),
+        _ => copy_expression(e),
+    }
+}
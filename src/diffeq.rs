@@ -0,0 +1,473 @@
+use super::*;
+use integral::antiderivative;
+use simplify::simplify;
+
+/// One segment of a slope field
+#[derive(Debug)]
+pub struct Slope {
+    pub x: f64,
+    pub y: f64,
+    pub slope: f64,
+}
+
+/// One row of the tables AP questions ask for, slope is the one used to take the step
+/// so Δy = slope * (x_(n+1) - x_n)
+#[derive(Debug)]
+pub struct Step {
+    pub n: usize,
+    pub x: f64,
+    pub y: f64,
+    pub slope: f64,
+    pub dy: f64,
+}
+
+fn slope_at(bank: &Workspace, dydx: &Expression, x: f64, y: f64) -> f64 {
+    dydx.approximate_with(bank, &[('x', x), ('y', y)])
+}
+
+/// dy/dx sampled on every (x, y) of the grid, ranges are inclusive
+pub fn slope_field(bank: &Workspace, dydx: &Expression, x_range: (f64, f64), y_range: (f64, f64), step: f64) -> Vec<Slope> {
+    let count = |(low, high): (f64, f64)| ((high - low) / step + 1e-9).floor() as usize + 1;
+    let mut field = Vec::new();
+
+    for i in 0..count(x_range) {
+        let x = x_range.0 + step * i as f64;
+        for j in 0..count(y_range) {
+            let y = y_range.0 + step * j as f64;
+            field.push(Slope { x, y, slope: slope_at(bank, dydx, x, y) });
+        }
+    }
+    field
+}
+
+/// Euler's method, the last row is the approximation at x0 + steps*h and has no slope
+pub fn euler(bank: &Workspace, dydx: &Expression, x0: f64, y0: f64, h: f64, steps: usize) -> Vec<Step> {
+    fixed_steps(x0, y0, h, steps, |x, y| slope_at(bank, dydx, x, y))
+}
+
+/// Classic fourth order Runge-Kutta, slope is the weighted average (k1+2k2+2k3+k4)/6
+pub fn rk4(bank: &Workspace, dydx: &Expression, x0: f64, y0: f64, h: f64, steps: usize) -> Vec<Step> {
+    fixed_steps(x0, y0, h, steps, |x, y| {
+        let k1 = slope_at(bank, dydx, x, y);
+        let k2 = slope_at(bank, dydx, x + h / 2.0, y + h * k1 / 2.0);
+        let k3 = slope_at(bank, dydx, x + h / 2.0, y + h * k2 / 2.0);
+        let k4 = slope_at(bank, dydx, x + h, y + h * k3);
+        (k1 + 2.0 * k2 + 2.0 * k3 + k4) / 6.0
+    })
+}
+
+fn fixed_steps<F: Fn(f64, f64) -> f64>(x0: f64, y0: f64, h: f64, steps: usize, slope: F) -> Vec<Step> {
+    let mut table = Vec::new();
+    let mut y = y0;
+
+    for n in 0..steps {
+        // x from n*h rather than adding h up keeps 0.1 steps from drifting
+        let x = x0 + h * n as f64;
+        let m = slope(x, y);
+        table.push(Step { n, x, y, slope: m, dy: h * m });
+        y += h * m;
+    }
+    table.push(Step { n: steps, x: x0 + h * steps as f64, y, slope: f64::NAN, dy: f64::NAN });
+    table
+}
+
+// Runge-Kutta-Fehlberg coefficients
+const A: [[f64; 5]; 5] = [
+    [1.0/4.0, 0.0, 0.0, 0.0, 0.0],
+    [3.0/32.0, 9.0/32.0, 0.0, 0.0, 0.0],
+    [1932.0/2197.0, -7200.0/2197.0, 7296.0/2197.0, 0.0, 0.0],
+    [439.0/216.0, -8.0, 3680.0/513.0, -845.0/4104.0, 0.0],
+    [-8.0/27.0, 2.0, -3544.0/2565.0, 1859.0/4104.0, -11.0/40.0],
+];
+const C: [f64; 5] = [1.0/4.0, 3.0/8.0, 12.0/13.0, 1.0, 1.0/2.0];
+const FOURTH: [f64; 6] = [25.0/216.0, 0.0, 1408.0/2565.0, 2197.0/4104.0, -1.0/5.0, 0.0];
+const FIFTH: [f64; 6] = [16.0/135.0, 0.0, 6656.0/12825.0, 28561.0/56430.0, -9.0/50.0, 2.0/55.0];
+
+/// Adaptive Runge-Kutta-Fehlberg 4(5) from x0 to x_end, each step is shrunk or grown so
+/// its local error estimate stays under tolerance
+pub fn rk45(bank: &Workspace, dydx: &Expression, x0: f64, y0: f64, x_end: f64, tolerance: f64) -> Vec<Step> {
+    let mut table = Vec::new();
+    let (mut x, mut y) = (x0, y0);
+    let mut h = (x_end - x0) / 10.0;
+
+    while (x_end - x) * h.signum() > 1e-12 {
+        if (x + h - x_end) * h.signum() > 0.0 {
+            h = x_end - x;
+        }
+        let mut k = [0.0; 6];
+        k[0] = slope_at(bank, dydx, x, y);
+        for i in 1..6 {
+            let y_i = y + h * (0..i).map(|j| A[i-1][j] * k[j]).sum::<f64>();
+            k[i] = slope_at(bank, dydx, x + C[i-1] * h, y_i);
+        }
+        let fourth: f64 = (0..6).map(|i| FOURTH[i] * k[i]).sum();
+        let fifth: f64 = (0..6).map(|i| FIFTH[i] * k[i]).sum();
+        let error = (h * (fifth - fourth)).abs();
+
+        if error <= tolerance || h.abs() < 1e-10 {
+            table.push(Step { n: table.len(), x, y, slope: fifth, dy: h * fifth });
+            x += h;
+            y += h * fifth;
+        }
+        let scale = if error == 0.0 { 5.0 } else { 0.9 * (tolerance / error).powf(0.2) };
+        h *= scale.clamp(0.2, 5.0);
+    }
+    table.push(Step { n: table.len(), x, y, slope: f64::NAN, dy: f64::NAN });
+    table
+}
+
+// enough digits for an FRQ without showing float noise
+fn tidy(value: f64) -> String {
+    if value.is_nan() {
+        return String::new();
+    }
+    let rounded = format!("{:.6}", value);
+    let rounded = rounded.trim_end_matches('0').trim_end_matches('.');
+    if rounded == "-0" { String::from("0") } else { String::from(rounded) }
+}
+
+/// The table as text, one row per step with columns n, x_n, y_n, slope and Δy
+pub fn table(steps: &[Step]) -> String {
+    let mut text = format!("{:>4} {:>12} {:>12} {:>12} {:>12}\n", "n", "x_n", "y_n", "slope", "Δy");
+    for step in steps {
+        text += &format!("{:>4} {:>12} {:>12} {:>12} {:>12}\n",
+            step.n, tidy(step.x), tidy(step.y), tidy(step.slope), tidy(step.dy));
+    }
+    text
+}
+
+/// The closed forms recognized before falling back to integrating both sides
+#[derive(Debug)]
+pub enum Family {
+    /// dy/dx = ky, y = y0e^(k(x-x0))
+    Exponential { k: Expression },
+    /// dy/dx = ry(1-y/L) or ky(L-y) (where r = kL), y = L/(1+((L-y0)/y0)e^(-r(x-x0)))
+    Logistic { rate: Expression, capacity: Expression },
+    General,
+}
+
+/// dy/dx = g(x)h(y) separated and integrated as left = right + constant where left is ∫1/h dy
+/// and right is ∫g dx, explicit is y written out when it could be isolated
+#[derive(Debug)]
+pub struct Separable {
+    pub x: char,
+    pub y: char,
+    pub x0: Fraction,
+    pub y0: Fraction,
+    pub g: Expression,
+    pub h: Expression,
+    pub left: Expression,
+    pub right: Expression,
+    pub constant: Expression,
+    pub explicit: Option<Expression>,
+    pub family: Family,
+}
+
+/// What the AP questions ask about a logistic model
+#[derive(Debug)]
+pub struct LogisticAnalysis {
+    pub capacity: f64,
+    /// lim x->inf of y, the capacity unless the model starts at 0
+    pub limit: f64,
+    /// y is growing fastest when it is half the capacity
+    pub fastest_at: f64,
+    /// the rate at that point, rL/4
+    pub max_rate: f64,
+    /// when y reaches L/2, None if it never does
+    pub time_of_fastest: Option<f64>,
+}
+
+impl Separable {
+    /// left = right + C as text
+    pub fn implicit(&self) -> String {
+        format!("{} = {} + {}", self.left, self.right, self.constant)
+    }
+
+    /// Whether an exponential model grows (k > 0) or decays, None for the other families
+    pub fn is_growth(&self, bank: &Workspace) -> Option<bool> {
+        match &self.family {
+            Family::Exponential { k } if known(bank, k, &[]) => Some(k.approximate_with(bank, &[]) > 0.0),
+            _ => None,
+        }
+    }
+
+    /// Carrying capacity analysis, None unless the equation is logistic with known numbers
+    pub fn logistic_analysis(&self, bank: &Workspace) -> Option<LogisticAnalysis> {
+        let (rate, capacity) = match &self.family {
+            Family::Logistic { rate, capacity } if known(bank, rate, &[]) && known(bank, capacity, &[]) =>
+                (rate.approximate_with(bank, &[]), capacity.approximate_with(bank, &[])),
+            _ => return None,
+        };
+        let (x0, y0) = (self.x0.to_f64()?, self.y0.to_f64()?);
+        let time_of_fastest = if y0 > 0.0 && y0 < capacity {
+            Some(x0 + ((capacity - y0) / y0).ln() / rate)
+        } else {
+            None
+        };
+
+        Some(LogisticAnalysis {
+            capacity,
+            limit: if y0 == 0.0 { 0.0 } else { capacity },
+            fastest_at: capacity / 2.0,
+            max_rate: rate * capacity / 4.0,
+            time_of_fastest,
+        })
+    }
+}
+
+/// Solves dy/dx = g(x)h(y) with y(x0) = y0, None when it doesn't separate or a side can't be integrated
+pub fn solve_separable(bank: &Workspace, dydx: &Expression, x0: Fraction, y0: Fraction) -> Option<Separable> {
+    solve_separable_in(bank, dydx, ('x', 'y'), x0, y0)
+}
+
+/// solve_separable with other letters, ('t', 'P') for dP/dt
+pub fn solve_separable_in(bank: &Workspace, dydx: &Expression, (x, y): (char, char), x0: Fraction, y0: Fraction) -> Option<Separable> {
+    let (g, h) = separate(bank, dydx, x, y)?;
+    let (g, h) = (simplify(&g), simplify(&h));
+    let family = if contains(&g, x) { Family::General } else { family(bank, &g, &h, y) };
+
+    let (left, right, explicit) = match &family {
+        Family::Exponential { k } => (
+            parse(format!("ln({})", y)),
+            simplify(&parse(format!("({})({})", k, x))),
+            Some(parse(format!("({})((e)^(({})(({})-({}))))", y0, k, x, x0))),
+        ),
+        Family::Logistic { rate, capacity } => (
+            parse(format!("ln(({})/(({})-({})))", y, capacity, y)),
+            simplify(&parse(format!("({})({})", rate, x))),
+            Some(if y0 == Fraction::from(0) {
+                Expression::from("0")
+            } else {
+                parse(format!("({L})/((1)+((({L})-({y0}))/({y0}))((e)^((0-({r}))(({x})-({x0})))))",
+                    L = capacity, y0 = y0, r = rate, x = x, x0 = x0))
+            }),
+        ),
+        Family::General => {
+            let left = antiderivative(&parse(format!("(1)/({})", h)), y)?;
+            let right = antiderivative(&g, x)?;
+            (left, right, None)
+        },
+    };
+
+    let at_start = parse(format!("({})-({})",
+        substitute(&left, y, &Expression::Constant(y0)), substitute(&right, x, &Expression::Constant(x0))));
+    let constant = simplify(&at_start);
+    let explicit = match explicit {
+        Some(explicit) => Some(simplify(&explicit)),
+        None => explicit_solution(bank, &left, &right, &constant, (x, y), x0, y0),
+    };
+
+    Some(Separable { x, y, x0, y0, g, h, left, right, constant, explicit, family })
+}
+
+fn parse(s: String) -> Expression {
+    Expression::from(&s)
+}
+
+// dy/dx = g(x)h(y) as (g, h)
+fn separate(bank: &Workspace, e: &Expression, x: char, y: char) -> Option<(Expression, Expression)> {
+    if !contains(e, y) {
+        return Some((copy_expression(e), Expression::from("1")));
+    }
+    if !contains(e, x) {
+        return Some((Expression::from("1"), copy_expression(e)));
+    }
+    let equation = match e {
+        Expression::Equa(equation) => equation,
+        _ => return None,
+    };
+    let (a, b) = (&equation.element1, &equation.element2);
+
+    match equation.operation {
+        Operation::Mult | Operation::Div => {
+            let ((g1, h1), (g2, h2)) = (separate(bank, a, x, y)?, separate(bank, b, x, y)?);
+            let join = if equation.operation == Operation::Mult { "" } else { "/" };
+            Some((parse(format!("({}){}({})", g1, join, g2)), parse(format!("({}){}({})", h1, join, h2))))
+        },
+        Operation::Func if !is_function(bank, a) => {
+            let ((g1, h1), (g2, h2)) = (separate(bank, a, x, y)?, separate(bank, b, x, y)?);
+            Some((parse(format!("({})({})", g1, g2)), parse(format!("({})({})", h1, h2))))
+        },
+        // a^(p(x)+q(y)) = a^p a^q
+        Operation::Exp if !contains(a, x) && !contains(a, y) => {
+            let (p, q) = split_sum(b, x, y)?;
+            Some((parse(format!("({})^({})", a, p)), parse(format!("({})^({})", a, q))))
+        },
+        _ => None,
+    }
+}
+
+// e = p(x) + q(y) as (p, q)
+fn split_sum(e: &Expression, x: char, y: char) -> Option<(Expression, Expression)> {
+    if !contains(e, y) {
+        return Some((copy_expression(e), Expression::from("0")));
+    }
+    if !contains(e, x) {
+        return Some((Expression::from("0"), copy_expression(e)));
+    }
+    match e {
+        Expression::Equa(equation) if equation.operation == Operation::Add || equation.operation == Operation::Sub => {
+            let ((p1, q1), (p2, q2)) = (split_sum(&equation.element1, x, y)?, split_sum(&equation.element2, x, y)?);
+            let sign = if equation.operation == Operation::Add { "+" } else { "-" };
+            Some((parse(format!("({}){}({})", p1, sign, p2)), parse(format!("({}){}({})", q1, sign, q2))))
+        },
+        _ => None,
+    }
+}
+
+// the factors of a product, n(n+1) style implied products included and -u as (-1)u
+fn factors(bank: &Workspace, e: &Expression, list: &mut Vec<Expression>) {
+    match e {
+        Expression::Equa(equation) if equation.operation == Operation::Mult
+            || (equation.operation == Operation::Func && !is_function(bank, &equation.element1)) => {
+            factors(bank, &equation.element1, list);
+            factors(bank, &equation.element2, list);
+        },
+        Expression::Equa(equation) if equation.operation == Operation::Sub && *equation.element1 == Expression::from("0") => {
+            list.push(Expression::from("-1"));
+            factors(bank, &equation.element2, list);
+        },
+        _ => list.push(copy_expression(e)),
+    }
+}
+
+fn family(bank: &Workspace, g: &Expression, h: &Expression, y: char) -> Family {
+    let mut list = Vec::new();
+    factors(bank, g, &mut list);
+    factors(bank, h, &mut list);
+    let (with_y, constants): (Vec<Expression>, Vec<Expression>) = list.into_iter().partition(|f| contains(f, y));
+    let k = constants.iter().fold(Expression::from("1"), |k, c| parse(format!("({})({})", k, c)));
+    let k = simplify(&k);
+    let is_y = |e: &Expression| *e == Expression::Variable(y);
+
+    match &with_y[..] {
+        [only] if is_y(only) => Family::Exponential { k },
+        [first, second] if is_y(first) || is_y(second) => {
+            let other = if is_y(first) { second } else { first };
+            match capacity(bank, other, y) {
+                Some((capacity, true)) => Family::Logistic { rate: simplify(&parse(format!("({})({})", k, capacity))), capacity },
+                Some((capacity, false)) => Family::Logistic { rate: k, capacity },
+                None => Family::General,
+            }
+        },
+        _ => Family::General,
+    }
+}
+
+// L for L-y (true, the rate picks up a factor of L) or for 1-y/L (false)
+fn capacity(bank: &Workspace, e: &Expression, y: char) -> Option<(Expression, bool)> {
+    let equation = match e {
+        Expression::Equa(equation) if equation.operation == Operation::Sub => equation,
+        _ => return None,
+    };
+    let (a, b) = (&*equation.element1, &*equation.element2);
+    if contains(a, y) {
+        return None;
+    }
+    if *b == Expression::Variable(y) {
+        return Some((simplify(a), true));
+    }
+    if simplify(a) != Expression::from("1") {
+        return None;
+    }
+    let part = match b {
+        Expression::Equa(part) => part,
+        _ => return None,
+    };
+    let (p, q) = (&*part.element1, &*part.element2);
+    match part.operation {
+        // 1-y/L
+        Operation::Div if *p == Expression::Variable(y) && !contains(q, y) => Some((simplify(q), false)),
+        // 1-cy with L = 1/c
+        Operation::Mult | Operation::Func if *q == Expression::Variable(y) && !contains(p, y)
+            && (part.operation == Operation::Mult || !is_function(bank, p)) =>
+            Some((simplify(&parse(format!("(1)/({})", p))), false)),
+        _ => None,
+    }
+}
+
+// y from left = right + constant, an even root takes the sign that passes through (x0, y0)
+fn explicit_solution(bank: &Workspace, left: &Expression, right: &Expression, constant: &Expression,
+                     (x, y): (char, char), x0: Fraction, y0: Fraction) -> Option<Expression> {
+    let side = parse(format!("({})+({})", right, constant));
+    let (positive, even_root) = isolate(bank, left, &side, y, false)?;
+    if !even_root || !known(bank, &positive, &[x]) {
+        return Some(positive);
+    }
+    let (x0, y0) = (x0.to_f64()?, y0.to_f64()?);
+    if (positive.approximate_with(bank, &[(x, x0)]) - y0).abs() < 1e-9 * y0.abs().max(1.0) {
+        return Some(positive);
+    }
+    isolate(bank, left, &side, y, true).map(|(negative, _)| negative)
+}
+
+// undoes each operation around y in turn, the bool is whether an even root or an abs was
+// taken
+fn isolate(bank: &Workspace, left: &Expression, right: &Expression, y: char, negative_root: bool) -> Option<(Expression, bool)> {
+    let mut left = copy_expression(left);
+    let mut right = copy_expression(right);
+    let mut even_root = false;
+
+    loop {
+        let equation = match &left {
+            Expression::Variable(v) if *v == y => return Some((simplify(&right), even_root)),
+            Expression::Equa(equation) => equation,
+            _ => return None,
+        };
+        let (a, b) = (&*equation.element1, &*equation.element2);
+        let in_a = contains(a, y);
+        if in_a == contains(b, y) {
+            return None;
+        }
+        // k(y) is a product when k isn't a function
+        let operation = match equation.operation {
+            Operation::Func if !is_function(bank, a) => Operation::Mult,
+            operation => operation,
+        };
+
+        let inverse = match (operation, in_a) {
+            (Operation::Add, true) => format!("({})-({})", right, b),
+            (Operation::Add, false) => format!("({})-({})", right, a),
+            (Operation::Sub, true) => format!("({})+({})", right, b),
+            (Operation::Sub, false) => format!("({})-({})", a, right),
+            (Operation::Mult, true) => format!("({})/({})", right, b),
+            (Operation::Mult, false) => format!("({})/({})", right, a),
+            (Operation::Div, true) => format!("({})({})", right, b),
+            (Operation::Div, false) => format!("({})/({})", a, right),
+            (Operation::Exp, true) => {
+                let root = format!("({})^((1)/({}))", right, b);
+                match simplify(b) {
+                    Expression::Constant(n) if n.denom() == Some(&1) && n.numer().is_some_and(|n| n % 2 == 0) => {
+                        even_root = true;
+                        if negative_root { format!("0-({})", root) } else { root }
+                    },
+                    _ => root,
+                }
+            },
+            // |u| = r is u = ±r, the sign is picked like an even root's
+            (Operation::Abs, false) => {
+                even_root = true;
+                if negative_root { format!("0-({})", right) } else { format!("{}", right) }
+            },
+            (Operation::Exp, false) if *a == Expression::Variable('e') => format!("ln({})", right),
+            (Operation::Exp, false) => format!("(ln({}))/(ln({}))", right, a),
+            (Operation::Log, false) => format!("({})^({})", a, right),
+            (Operation::Trig, false) => {
+                let inverse = match a {
+                    Expression::Variable('s') => "arcsin",
+                    Expression::Variable('c') => "arccos",
+                    Expression::Variable('t') => "arctan",
+                    Expression::Variable('S') => "sin",
+                    Expression::Variable('C') => "cos",
+                    Expression::Variable('T') => "tan",
+                    _ => return None,
+                };
+                format!("{}({})", inverse, right)
+            },
+            _ => return None,
+        };
+        let next = copy_expression(if in_a { a } else { b });
+        right = parse(inverse);
+        left = next;
+    }
+}
@@ -0,0 +1,390 @@
+use super::*;
+use applications::Bound;
+use limits::Side;
+use simplify::simplify;
+
+// where roots and sign changes are looked for, past it the sign is assumed to stay put
+const WINDOW: (f64, f64) = (-100.0, 100.0);
+
+/// An interval of the real line, the ends can be ±inf
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Interval {
+    pub low: Bound,
+    pub high: Bound,
+    pub closed_low: bool,
+    pub closed_high: bool,
+}
+
+/// The points start + k period for every whole k, like the asymptotes tan(x) leaves out
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Periodic {
+    pub start: Bound,
+    pub period: Bound,
+}
+
+/// A union of disjoint intervals in increasing order, less the points in excluded. When
+/// approximate is Some((a, b)) the sign changes went on past [a, b] and weren't followed, so
+/// the intervals are only right on [a, b]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IntervalSet {
+    pub intervals: Vec<Interval>,
+    pub excluded: Vec<Periodic>,
+    pub approximate: Option<(f64, f64)>,
+}
+
+impl Interval {
+    /// Whether x is in the interval
+    pub fn contains(&self, x: f64) -> bool {
+        let above = x > self.low.value || (self.closed_low && x == self.low.value);
+        let below = x < self.high.value || (self.closed_high && x == self.high.value);
+        above && below
+    }
+}
+
+impl Periodic {
+    /// Whether x is start + k period for some whole k
+    pub fn contains(&self, x: f64) -> bool {
+        let k = (x - self.start.value) / self.period.value;
+        (k - k.round()).abs() < 1e-9 * k.abs().max(1.0)
+    }
+}
+
+impl IntervalSet {
+    /// Whether x is in one of the intervals and isn't excluded
+    pub fn contains(&self, x: f64) -> bool {
+        self.intervals.iter().any(|interval| interval.contains(x)) && !self.excluded.iter().any(|points| points.contains(x))
+    }
+}
+
+fn end(b: &Bound) -> String {
+    match b.value {
+        v if v == f64::INFINITY => String::from("∞"),
+        v if v == f64::NEG_INFINITY => String::from("-∞"),
+        _ => b.to_string(),
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.low.value == self.high.value {
+            return write!(f,"{{{}}}",end(&self.low));
+        }
+        write!(f,"{}{}, {}{}",if self.closed_low { "[" } else { "(" },end(&self.low),end(&self.high),if self.closed_high { "]" } else { ")" })
+    }
+}
+
+impl fmt::Display for Periodic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.start.value == 0.0 {
+            write!(f,"x ≠ k{}",self.period)
+        } else {
+            write!(f,"x ≠ {} + k{}",self.start,self.period)
+        }
+    }
+}
+
+impl fmt::Display for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.intervals.is_empty() {
+            write!(f,"∅")?;
+        } else {
+            let parts: Vec<String> = self.intervals.iter().map(|interval| interval.to_string()).collect();
+            write!(f,"{}",parts.join(" ∪ "))?;
+        }
+        for points in &self.excluded {
+            write!(f,", {}",points)?;
+        }
+        match self.approximate {
+            Some((a, b)) => write!(f," on [{}, {}]",a,b),
+            None => Ok(()),
+        }
+    }
+}
+
+// what has to be true of an expression for e to be defined
+#[derive(Clone, Copy)]
+enum Condition {
+    NonZero,
+    Positive,
+    NonNegative,
+    // arcsin and arccos
+    UnitInterval,
+    // a piecewise function, which has to have a piece for x that is defined there
+    Covered,
+}
+
+fn holds(condition: Condition, v: f64) -> bool {
+    v.is_finite() && match condition {
+        Condition::NonZero => v.abs() > 1e-9,
+        Condition::Positive => v > 1e-9,
+        Condition::NonNegative => v > -1e-9,
+        Condition::UnitInterval => v.abs() <= 1.0 + 1e-9,
+        Condition::Covered => true,
+    }
+}
+
+// the conditions read off the tree, before simplifying since x/x = 1 would lose x != 0
+fn conditions(e: &Expression, list: &mut Vec<(Expression, Condition)>) {
+    let equation = match e {
+        Expression::Equa(equation) => equation,
+        _ => return,
+    };
+    let (a, b) = (&*equation.element1, &*equation.element2);
+    match equation.operation {
+        Operation::Div => list.push((copy_expression(b), Condition::NonZero)),
+        Operation::Log => list.push((copy_expression(b), Condition::Positive)),
+        Operation::Exp => match simplify(b) {
+            // even roots need a radicand that isn't negative, negative powers one that isn't 0
+            Expression::Constant(p) => {
+                let even = p.denom().is_some_and(|d| d.is_multiple_of(2));
+                let negative = p < Fraction::from(0);
+                match (even, negative) {
+                    (true, true) => list.push((copy_expression(a), Condition::Positive)),
+                    (true, false) => list.push((copy_expression(a), Condition::NonNegative)),
+                    (false, true) => list.push((copy_expression(a), Condition::NonZero)),
+                    (false, false) => (),
+                }
+            },
+            // a variable power like x^x needs a positive base
+            _ if contains(a, 'x') => list.push((copy_expression(a), Condition::Positive)),
+            _ => (),
+        },
+        Operation::Trig => match a {
+            Expression::Variable('t') => list.push((Expression::from(&format!("cos({})", b)), Condition::NonZero)),
+            Expression::Variable('S') | Expression::Variable('C') => list.push((copy_expression(b), Condition::UnitInterval)),
+            _ => (),
+        },
+        Operation::Piecewise | Operation::Piece => return list.push((copy_expression(e), Condition::Covered)),
+        Operation::Sum(_) | Operation::Integral(_) | Operation::Deriv | Operation::Bounds => return,
+        _ => (),
+    }
+    if !matches!(equation.operation, Operation::Trig | Operation::Log) {
+        conditions(a, list);
+    }
+    conditions(b, list);
+}
+
+// roots evenly spaced from one end of the window to the other, like cos(x) = 0, are taken to
+// go on forever as (the first at or past 0, the spacing)
+fn periodic(roots: &[f64]) -> Option<(f64, f64)> {
+    if roots.len() < 4 {
+        return None;
+    }
+    let period = (roots[roots.len()-1] - roots[0]) / (roots.len() - 1) as f64;
+    let even = roots.windows(2).all(|pair| (pair[1] - pair[0] - period).abs() < 1e-6 * period);
+    let spanning = roots[0] - WINDOW.0 <= period * (1.0 + 1e-6) && WINDOW.1 - roots[roots.len()-1] <= period * (1.0 + 1e-6);
+    if !(even && spanning) {
+        return None;
+    }
+    let start = roots[0].rem_euclid(period);
+    Some((if period - start < 1e-9 { 0.0 } else { start }, period))
+}
+
+// roots that come right up to both ends of the window, closer than they are to each other,
+// likely keep coming past it
+fn keeps_going(roots: &[f64]) -> bool {
+    let widest = roots.windows(2).map(|pair| pair[1] - pair[0]).fold(0.0, f64::max);
+    roots.len() >= 4 && roots[0] - WINDOW.0 <= widest && WINDOW.1 - roots[roots.len()-1] <= widest
+}
+
+// where g crosses into or out of what condition allows
+fn edges(bank: &Workspace, g: &Expression, condition: Condition) -> Vec<f64> {
+    let value = |x: f64| g.approximate(bank, x);
+    let mut roots = numeric::roots(value, WINDOW.0, WINDOW.1);
+    if let Condition::UnitInterval = condition {
+        roots.extend(numeric::roots(|x| value(x) - 1.0, WINDOW.0, WINDOW.1));
+        roots.extend(numeric::roots(|x| value(x) + 1.0, WINDOW.0, WINDOW.1));
+        roots.sort_by(f64::total_cmp);
+    }
+    roots
+}
+
+/// The x where e is defined, worked out from its tree: denominators can't be 0, logs need
+/// positive arguments, even roots non-negative radicands, arcsin and arccos arguments in
+/// [-1, 1], tan can't sit on an asymptote and a piecewise function needs a piece for x. Sign changes are looked for on [-100, 100];
+/// points left out over and over like tan's asymptotes become excluded, and sign changes that
+/// don't stop at the window in any other way make the set approximate
+pub fn domain(bank: &Workspace, e: &Expression) -> IntervalSet {
+    let e = expand(bank, e);
+    let mut list = Vec::new();
+    conditions(&e, &mut list);
+
+    let mut cuts: Vec<f64> = Vec::new();
+    let mut excluded: Vec<Periodic> = Vec::new();
+    let mut approximate = None;
+    list.retain(|(g, condition)| {
+        // a piecewise function switches where its conditions do, and each piece's value has
+        // conditions of its own that only count where the piece is used
+        if let Condition::Covered = condition {
+            cuts.extend(piecewise::breaks(bank, g, 'x').into_iter().map(|b| b.value));
+            let mut inner = Vec::new();
+            for (_, value) in piecewise::pieces(g) {
+                conditions(value, &mut inner);
+            }
+            for (h, condition) in &inner {
+                cuts.extend(edges(bank, h, *condition));
+                cuts.extend(integral::singularities(bank, h, 'x', WINDOW.0, WINDOW.1));
+            }
+            return true;
+        }
+        let roots = edges(bank, g, *condition);
+        if let Condition::NonZero = condition {
+            if let Some((start, period)) = periodic(&roots) {
+                excluded.push(Periodic { start: applications::bound(start), period: applications::bound(period) });
+                return false;
+            }
+        }
+        if keeps_going(&roots) {
+            approximate = Some(WINDOW);
+        }
+        cuts.extend(roots);
+        cuts.extend(integral::singularities(bank, g, 'x', WINDOW.0, WINDOW.1));
+        true
+    });
+    let mut cuts: Vec<Bound> = cuts.into_iter().map(applications::bound).collect();
+    cuts.sort_by(|p, q| p.value.total_cmp(&q.value));
+    cuts.dedup_by(|p, q| (p.value - q.value).abs() < 1e-9);
+
+    let defined = |x: f64| list.iter().all(|(g, condition)| holds(*condition, g.approximate(bank, x)));
+    let infinite = |value: f64| Bound { value, exact: None };
+
+    // pieces between the cuts, each is in or out as a whole
+    let mut ends: Vec<Bound> = vec![infinite(f64::NEG_INFINITY)];
+    ends.extend(cuts);
+    ends.push(infinite(f64::INFINITY));
+    let mut intervals: Vec<Interval> = Vec::new();
+    for i in 0..ends.len() - 1 {
+        let (low, high) = (ends[i].value, ends[i+1].value);
+        let middle = match (low.is_finite(), high.is_finite()) {
+            (true, true) => (low + high) / 2.0,
+            (true, false) => low + 1.0,
+            (false, true) => high - 1.0,
+            (false, false) => 0.0,
+        };
+        if !defined(middle) {
+            continue;
+        }
+        let closed_low = low.is_finite() && defined(low);
+        let closed_high = high.is_finite() && defined(high);
+        match intervals.last_mut() {
+            Some(last) if last.high.value == low && closed_low => {
+                last.high = copy_bound(&ends[i+1]);
+                last.closed_high = closed_high;
+            },
+            _ => intervals.push(Interval { low: copy_bound(&ends[i]), high: copy_bound(&ends[i+1]), closed_low, closed_high }),
+        }
+    }
+    // a lone point like x = 0 for (0-x^2)^(1/2)
+    for cut in &ends[1..ends.len() - 1] {
+        if defined(cut.value) && !intervals.iter().any(|interval| interval.contains(cut.value)) {
+            intervals.push(Interval { low: copy_bound(cut), high: copy_bound(cut), closed_low: true, closed_high: true });
+        }
+    }
+    intervals.sort_by(|p, q| p.low.value.total_cmp(&q.low.value));
+    IntervalSet { intervals, excluded, approximate }
+}
+
+fn copy_bound(b: &Bound) -> Bound {
+    Bound { value: b.value, exact: b.exact.as_ref().map(copy_expression) }
+}
+
+// the limit of f at an open end, an infinite one has to come from |f| growing steadily since
+// the extrapolation can take sin(x) at inf for a blow up
+fn approach(f: &dyn Fn(f64) -> f64, x: f64, side: Side) -> f64 {
+    let limit = limits::limit_of(f, x, side);
+    let toward = |k: i32| match (x.is_infinite(), side) {
+        (true, _) => x.signum() * 10f64.powi(k),
+        (false, Side::Left) => x - 10f64.powi(-k),
+        (false, _) => x + 10f64.powi(-k),
+    };
+    let sizes: Vec<f64> = (1..=6).map(|k| f(toward(k)).abs()).collect();
+    let growing = sizes.windows(2).all(|pair| pair[1] > pair[0] || pair[1] == f64::INFINITY);
+    if limit.is_infinite() && !growing { f64::NAN } else { limit }
+}
+
+/// A best effort range of e: on each piece of the domain the values at critical points and
+/// closed ends are reached, limits at open ends are approached but not reached
+pub fn range(bank: &Workspace, e: &Expression) -> IntervalSet {
+    let domain = domain(bank, e);
+    // simplified so x^(1/3) works out for negative x, the domain already has what it loses
+    let e = simplify(&expand(bank, e));
+    let slope = derivative::derivative(bank, &e);
+    let f = |x: f64| e.approximate(bank, x);
+
+    // the excluded points in the window split the intervals like any other cut
+    let mut split: Vec<Interval> = Vec::new();
+    for interval in &domain.intervals {
+        let mut low = (copy_bound(&interval.low), interval.closed_low);
+        let mut points: Vec<f64> = Vec::new();
+        for Periodic { start, period } in &domain.excluded {
+            let first = ((interval.low.value.max(WINDOW.0) - start.value) / period.value).ceil() as i64;
+            let last = ((interval.high.value.min(WINDOW.1) - start.value) / period.value).floor() as i64;
+            points.extend((first..=last).map(|k| start.value + k as f64 * period.value));
+        }
+        points.sort_by(f64::total_cmp);
+        for x in points.into_iter().filter(|x| interval.contains(*x)) {
+            split.push(Interval { low: low.0, high: applications::bound(x), closed_low: low.1, closed_high: false });
+            low = (applications::bound(x), false);
+        }
+        split.push(Interval { low: low.0, high: copy_bound(&interval.high), closed_low: low.1, closed_high: interval.closed_high });
+    }
+
+    let mut pieces: Vec<(f64, bool, f64, bool)> = Vec::new();
+    for interval in &split {
+        let (low, high) = (interval.low.value, interval.high.value);
+        // (value, reached)
+        let mut candidates: Vec<(f64, bool)> = Vec::new();
+        for (x, closed, side) in [(low, interval.closed_low, Side::Right), (high, interval.closed_high, Side::Left)] {
+            if closed {
+                candidates.push((f(x), true));
+            } else {
+                candidates.push((approach(&f, x, side), false));
+            }
+        }
+        let (scan_low, scan_high) = (low.max(WINDOW.0), high.min(WINDOW.1));
+        if scan_low < scan_high {
+            // critical points, f' = 0 or f' undefined like (x^2)^(1/2) at 0
+            let mut critical = numeric::roots(|x| slope.approximate(bank, x), scan_low, scan_high);
+            critical.extend(integral::singularities(bank, &slope, 'x', scan_low, scan_high));
+            candidates.extend(critical.into_iter()
+                .filter(|x| interval.contains(*x))
+                .map(|x| (f(x), true)));
+        }
+        let candidates: Vec<(f64, bool)> = candidates.into_iter().filter(|(v, _)| !v.is_nan()).collect();
+        if candidates.is_empty() {
+            continue;
+        }
+        let lowest = candidates.iter().map(|(v, _)| *v).fold(f64::INFINITY, f64::min);
+        let highest = candidates.iter().map(|(v, _)| *v).fold(f64::NEG_INFINITY, f64::max);
+        // only a blow up at one end, like the piece of tan past the window, says nothing
+        if lowest == highest && lowest.is_infinite() {
+            continue;
+        }
+        let reached = |target: f64| candidates.iter().any(|(v, reached)| *reached && (v - target).abs() < 1e-9 * target.abs().max(1.0));
+        pieces.push((lowest, reached(lowest), highest, reached(highest)));
+    }
+
+    // pieces that overlap or touch become one interval
+    pieces.sort_by(|p, q| p.0.total_cmp(&q.0));
+    let mut merged: Vec<(f64, bool, f64, bool)> = Vec::new();
+    for piece in pieces {
+        match merged.last_mut() {
+            Some(last) if piece.0 < last.2 || (piece.0 == last.2 && (piece.1 || last.3)) => {
+                if piece.0 == last.0 { last.1 |= piece.1 }
+                if piece.2 > last.2 { *last = (last.0, last.1, piece.2, piece.3) } else if piece.2 == last.2 { last.3 |= piece.3 }
+            },
+            _ => merged.push(piece),
+        }
+    }
+    let bound = |v: f64| if v.is_finite() { applications::bound(v) } else { Bound { value: v, exact: None } };
+    IntervalSet {
+        intervals: merged.into_iter()
+            .map(|(low, closed_low, high, closed_high)| Interval { low: bound(low), high: bound(high), closed_low, closed_high })
+            .collect(),
+        excluded: Vec::new(),
+        approximate: domain.approximate,
+    }
+}
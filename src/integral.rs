@@ -0,0 +1,514 @@
+use super::*;
+use simplify::simplify;
+
+// points checked for a blow up before trusting F(b) - F(a)
+const SAMPLES: usize = 200;
+
+/// How a definite integral was found
+#[derive(PartialEq)]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Method {
+    /// F(b) - F(a) with F from antiderivative
+    FundamentalTheorem,
+    /// Simpson's rule, when no antiderivative was found or the integrand isn't continuous
+    Numeric,
+}
+
+/// A definite integral, exact is the FTC answer with π and e left symbolic
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Definite {
+    #[cfg_attr(feature = "serde", serde(with = "crate::schema::number"))]
+    pub value: f64,
+    pub exact: Option<Expression>,
+    pub method: Method,
+}
+
+impl fmt::Display for Definite {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.exact {
+            Some(exact) => write!(f,"{}",exact),
+            None => write!(f,"{}",self.value),
+        }
+    }
+}
+
+/// ∫_a^b e dx, bank functions in e are written out first. Jumps split the integral into
+/// pieces and a blow up between a and b makes it NaN, improper is for those
+pub fn integrate(bank: &Workspace, e: &Expression, a: &Expression, b: &Expression) -> Definite {
+    integrate_in(bank, e, 'x', a, b)
+}
+
+/// integrate with respect to var
+pub fn integrate_in(bank: &Workspace, e: &Expression, var: char, a: &Expression, b: &Expression) -> Definite {
+    let integrand = simplify(&expand(bank, e));
+    if let Expression::Equa(equation) = &integrand {
+        if matches!(equation.operation, Operation::Piecewise | Operation::Piece) {
+            return piecewise::integrate(bank, &integrand, var, a, b);
+        }
+    }
+    let anti = antiderivative(&integrand, var);
+    let exact = anti.as_ref().map(|anti| simplify(&parse(format!("({})-({})",
+        substitute(anti, var, b), substitute(anti, var, a)))));
+
+    // letters without values leave only the symbolic answer
+    if !known(bank, &integrand, &[var]) || !known(bank, a, &[]) || !known(bank, b, &[]) {
+        match exact {
+            Some(exact) => return Definite { value: f64::NAN, exact: Some(exact), method: Method::FundamentalTheorem },
+            // nothing to give but that it couldn't be worked out
+            None => return Definite { value: f64::NAN, exact: None, method: Method::Numeric },
+        }
+    }
+
+    let (low, high) = (a.approximate_with(bank, &[]), b.approximate_with(bank, &[]));
+    let f = |t: f64| integrand.approximate_with(bank, &[(var, t)]);
+    // FTC needs f continuous on [a, b], a jump like floor's is finite everywhere so the
+    // discontinuities are looked for as well as blow ups
+    let breaks = if contains(&integrand, 'x') && var != 'x' {
+        Vec::new()
+    } else {
+        continuity::discontinuities(bank, &substitute(&integrand, var, &Expression::Variable('x')), low.min(high), low.max(high))
+    };
+    if breaks.iter().any(|point| point.kind == continuity::Discontinuity::Infinite) {
+        return Definite { value: f64::NAN, exact: None, method: Method::Numeric };
+    }
+    if !breaks.is_empty() {
+        // each piece between the jumps on its own, in the direction from a to b
+        let mut ends: Vec<Expression> = vec![copy_expression(a)];
+        let inner = breaks.iter().map(|point| point.x.exact.as_ref().map_or(Expression::Constant(Fraction::from(point.x.value)), copy_expression));
+        if low <= high { ends.extend(inner) } else { ends.extend(inner.rev()) }
+        ends.push(copy_expression(b));
+        let pieces: Vec<Definite> = ends.windows(2).map(|pair| integrate_in(bank, &integrand, var, &pair[0], &pair[1])).collect();
+        let value = pieces.iter().map(|piece| piece.value).sum();
+        let exact = pieces.iter()
+            .map(|piece| piece.exact.as_ref().map(|e| format!("({})", e)))
+            .collect::<Option<Vec<String>>>()
+            .map(|parts| simplify(&parse(parts.join("+"))));
+        let method = if exact.is_some() { Method::FundamentalTheorem } else { Method::Numeric };
+        let value = exact.as_ref().map_or(value, |e| e.approximate_with(bank, &[]));
+        return Definite { value, exact, method };
+    }
+    let continuous = (0..=SAMPLES).all(|i| f(low + (high - low) * i as f64 / SAMPLES as f64).is_finite());
+
+    if let (Some(exact), true) = (exact, continuous) {
+        let value = exact.approximate_with(bank, &[]);
+        if value.is_finite() {
+            return Definite { value, exact: Some(exact), method: Method::FundamentalTheorem };
+        }
+    }
+    // the ends are taken from inside so a piece that stops at a jump gets the one sided
+    // limit, blow ups there stay blow ups
+    let nudge = (high - low) * 1e-12;
+    let inside = |t: f64| match t {
+        t if t == low && !f(t).is_infinite() => f(low + nudge),
+        t if t == high && !f(t).is_infinite() => f(high - nudge),
+        t => f(t),
+    };
+    let value = numeric::simpson(inside, low, high, 1000);
+    Definite { value: if value.is_finite() { value } else { f64::NAN }, exact: None, method: Method::Numeric }
+}
+
+/// What an improper integral does
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Improper {
+    Converges(Definite),
+    Diverges,
+}
+
+impl fmt::Display for Improper {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Improper::Converges(definite) => write!(f,"converges to {}",definite),
+            Improper::Diverges => write!(f,"diverges"),
+        }
+    }
+}
+
+// |f| past this counts as blowing up
+const BLOW_UP: f64 = 1e8;
+
+/// ∫_a^b e dx where a and b can be ±inf and e can blow up at an end or inside. The integral is
+/// split at every trouble spot and each piece is a limit, of F when there's an antiderivative
+/// and of the numeric integral otherwise
+pub fn improper(bank: &Workspace, e: &Expression, a: &Expression, b: &Expression) -> Improper {
+    improper_in(bank, e, 'x', a, b)
+}
+
+/// improper with respect to var
+pub fn improper_in(bank: &Workspace, e: &Expression, var: char, a: &Expression, b: &Expression) -> Improper {
+    let integrand = simplify(&expand(bank, e));
+    let f = |t: f64| integrand.approximate_with(bank, &[(var, t)]);
+    let trouble = |t: f64| t.is_infinite() || !f(t).is_finite() || f(t).abs() > BLOW_UP;
+    let (low, high) = (a.approximate_with(bank, &[]), b.approximate_with(bank, &[]));
+
+    let mut points = vec![(low, Some(copy_expression(a)))];
+    points.extend(singularities(bank, &integrand, var, low, high).into_iter().map(|c| (c, limits::exact_value(c, 1e-9))));
+    points.push((high, Some(copy_expression(b))));
+    if points.len() == 2 && !trouble(low) && !trouble(high) {
+        return Improper::Converges(integrate_in(bank, &integrand, var, a, b));
+    }
+
+    let anti = antiderivative(&integrand, var);
+    let piece = |good: f64, bad: f64| -> Option<Definite> {
+        limit_piece(bank, &integrand, anti.as_ref(), var, (good, limits::exact_value(good, 1e-9)), bad)
+    };
+    let mut total: Vec<Definite> = Vec::new();
+    for window in points.windows(2) {
+        let ((p, p_exact), (q, q_exact)) = (&window[0], &window[1]);
+        let (p, q) = (*p, *q);
+        let parts = match (trouble(p), trouble(q)) {
+            (false, true) => vec![limit_piece(bank, &integrand, anti.as_ref(), var, (p, clone(p_exact)), q)],
+            (true, false) => vec![limit_piece(bank, &integrand, anti.as_ref(), var, (q, clone(q_exact)), p).map(negate)],
+            (true, true) => {
+                let middle = match (p.is_infinite(), q.is_infinite()) {
+                    (true, true) => 0.0,
+                    (true, false) => q - 1.0,
+                    (false, true) => p + 1.0,
+                    (false, false) => (p + q) / 2.0,
+                };
+                vec![piece(middle, p).map(negate), piece(middle, q)]
+            },
+            (false, false) => vec![Some(integrate_in(bank, &integrand, var, &p_exact.as_ref().map_or(Expression::Constant(Fraction::from(p)), copy_expression),
+                &q_exact.as_ref().map_or(Expression::Constant(Fraction::from(q)), copy_expression)))],
+        };
+        for part in parts {
+            match part {
+                Some(part) => total.push(part),
+                None => return Improper::Diverges,
+            }
+        }
+    }
+
+    let value = total.iter().map(|part| part.value).sum();
+    let exact = total.iter()
+        .map(|part| part.exact.as_ref().map(|e| format!("({})", e)))
+        .collect::<Option<Vec<String>>>()
+        .map(|parts| simplify(&parse(parts.join("+"))));
+    let method = if exact.is_some() { Method::FundamentalTheorem } else { Method::Numeric };
+    Improper::Converges(Definite { value, exact, method })
+}
+
+fn clone(e: &Option<Expression>) -> Option<Expression> {
+    e.as_ref().map(copy_expression)
+}
+
+fn negate(definite: Definite) -> Definite {
+    Definite {
+        value: -definite.value,
+        exact: definite.exact.map(|e| simplify(&parse(format!("0-({})", e)))),
+        method: definite.method,
+    }
+}
+
+// ∫ from good to the trouble spot bad as a limit, None when it diverges
+fn limit_piece(bank: &Workspace, integrand: &Expression, anti: Option<&Expression>, var: char,
+               (good, good_exact): (f64, Option<Expression>), bad: f64) -> Option<Definite> {
+    let side = if bad > good { limits::Side::Left } else { limits::Side::Right };
+    if let Some(anti) = anti {
+        let big_f = |t: f64| anti.approximate_with(bank, &[(var, t)]);
+        let (start, end) = (big_f(good), limits::limit_of(big_f, bad, side));
+        if start.is_finite() && end.is_infinite() {
+            return None;
+        }
+        if start.is_finite() && end.is_finite() {
+            let value = end - start;
+            // extrapolated limits are only good to about 1e-7
+            let exact = match (limits::exact_value(end, 1e-6), good_exact) {
+                (Some(end), Some(good)) => Some(simplify(&parse(format!("({})-({})", end, substitute(anti, var, &good))))),
+                _ => None,
+            };
+            // a snapped limit that doesn't agree with the numbers isn't trusted
+            let exact = exact.filter(|e| known(bank, e, &[]) && (e.approximate_with(bank, &[]) - value).abs() < 1e-6 * value.abs().max(1.0));
+            let value = exact.as_ref().map_or(value, |e| e.approximate_with(bank, &[]));
+            return Some(Definite { value, exact, method: Method::FundamentalTheorem });
+        }
+    }
+    let value = tail(|t| integrand.approximate_with(bank, &[(var, t)]), good, bad);
+    if value.is_finite() { Some(Definite { value, exact: None, method: Method::Numeric }) } else { None }
+}
+
+// ∫ from good toward bad on pieces that halve the distance left (or double the length out
+// to infinity), with numeric::sequence_limit deciding where the partial integrals go
+fn tail<F: Fn(f64) -> f64>(f: F, good: f64, bad: f64) -> f64 {
+    let point = |n: f64| if bad.is_infinite() { good + bad.signum() * (n - 1.0) } else { bad + (good - bad) / n };
+    numeric::sequence_limit(|n| {
+        let k = n.log2().round() as i32;
+        (1..=k).map(|j| numeric::simpson(&f, point(2f64.powi(j - 1)), point(2f64.powi(j)), 64)).sum()
+    })
+}
+
+/// Points strictly between low and high where e blows up or is undefined: roots of
+/// denominators, log arguments, bases raised to negative powers and cos under tan,
+/// plus anything a grid turns up
+pub fn singularities(bank: &Workspace, e: &Expression, var: char, low: f64, high: f64) -> Vec<f64> {
+    let f = |t: f64| e.approximate_with(bank, &[(var, t)]);
+    // infinite bounds get a finite stretch scanned
+    let scan_low = if low.is_finite() { low } else { high.min(0.0) - 100.0 };
+    let scan_high = if high.is_finite() { high } else { low.max(0.0) + 100.0 };
+
+    let mut watched = Vec::new();
+    watch(e, &mut watched);
+    let mut candidates: Vec<f64> = watched.iter()
+        .flat_map(|g| numeric::roots(|t| g.approximate_with(bank, &[(var, t)]), scan_low, scan_high))
+        .collect();
+    let h = (scan_high - scan_low) / 1000.0;
+    candidates.extend((0..=1000).map(|i| scan_low + h * i as f64).filter(|t| !f(*t).is_finite()));
+
+    let mut found: Vec<f64> = candidates.into_iter()
+        .map(|c| limits::exact_value(c, 1e-9).map_or(c, |exact| exact.approximate_with(bank, &[])))
+        .filter(|c| *c > low + 1e-12 && *c < high - 1e-12)
+        .filter(|c| !f(*c).is_finite() || f(*c).abs() > BLOW_UP)
+        .collect();
+    found.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    found.dedup_by(|a, b| (*a - *b).abs() < 1e-9);
+    found
+}
+
+// the parts of e whose zeros can make it blow up
+fn watch(e: &Expression, list: &mut Vec<Expression>) {
+    let equation = match e {
+        Expression::Equa(equation) => equation,
+        _ => return,
+    };
+    let (a, b) = (&*equation.element1, &*equation.element2);
+    match equation.operation {
+        Operation::Div => list.push(copy_expression(b)),
+        Operation::Log => list.push(copy_expression(b)),
+        Operation::Exp if matches!(simplify(b), Expression::Constant(c) if c < Fraction::from(0)) => list.push(copy_expression(a)),
+        Operation::Trig if *a == Expression::Variable('t') => list.push(parse(format!("cos({})", b))),
+        _ => (),
+    }
+    if !matches!(equation.operation, Operation::Trig | Operation::Func | Operation::Deriv) {
+        watch(a, list);
+    }
+    watch(b, list);
+}
+
+/// An int(t,a,b,f) node as a Fraction, exact when the FTC answer is rational
+pub fn evaluate_node(bank: &Workspace, node: &Expression, x: Fraction) -> Fraction {
+    let (t, lower, upper, integrand) = parts(node);
+    let x = Expression::Constant(x);
+    let definite = integrate_in(bank, integrand, t, &substitute(lower, 'x', &x), &substitute(upper, 'x', &x));
+    match definite.exact {
+        Some(Expression::Constant(c)) => c,
+        _ => Fraction::from(definite.value),
+    }
+}
+
+/// An int(t,a,b,f) node with Simpson's rule, vars are bound in the bounds and the integrand
+pub fn approximate_node(bank: &Workspace, node: &Expression, vars: &[(char, f64)]) -> f64 {
+    let (t, lower, upper, integrand) = parts(node);
+    let (low, high) = (lower.approximate_with(bank, vars), upper.approximate_with(bank, vars));
+    numeric::simpson(|value| {
+        let bound: Vec<(char, f64)> = [(t, value)].into_iter().chain(vars.iter().copied()).collect();
+        integrand.approximate_with(bank, &bound)
+    }, low, high, 1000)
+}
+
+fn parts(node: &Expression) -> (char, &Expression, &Expression, &Expression) {
+    match node {
+        Expression::Equa(equation) => match (equation.operation, &*equation.element1) {
+            (Operation::Integral(t), Expression::Equa(bounds)) => (t, &bounds.element1, &bounds.element2, &equation.element2),
+            _ => panic!("{} is not an integral", node),
+        },
+        _ => panic!("{} is not an integral", node),
+    }
+}
+
+/// An antiderivative of e with respect to var (without the + C), None when none of the
+/// rules apply. Letters other than var are treated as constants
+pub fn antiderivative(e: &Expression, var: char) -> Option<Expression> {
+    let e = simplify(e);
+    rules(&e, var).map(|f| simplify(&f))
+}
+
+fn parse(s: String) -> Expression {
+    Expression::from(&s)
+}
+
+/// d(u)/d(var) when u is linear in var
+fn slope(u: &Expression, var: char) -> Option<Expression> {
+    if !contains(u, var) {
+        return Some(Expression::from("0"));
+    }
+    let equation = match u {
+        Expression::Variable(_) => return Some(Expression::from("1")),
+        Expression::Equa(equation) => equation,
+        _ => return None,
+    };
+    let (a, b) = (&equation.element1, &equation.element2);
+    let m = match equation.operation {
+        Operation::Add => parse(format!("({})+({})", slope(a, var)?, slope(b, var)?)),
+        Operation::Sub => parse(format!("({})-({})", slope(a, var)?, slope(b, var)?)),
+        Operation::Mult if !contains(a, var) => parse(format!("({})({})", a, slope(b, var)?)),
+        Operation::Mult if !contains(b, var) => parse(format!("({})({})", slope(a, var)?, b)),
+        Operation::Div if !contains(b, var) => parse(format!("({})/({})", slope(a, var)?, b)),
+        _ => return None,
+    };
+    Some(simplify(&m))
+}
+
+fn rules(e: &Expression, var: char) -> Option<Expression> {
+    if !contains(e, var) {
+        return Some(parse(format!("({})({})", e, var)));
+    }
+    let equation = match e {
+        Expression::Variable(_) => return Some(parse(format!("(({})^(2))/(2)", var))),
+        Expression::Equa(equation) => equation,
+        _ => return None,
+    };
+    let (a, b) = (&equation.element1, &equation.element2);
+    let (free_a, free_b) = (!contains(a, var), !contains(b, var));
+
+    match equation.operation {
+        Operation::Add => Some(parse(format!("({})+({})", rules(a, var)?, rules(b, var)?))),
+        Operation::Sub => Some(parse(format!("({})-({})", rules(a, var)?, rules(b, var)?))),
+        Operation::Mult if free_a => Some(parse(format!("({})({})", a, rules(b, var)?))),
+        Operation::Mult if free_b => Some(parse(format!("({})({})", b, rules(a, var)?))),
+        Operation::Div if free_b => Some(parse(format!("({})/({})", rules(a, var)?, b))),
+        Operation::Div if free_a => Some(parse(format!("({})({})", a, reciprocal(b, var)?))),
+        Operation::Mult if distribute(e).is_some() => rules(&simplify(&distribute(e)?), var),
+        Operation::Exp if free_b && power_reduced(a, b, var).is_some() => power_reduced(a, b, var),
+        Operation::Exp if free_b && slope(a, var).is_none() => rules(&simplify(&distribute(e)?), var),
+        Operation::Exp if free_b => {
+            // u^n with u linear
+            let m = slope(a, var)?;
+            if simplify(b) == Expression::from("-1") {
+                Some(parse(format!("(ln(abs({})))/({})", a, m)))
+            } else {
+                Some(parse(format!("(({})^(({})+(1)))/((({})+(1))({}))", a, b, b, m)))
+            }
+        },
+        Operation::Exp if free_a => {
+            // a^u with u linear
+            let m = slope(b, var)?;
+            if **a == Expression::Variable('e') {
+                Some(parse(format!("((e)^({}))/({})", b, m)))
+            } else {
+                Some(parse(format!("(({})^({}))/(({})(ln({})))", a, b, m, a)))
+            }
+        },
+        Operation::Trig => {
+            let m = slope(b, var)?;
+            match **a {
+                Expression::Variable('s') => Some(parse(format!("(0-(cos({})))/({})", b, m))),
+                Expression::Variable('c') => Some(parse(format!("(sin({}))/({})", b, m))),
+                Expression::Variable('t') => Some(parse(format!("(0-(ln(abs(cos({})))))/({})", b, m))),
+                _ => None,
+            }
+        },
+        Operation::Log if **a == Expression::Variable('e') => {
+            let m = slope(b, var)?;
+            Some(parse(format!("((({})(ln({})))-({}))/({})", b, b, b, m)))
+        },
+        _ => None,
+    }
+}
+
+// sin(u)^2 = (1-cos(2u))/2 and cos(u)^2 = (1+cos(2u))/2 integrated, u linear
+fn power_reduced(a: &Expression, b: &Expression, var: char) -> Option<Expression> {
+    let inner = match a {
+        Expression::Equa(inner) if inner.operation == Operation::Trig && simplify(b) == Expression::from("2") => inner,
+        _ => return None,
+    };
+    let (u, m) = (&inner.element2, slope(&inner.element2, var)?);
+    let sign = match *inner.element1 {
+        Expression::Variable('s') => "-",
+        Expression::Variable('c') => "+",
+        _ => return None,
+    };
+    Some(parse(format!("((({})/(2)){}((sin((2)({})))/(4)))/({})", u, sign, u, m)))
+}
+
+// multiplies out products and whole number powers of sums so polynomials go term by term
+fn distribute(e: &Expression) -> Option<Expression> {
+    let equation = match e {
+        Expression::Equa(equation) => equation,
+        _ => return None,
+    };
+    let (a, b) = (&equation.element1, &equation.element2);
+    let sum = |e: &Expression| match e {
+        Expression::Equa(inner) if inner.operation == Operation::Add => Some(("+", copy_expression(&inner.element1), copy_expression(&inner.element2))),
+        Expression::Equa(inner) if inner.operation == Operation::Sub => Some(("-", copy_expression(&inner.element1), copy_expression(&inner.element2))),
+        _ => None,
+    };
+
+    match equation.operation {
+        Operation::Mult => {
+            // x(x+1)(x-1) needs the inside product multiplied out first
+            let (a, b) = (distribute(a).unwrap_or_else(|| copy_expression(a)), distribute(b).unwrap_or_else(|| copy_expression(b)));
+            if let Some((sign, p, q)) = sum(&a) {
+                Some(parse(format!("(({})({})){}(({})({}))", p, b, sign, q, b)))
+            } else {
+                let (sign, p, q) = sum(&b)?;
+                Some(parse(format!("(({})({})){}(({})({}))", a, p, sign, a, q)))
+            }
+        },
+        Operation::Exp => match **b {
+            Expression::Constant(n) if sum(a).is_some() && n.denom() == Some(&1) && n > Fraction::from(1) && n <= Fraction::from(8) =>
+                // multiplied out straight away, simplify would fold u(u) back into u^2
+                distribute(&parse(format!("({})(({})^({}))", a, a, n - Fraction::from(1)))),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// ∫ 1/e for the denominators that have a standard form
+fn reciprocal(e: &Expression, var: char) -> Option<Expression> {
+    // ln|u| so u < 0 works too
+    if let Some(m) = slope(e, var) {
+        return Some(parse(format!("(ln(abs({})))/({})", e, m)));
+    }
+    let equation = match e {
+        Expression::Equa(equation) => equation,
+        _ => return None,
+    };
+    let (a, b) = (&equation.element1, &equation.element2);
+
+    match equation.operation {
+        Operation::Exp if !contains(b, var) => {
+            let n = simplify(b);
+            // 1/cos(u)^2 = sec(u)^2
+            if let (Expression::Equa(inner), true) = (&**a, n == Expression::from("2")) {
+                if inner.operation == Operation::Trig && *inner.element1 == Expression::Variable('c') {
+                    let m = slope(&inner.element2, var)?;
+                    return Some(parse(format!("(tan({}))/({})", inner.element2, m)));
+                }
+            }
+            // 1/(1-u^2)^(1/2)
+            if let (Expression::Equa(inner), true) = (&**a, n == simplify(&Expression::from("1/2"))) {
+                if inner.operation == Operation::Sub && simplify(&inner.element1) == Expression::from("1") {
+                    let u = square_root_of(&inner.element2)?;
+                    let m = slope(&u, var)?;
+                    return Some(parse(format!("(arcsin({}))/({})", u, m)));
+                }
+            }
+            // 1/u^n = u^(-n)
+            rules(&parse(format!("({})^(0-({}))", a, b)), var)
+        },
+        // 1/a^u = a^(-u)
+        Operation::Exp if !contains(a, var) =>
+            rules(&parse(format!("({})^(0-({}))", a, b)), var),
+        // 1/(1+u^2)
+        Operation::Add if simplify(a) == Expression::from("1") => {
+            let u = square_root_of(b)?;
+            let m = slope(&u, var)?;
+            Some(parse(format!("(arctan({}))/({})", u, m)))
+        },
+        _ => None,
+    }
+}
+
+// u for u^2
+fn square_root_of(e: &Expression) -> Option<Expression> {
+    match e {
+        Expression::Equa(equation) if equation.operation == Operation::Exp && simplify(&equation.element2) == Expression::from("2") =>
+            Some(copy_expression(&equation.element1)),
+        _ => None,
+    }
+}
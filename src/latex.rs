@@ -0,0 +1,457 @@
+use super::*;
+
+impl Expression {
+    /// The expression as LaTeX with only the brackets precedence calls for, so
+    /// ((3)(x))+((2)^(x)) comes out as 3x + 2^{x} and constants as \frac{a}{b}
+    pub fn to_latex(&self) -> String {
+        latex(self)
+    }
+
+    /// Reads the LaTeX problems are written in into the tree Expression::from gives for the
+    /// ASCII version. \frac{d}{dx} is carried out as it's read since trees have no d/dx node,
+    /// it applies to the rest of its term. LaTeX that can't be read is an error at the
+    /// character it went wrong at
+    pub fn from_latex(latex: &str) -> Result<Expression, ParseError> {
+        let mut reader = Reader { chars: latex.chars().collect(), at: 0, differential: None };
+        let ascii = reader.until(&Closer::End)?;
+        if ascii.is_empty() {
+            return Err(reader.error("there's nothing to read"));
+        }
+        Ok(Expression::from(&ascii))
+    }
+}
+
+fn operand(e: &Expression, needs: u8) -> String {
+    if precedence(e) < needs { format!("\\left({}\\right)", latex(e)) } else { latex(e) }
+}
+
+fn constant(c: &Fraction) -> String {
+    let sign = if *c < Fraction::from(0) { "-" } else { "" };
+    if c.is_infinite() {
+        return format!("{}\\infty", sign);
+    }
+    match (c.numer(), c.denom()) {
+        (Some(n), Some(1)) => format!("{}{}", sign, n),
+        (Some(n), Some(d)) => format!("{}\\frac{{{}}}{{{}}}", sign, n, d),
+        _ => "\\text{undefined}".to_string(),
+    }
+}
+
+fn variable(v: char) -> String {
+    match v {
+        'π' => "\\pi".to_string(),
+        'θ' => "\\theta".to_string(),
+        _ => v.to_string(),
+    }
+}
+
+fn trig(name: &Expression) -> &str {
+    match name {
+        Expression::Variable('s') => "\\sin",
+        Expression::Variable('c') => "\\cos",
+        Expression::Variable('t') => "\\tan",
+        Expression::Variable('S') => "\\arcsin",
+        Expression::Variable('C') => "\\arccos",
+        Expression::Variable('T') => "\\arctan",
+        _ => panic!("{} isn't a trig function", name),
+    }
+}
+
+fn relation(relation: Relation) -> &'static str {
+    match relation {
+        Relation::Less => "<",
+        Relation::LessEqual => "\\le",
+        Relation::Greater => ">",
+        Relation::GreaterEqual => "\\ge",
+        Relation::Equal => "=",
+        Relation::And => panic!("a chain is two comparisons"),
+    }
+}
+
+fn bracketed(e: &Expression) -> String {
+    format!("\\left({}\\right)", latex(e))
+}
+
+fn latex(e: &Expression) -> String {
+    let equation = match e {
+        Expression::Constant(c) => return constant(c),
+        Expression::Variable(v) => return variable(*v),
+        Expression::Equa(equation) => equation,
+    };
+    let (a, b) = (&*equation.element1, &*equation.element2);
+    match equation.operation {
+        Operation::Add if negative(b) => format!("{} + {}", operand(a, 1), bracketed(b)),
+        Operation::Add => format!("{} + {}", operand(a, 1), operand(b, 1)),
+        Operation::Sub if *a == Expression::Constant(Fraction::from(0)) => format!("-{}", operand(b, 2)),
+        Operation::Sub => format!("{} - {}", operand(a, 1), operand(b, 2)),
+        Operation::Mult => {
+            let left = if negative(a) { latex(a) } else { operand(a, 2) };
+            let right = operand(b, 2);
+            // 2 \cdot 3 and x \cdot \frac{1}{2} would otherwise run together, as would \pi r
+            let dot = right.starts_with(|c: char| c.is_ascii_digit() || c == '-') || right.starts_with("\\frac");
+            let command = left.trim_end_matches(|c: char| c.is_ascii_alphabetic()).ends_with('\\')
+                && right.starts_with(|c: char| c.is_alphabetic());
+            let between = if dot { " \\cdot " } else if command { " " } else { "" };
+            format!("{}{}{}", left, between, right)
+        },
+        Operation::Div => format!("\\frac{{{}}}{{{}}}", latex(a), latex(b)),
+        Operation::Exp => match (a, b) {
+            // \sin^{2}\left(x\right)
+            (Expression::Equa(inner), Expression::Constant(n)) if inner.operation == Operation::Trig
+                && n.denom() == Some(&1) && *n > Fraction::from(0) =>
+                format!("{}^{{{}}}{}", trig(&inner.element1), n, bracketed(&inner.element2)),
+            _ => format!("{}^{{{}}}", operand(a, 4), latex(b)),
+        },
+        Operation::Func => format!("{}{}", latex(a), bracketed(b)),
+        Operation::Trig => format!("{}{}", trig(a), bracketed(b)),
+        Operation::Log => match a {
+            Expression::Variable('e') => format!("\\ln{}", bracketed(b)),
+            Expression::Constant(c) if *c == Fraction::from(10) => format!("\\log{}", bracketed(b)),
+            _ => format!("\\log_{{{}}}{}", latex(a), bracketed(b)),
+        },
+        Operation::Deriv => format!("{}'", latex(a)),
+        Operation::Sum(n) | Operation::Integral(n) => {
+            let (lower, upper) = match a {
+                Expression::Equa(bounds) => (latex(&bounds.element1), latex(&bounds.element2)),
+                _ => panic!("a sum or integral's first element is its bounds"),
+            };
+            if let Operation::Sum(_) = equation.operation {
+                format!("\\sum_{{{}={}}}^{{{}}} {}", n, lower, upper, operand(b, 2))
+            } else {
+                format!("\\int_{{{}}}^{{{}}} {} \\, d{}", lower, upper, latex(b), n)
+            }
+        },
+        Operation::Bounds => format!("{}, {}", latex(a), latex(b)),
+        Operation::Piecewise | Operation::Piece => {
+            let rows: Vec<String> = piecewise::pieces(e).iter()
+                .map(|(condition, value)| format!("{} & \\text{{if }} {}", latex(value), latex(condition)))
+                .collect();
+            format!("\\begin{{cases}} {} \\end{{cases}}", rows.join(" \\\\ "))
+        },
+        // a \le x < b
+        Operation::Relation(Relation::And) => match b {
+            Expression::Equa(second) => match second.operation {
+                Operation::Relation(r) => format!("{} {} {}", latex(a), relation(r), latex(&second.element2)),
+                _ => panic!("the second half of a chained condition is a relation"),
+            },
+            _ => panic!("the second half of a chained condition is a relation"),
+        },
+        Operation::Relation(r) => format!("{} {} {}", latex(a), relation(r), latex(b)),
+        Operation::Abs => format!("\\left|{}\\right|", latex(b)),
+        Operation::Floor => format!("\\left\\lfloor {} \\right\\rfloor", latex(b)),
+        Operation::Ceil => format!("\\left\\lceil {} \\right\\rceil", latex(b)),
+        Operation::Root => match a {
+            Expression::Constant(n) if *n == Fraction::from(2) => format!("\\sqrt{{{}}}", latex(b)),
+            _ => format!("\\sqrt[{}]{{{}}}", latex(a), latex(b)),
+        },
+    }
+}
+
+// (2/3), a fraction of whole numbers
+fn constant_fraction(text: &str) -> bool {
+    let whole = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    text.strip_prefix('(').and_then(|t| t.strip_suffix(')')).and_then(|t| t.split_once('/'))
+        .is_some_and(|(top, bottom)| whole(top) && whole(bottom))
+}
+
+// where a stretch of LaTeX stops, the dx of an integral included
+enum Closer<'a> {
+    End,
+    Text(&'a str),
+    Differential,
+}
+
+// LaTeX is turned into ASCII as it's read, whitespace dropped
+struct Reader {
+    chars: Vec<char>,
+    at: usize,
+    differential: Option<char>,
+}
+
+impl Reader {
+    fn error(&self, message: &str) -> ParseError {
+        ParseError { column: self.at, message: String::from(message) }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.at).copied()
+    }
+
+    fn starts(&self, text: &str) -> bool {
+        text.chars().enumerate().all(|(i, c)| self.chars.get(self.at + i) == Some(&c))
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        let found = self.starts(text);
+        if found {
+            self.at += text.chars().count();
+        }
+        found
+    }
+
+    fn skip_space(&mut self) {
+        while self.peek().is_some_and(|c| c.is_whitespace()) {
+            self.at += 1;
+        }
+    }
+
+    // dx, dt and so on, with the letter kept
+    fn differential(&mut self) -> bool {
+        let start = self.at;
+        if self.eat("d") {
+            self.skip_space();
+            if let Some(v) = self.peek().filter(|c| c.is_alphabetic()) {
+                self.at += 1;
+                if !self.peek().is_some_and(|c| c.is_alphanumeric()) {
+                    self.differential = Some(v);
+                    return true;
+                }
+            }
+        }
+        self.at = start;
+        false
+    }
+
+    fn until(&mut self, closer: &Closer) -> Result<String, ParseError> {
+        let mut out = String::new();
+        loop {
+            self.skip_space();
+            let done = match closer {
+                Closer::End => self.peek().is_none(),
+                Closer::Text(text) => self.eat(text),
+                Closer::Differential => self.differential(),
+            };
+            if done {
+                return Ok(out);
+            }
+            if self.peek().is_none() {
+                return Err(match closer {
+                    Closer::Text(text) => self.error(&format!("the LaTeX ends before the {} that closes this", text)),
+                    _ => self.error("an integral needs a dx at the end"),
+                });
+            }
+            let item = match self.negative(&out) { Some(number) => number, None => self.item()? };
+            out.push_str(&item);
+        }
+    }
+
+    // -2 and -\frac{2}{3} where nothing comes before the minus are the constants the ASCII
+    // (-2) and (-2/3) are, -2^2 is still -(2^2)
+    fn negative(&mut self, before: &str) -> Option<String> {
+        let unary = before.is_empty() || before.ends_with(['(', '+', '-', '*', '/', '=', '<', '>', ',', ';']);
+        if !unary || self.peek() != Some('-') {
+            return None;
+        }
+        let start = self.at;
+        self.at += 1;
+        self.skip_space();
+        let mut number = String::new();
+        while let Some(c) = self.peek().filter(|c| c.is_ascii_digit() || *c == '.') {
+            number.push(c);
+            self.at += 1;
+        }
+        if number.is_empty() && ["\\frac", "\\dfrac", "\\tfrac"].iter().any(|s| self.starts(s)) {
+            number = self.item().ok().filter(|f| constant_fraction(f)).unwrap_or_default();
+        }
+        self.skip_space();
+        if number.is_empty() || self.starts("^") {
+            self.at = start;
+            return None;
+        }
+        Some(format!("(-{})", number.trim_start_matches('(').trim_end_matches(')')))
+    }
+
+    // {a} or a single character, as in ^2 and _b
+    fn group(&mut self) -> Result<String, ParseError> {
+        self.skip_space();
+        let group = match self.peek() {
+            Some('{') => {
+                self.at += 1;
+                self.until(&Closer::Text("}"))?
+            },
+            Some(_) => self.item()?,
+            None => return Err(self.error("a group is missing at the end")),
+        };
+        if group.is_empty() {
+            return Err(self.error("a group can't be empty"));
+        }
+        Ok(group)
+    }
+
+    // a function's argument in brackets, \sin 2x is sin(2x)
+    fn argument(&mut self) -> Result<String, ParseError> {
+        self.skip_space();
+        if self.starts("\\left") || self.starts("(") || self.starts("{") || self.starts("[") {
+            let inside = self.item()?;
+            return Ok(if inside.starts_with('(') { inside } else { format!("({})", inside) });
+        }
+        let mut out = String::new();
+        while let Some(c) = self.peek().filter(|c| c.is_alphanumeric() || *c == '.') {
+            out.push(c);
+            self.at += 1;
+        }
+        if out.is_empty() {
+            if self.peek().is_none() {
+                return Err(self.error("a function needs an argument"));
+            }
+            out = self.item()?;
+        }
+        Ok(format!("({})", out))
+    }
+
+    // the rest of the term, which is what d/dx applies to
+    fn term(&mut self) -> Result<String, ParseError> {
+        let mut out = String::new();
+        loop {
+            self.skip_space();
+            let stop = ["+", "-", "=", "}", ")", "]", "\\right", "\\rfloor", "\\rceil"].iter().any(|s| self.starts(s));
+            if stop || self.peek().is_none() {
+                return Ok(out);
+            }
+            let item = match self.negative(&out) { Some(number) => number, None => self.item()? };
+            out.push_str(&item);
+        }
+    }
+
+    fn item(&mut self) -> Result<String, ParseError> {
+        let Some(c) = self.peek() else { return Err(self.error("ran out of LaTeX")) };
+        self.at += 1;
+        Ok(match c {
+            '{' => format!("({})", self.until(&Closer::Text("}"))?),
+            '(' => format!("({})", self.until(&Closer::Text(")"))?),
+            '[' => format!("({})", self.until(&Closer::Text("]"))?),
+            '^' => format!("^({})", self.group()?),
+            '\\' => self.command()?,
+            c => c.to_string(),
+        })
+    }
+
+    fn command(&mut self) -> Result<String, ParseError> {
+        let start = self.at - 1;
+        let mut name = String::new();
+        while let Some(c) = self.peek().filter(|c| c.is_ascii_alphabetic()) {
+            name.push(c);
+            self.at += 1;
+        }
+        // \\ ends a row of cases, \, \; \! and the like are spacing
+        if name.is_empty() {
+            let Some(c) = self.peek() else { return Err(self.error("a \\ needs a command after it")) };
+            self.at += 1;
+            return Ok(if c == '\\' { ";".to_string() } else { String::new() });
+        }
+        // the assert!s and expects of the commands below, pointing at the command
+        let wrong = |message: &str| ParseError { column: start, message: String::from(message) };
+        Ok(match name.as_str() {
+            "left" => match self.peek() {
+                Some('(') => { self.at += 1; format!("({})", self.until(&Closer::Text("\\right)"))?) },
+                Some('[') => { self.at += 1; format!("({})", self.until(&Closer::Text("\\right]"))?) },
+                Some('|') => { self.at += 1; format!("abs({})", self.until(&Closer::Text("\\right|"))?) },
+                _ if self.eat("\\lfloor") => format!("floor({})", self.until(&Closer::Text("\\right\\rfloor"))?),
+                _ if self.eat("\\lceil") => format!("ceil({})", self.until(&Closer::Text("\\right\\rceil"))?),
+                _ => return Err(wrong("\\left needs (, [, |, \\lfloor or \\lceil after it")),
+            },
+            "frac" | "dfrac" | "tfrac" => {
+                let (top, bottom) = (self.group()?, self.group()?);
+                if top == "d" && bottom == "dx" {
+                    let term = self.term()?;
+                    if term.is_empty() {
+                        return Err(wrong("\\frac{d}{dx} needs something to differentiate"));
+                    }
+                    format!("({})", simplify::simplify(&derivative::ddx(&Expression::from(&term))))
+                } else if constant_fraction(&format!("({}/{})", top, bottom)) {
+                    // \frac{2}{3} is the constant 2/3, as it is in ASCII
+                    format!("({}/{})", top, bottom)
+                } else {
+                    format!("(({})/({}))", top, bottom)
+                }
+            },
+            "sqrt" => {
+                self.skip_space();
+                if self.eat("[") {
+                    let index = self.until(&Closer::Text("]"))?;
+                    format!("root({},{})", index, self.group()?)
+                } else {
+                    format!("sqrt({})", self.group()?)
+                }
+            },
+            "sin" | "cos" | "tan" | "arcsin" | "arccos" | "arctan" | "ln" => {
+                // \sin^{-1} is arcsin, any other power goes on the outside
+                self.skip_space();
+                let power = if self.eat("^") { Some(self.group()?) } else { None };
+                let inverse = matches!(power.as_deref(), Some("-1" | "(-1)")) && name.len() == 3 && name != "ln";
+                let name = if inverse { format!("arc{}", name) } else { name };
+                let call = format!("{}{}", name, self.argument()?);
+                match power {
+                    Some(p) if !inverse => format!("({})^({})", call, p),
+                    _ => call,
+                }
+            },
+            "log" => {
+                self.skip_space();
+                if self.eat("_") {
+                    let base = self.group()?;
+                    format!("log_{}{}", base, self.argument()?)
+                } else {
+                    format!("log{}", self.argument()?)
+                }
+            },
+            "exp" => format!("e^{}", self.argument()?),
+            "int" => {
+                let definite = "only definite integrals can be read, \\int_a^b";
+                self.skip_space();
+                if !self.eat("_") {
+                    return Err(wrong(definite));
+                }
+                let lower = self.group()?;
+                self.skip_space();
+                if !self.eat("^") {
+                    return Err(wrong(definite));
+                }
+                let upper = self.group()?;
+                let integrand = self.until(&Closer::Differential)?;
+                let t = self.differential.take().unwrap();
+                format!("int({},{},{},{})", t, lower, upper, integrand)
+            },
+            // \sum_{n=1}^{\infty} takes the rest of the term as the summand
+            "sum" => {
+                let bounds = "a sum needs its index and bounds, \\sum_{n=a}^{b}";
+                self.skip_space();
+                if !self.eat("_") {
+                    return Err(wrong(bounds));
+                }
+                let start = self.group()?;
+                let Some((n, lower)) = start.split_once('=') else { return Err(wrong(bounds)) };
+                self.skip_space();
+                if !self.eat("^") {
+                    return Err(wrong(bounds));
+                }
+                let upper = self.group()?;
+                format!("sum({},{},{},{})", n, lower, upper, self.term()?)
+            },
+            // x^2 & \text{if } x < 1 \\ ..., read as {x^2 if x<1; ...}
+            "begin" => {
+                if self.group()? != "cases" {
+                    return Err(wrong("only the cases environment can be read"));
+                }
+                let rows = self.until(&Closer::Text("\\end{cases}"))?;
+                format!("{{{}}}", rows.replace("&if", "if").replace('&', "if"))
+            },
+            "text" => {
+                self.skip_space();
+                if self.eat("{") { self.until(&Closer::Text("}"))? } else { self.group()? }
+            },
+            "le" | "leq" => "<=".to_string(),
+            "ge" | "geq" => ">=".to_string(),
+            "lt" => "<".to_string(),
+            "gt" => ">".to_string(),
+            "lfloor" => format!("floor({})", self.until(&Closer::Text("\\rfloor"))?),
+            "lceil" => format!("ceil({})", self.until(&Closer::Text("\\rceil"))?),
+            "cdot" | "times" => "*".to_string(),
+            "pi" => "π".to_string(),
+            "theta" => "θ".to_string(),
+            "infty" => "inf".to_string(),
+            "quad" | "qquad" => String::new(),
+            _ => return Err(wrong(&format!("\\{} isn't LaTeX that can be read", name))),
+        })
+    }
+}
@@ -1,10 +1,38 @@
 use std::{fmt, str::FromStr, collections::HashMap};
-use fraction::{Fraction,Sign,ToPrimitive};
-use num::pow;
+use fraction::{Fraction,ToPrimitive};
 
 pub mod derivative;
+pub mod numeric;
+pub mod series;
+pub mod diffeq;
+pub mod simplify;
+pub mod integral;
+pub mod limits;
+pub mod applications;
+pub mod curves;
+pub mod motion;
+pub mod tangent;
+pub mod theorems;
+pub mod continuity;
+pub mod domain;
+pub mod piecewise;
+pub mod latex;
+pub mod pretty;
+pub mod mathml;
+pub mod session;
+#[cfg(feature = "cli")]
+pub mod server;
+pub mod workspace;
+#[cfg(feature = "serde")]
+mod schema;
 
-type Bank = HashMap<char, Letter>;
+/// The letters defined so far, functions of x, variables and accumulation functions. The
+/// methods that change it are in the workspace module
+#[derive(Debug)]
+#[derive(Default)]
+pub struct Workspace {
+    letters: HashMap<char, Letter>,
+}
 
 #[derive(PartialEq)]
 #[derive(Debug)]
@@ -22,11 +50,27 @@ pub enum Expression{
     Equa(Box<Equation>),
 }
 
+/// ASCII or LaTeX that couldn't be read, column is the character it went wrong at counting
+/// from 0
+#[derive(PartialEq)]
+#[derive(Debug)]
+pub struct ParseError {
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "column {}: {}", self.column, self.message)
+    }
+}
+
 #[derive(PartialEq)]
 #[derive(Debug)]
 pub enum Letter {
     Function(Value),
     Variable(Value),
+    Accumulation(Value), // g(x) = int(t,a,x,f(t)), a function whose derivative is known by FTC
 }
 
 #[derive(PartialEq)]
@@ -38,6 +82,7 @@ pub enum Value {
 
 #[derive(PartialEq)]
 #[derive(Debug)]
+#[derive(Clone, Copy)]
 enum Operation { 
     Add,
     Sub,
@@ -48,45 +93,74 @@ enum Operation {
     Trig, // element 1 denotes which func s,c,t with arc being caps (S,C,T)
     Log, // element 1 will represent base
     Deriv,
+    Sum(char), // holds the index, element 1 is the Bounds and element 2 the summand
+    Integral(char), // holds the variable of integration, laid out like Sum
+    Bounds, // only found as element 1 of a Sum or Integral, element 1 is lower and element 2 upper
+    Piecewise, // element 1 is a Piece and element 2 the rest, another Piecewise or the last Piece
+    Piece, // element 1 is the condition and element 2 the value
+    Relation(Relation), // a condition, element 1 on the left and element 2 on the right
+    Abs, // element 1 is unused and always 1, element 2 is the argument
+    Floor, // laid out like Abs
+    Ceil, // laid out like Abs
+    Root, // element 1 is the index, 2 for sqrt and 3 for cbrt, element 2 is the radicand
+}
+
+#[derive(PartialEq)]
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+enum Relation {
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Equal,
+    And, // a<=x<b, element 1 is a<=x and element 2 is x<b
 }
 
-pub fn new_bank(function: char, input: &str) -> Bank {
-    let mut bank: Bank = Bank::new();
+pub fn new_bank(function: char, input: &str) -> Workspace {
+    let mut bank: Workspace = Workspace::new();
     
     let i = 0; for character in input.chars() { 
         if character.is_alphabetic() { 
             if input.chars().nth(i+1) != Some('(') {
-                bank.insert(character, Letter::Variable(Value::Undefined));
-            } else if !(bank.contains_key(&character)) && input.chars().nth(i+1) == Some('(') {
-                bank.insert(character, Letter::Function(Value::Undefined));
+                bank.letters.insert(character, Letter::Variable(Value::Undefined));
+            } else if !(bank.contains(&character)) && input.chars().nth(i+1) == Some('(') {
+                bank.letters.insert(character, Letter::Function(Value::Undefined));
             }
         }
     }
 
-    bank.insert(function, Letter::Function(Value::Defined(Expression::from(input))));
+    add_func_to_bank(&mut bank, function, input);
 
     bank
 }
 
+// the free functions panic on what the Workspace methods turn down
+pub fn add_func_to_bank(bank: &mut Workspace, f: char, input: &str) {
+    bank.define_function(f, Expression::from(input)).unwrap_or_else(|error| panic!("{}", error));
+}
 
-pub fn add_func_to_bank(bank: &mut Bank, f: char, input: &str) {
-    bank.insert(f,Letter::Function(Value::Defined(Expression::from(input))));
+pub fn add_var_to_bank(bank: &mut Workspace, f: char, input: &str) {
+    bank.define_variable(f, Expression::from(input)).unwrap_or_else(|error| panic!("{}", error));
 }
 
-pub fn add_var_to_bank(bank: &mut Bank, f: char, input: &str) {
-    bank.insert(f,Letter::Variable(Value::Defined(Expression::from(input))));
+/// g(x) = ∫_lower^x integrand dt where t is the integrand's variable
+pub fn add_accumulation_to_bank(bank: &mut Workspace, g: char, integrand: &str, t: char, lower: &str) {
+    let definition = Expression::from(&format!("int({},{},x,{})", t, lower, integrand));
+    bank.define_accumulation(g, definition).unwrap_or_else(|error| panic!("{}", error));
 }
 
-pub fn get_expression(bank: &Bank, exp: &Expression) -> Expression {
+pub fn get_expression(bank: &Workspace, exp: &Expression) -> Expression {
     let exp_cpy = copy_expression(exp);
 
     // match for variable or equation
     match exp {
         Expression::Variable(v) => {
-            let letter = bank.get(&v).unwrap();
+            let letter = bank.get(v).unwrap();
             let fin = match letter {
                 Letter::Function(Value::Defined(f)) => f,
                 Letter::Variable(Value::Defined(v)) => v,
+                Letter::Accumulation(Value::Defined(g)) => g,
                 _ => panic!("Probably undefined value"),
             };
             let fins = format!("{fin}");
@@ -94,7 +168,7 @@ pub fn get_expression(bank: &Bank, exp: &Expression) -> Expression {
         },
         Expression::Equa(e) => {
             if e.operation == Operation::Deriv {
-                derivative::ddx(&*e.element1)
+                derivative::ddx(&e.element1)
             } else {
                 exp_cpy 
             }
@@ -103,170 +177,272 @@ pub fn get_expression(bank: &Bank, exp: &Expression) -> Expression {
     }
 }
 
-impl Expression {
-    pub fn from(string: &str) -> Expression {
-        //println!!("expression from {}", string);
-        let mut i: usize = 0;
-        let mut bracket_counter = 0;
-        let mut last_add: usize = 0;
-        let mut last_sub: usize = 0;
-        let mut last_mult: usize = 0;
-        let mut last_div: usize = 0;
-        let mut last_func: usize = 0;
-        let mut last_implied: usize = 0;
-        let mut last_exp: usize = 0;
-        let mut last_spec: usize = 0;
-        let mut last_deriv: usize = 0;
-
-
-        let first = string.chars().nth(0).unwrap();
-        let last  = string.chars().nth(string.len()-1).unwrap();
-
-        let string = if (first,last) == ('(',')') {
-            if is_wrapped(string) {&string[1..string.len()-1]}else{string}
-        } else {
-            string
-        };
+// string is a part of whole, which columns count from
+fn read(whole: &str, string: &str) -> Result<Expression, ParseError> {
+    if string.is_empty() {
+        return Err(at(whole, string, "something is missing here"));
+    }
+    let mut bracket_counter = 0;
+    let mut last_add: usize = 0;
+    let mut last_sub: usize = 0;
+    let mut last_mult: usize = 0;
+    let mut last_div: usize = 0;
+    let mut last_func: usize = 0;
+    let mut last_implied: usize = 0;
+    let mut last_exp: usize = 0;
+    let mut last_spec: usize = 0;
+    let mut last_deriv: usize = 0;
 
 
-        let mut last_char = '(';
-        let mut during_name = false;
-        // find operators
-        for character in string.chars() {
-            if bracket_counter == 0 {
-                match character {
-                    '+' => last_add  = i, 
-                    '-' => last_sub  = i,
-                    '*' => last_mult = i,
-                    '/' => last_div  = i,
-                    '^' => last_exp  = i,
-                    '(' => { if last_char.is_alphabetic() { last_func = i }
-                            bracket_counter += 1 
-                            },
-                   '\'' => last_deriv = i,
-                    _   => (),
-                }
-                // a(x) needs to be counted as a function, even if it is a*x it will be handled later
-                if is_implied_mult(last_char, character) && !is_func(last_char, character)  { 
-                    last_implied = i 
-                }
-                if is_special(string, i) {
-                    during_name = true;
-                    bracket_counter -= 1
-                }
-            } else {
-                match character {
-                    '(' => { bracket_counter += 1;
-                             if during_name {
-                                 during_name = false;
-                                 last_spec = i;
-                                 bracket_counter +=1; // inside the name is skipped via brackets
-                             }
-                           },
-                    ')' => bracket_counter -= 1,
-                    _   => (),
-                }
-            }
+    // x², √x and the rest of what unicode() prints
+    if string.contains(|c: char| "⁰¹²³⁴⁵⁶⁷⁸⁹⁻√∛·′−∞≤≥".contains(c)) {
+        return Expression::parse(&pretty::plain(string)).map_err(|error| at(whole, string, &error.message));
+    }
 
-            i+=1;
-            last_char = character;
+    // |x| is read as abs(x)
+    if string.contains('|') {
+        let Some(barred) = bars(string) else { return Err(at(whole, string, "a | isn't closed")) };
+        return Expression::parse(&barred).map_err(|error| at(whole, string, &error.message));
+    }
+
+    // strip every layer of brackets around the whole thing, ((x)^(2)) inside a / has two
+    let mut string = string;
+    while string.starts_with('(') && string.ends_with(')') && is_wrapped(string) {
+        string = &string[1..string.len()-1];
+    }
+
+    if string.starts_with('{') && string.ends_with('}') && is_braced(string) {
+        return piecewise_from(whole, &string[1..string.len()-1]);
+    }
+
+    // 1/2 is the constant, which is how constants display
+    if let Some((numerator, denominator)) = string.split_once('/') {
+        let digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+        if digits(numerator) && digits(denominator) && denominator.chars().any(|c| c != '0') {
+            return Ok(Expression::Constant(Fraction::from_str(string).unwrap()));
         }
+    }
+
+    // inf would otherwise be read as i*n*f and pi as p*i
+    match string {
+        "inf" => return Ok(Expression::Constant(Fraction::infinity())),
+        "-inf" => return Ok(Expression::Constant(Fraction::neg_infinity())),
+        "pi" => return Ok(Expression::Variable('π')),
+        _ => (),
+    }
+
+    let mut last_char = '(';
+    let mut during_name = false;
+    // find operators, i is a byte index so names like π can be sliced around
+    for (i, character) in string.char_indices() {
+        if bracket_counter == 0 {
+            match character {
+                '+' => last_add  = i, 
+                '-' => last_sub  = i,
+                '*' => last_mult = i,
+                '/' => last_div  = i,
+                '^' => last_exp  = i,
+                '(' => { if last_char.is_alphabetic() || last_char == '\'' { last_func = i }
+                        bracket_counter += 1 
+                        },
+                '{' => bracket_counter += 1,
+               '\'' => last_deriv = i,
+                _   => (),
+            }
+            // a(x) needs to be counted as a function, even if it is a*x it will be handled later
+            if is_implied_mult(last_char, character) && !is_func(last_char, character)
+                && !(last_char == 'p' && character == 'i') { 
+                last_implied = i 
+            }
+            if is_special(string, i) {
+                during_name = true;
+                bracket_counter -= 1
+            }
+        } else {
+            match character {
+                '(' => { bracket_counter += 1;
+                         if during_name {
+                             during_name = false;
+                             last_spec = i;
+                             bracket_counter +=1; // inside the name is skipped via brackets
+                         }
+                       },
+                ')' | '}' => bracket_counter -= 1,
+                '{' => bracket_counter += 1,
+                _   => (),
+            }
+        }
+
+        last_char = character;
+    }
+    
         
-            
-        // find op to use SAMDEB order
-        if last_add != 0 {
-            ////println!!("{} + {}",&string[..last_add],&string[last_add+1..]);
-            Expression::Equa(Box::new(Equation {
-                operation: Operation::Add,
-                element1: Box::new(Expression::from(&string[..last_add])),
-                element2: Box::new(Expression::from(&string[last_add+1..])),
-            }))
-        } else if last_sub != 0 {
-            Expression::Equa(Box::new(Equation {
+    // find op to use SAMDEB order, implied mult goes before exponents so 3x^2 is 3(x^2)
+    Ok(if last_add != 0 {
+        ////println!!("{} + {}",&string[..last_add],&string[last_add+1..]);
+        Expression::Equa(Box::new(Equation {
+            operation: Operation::Add,
+            element1: Box::new(read(whole, &string[..last_add])?),
+            element2: Box::new(read(whole, &string[last_add+1..])?),
+        }))
+    } else if last_sub != 0 {
+        // x*-1 would be x* take away 1, the negative needs brackets
+        if let Some(op) = string[..last_sub].chars().last().filter(|c| "*/^".contains(*c)) {
+            return Err(at(whole, &string[last_sub..], &format!("a negative after {} needs brackets, like {}(-1)", op, op)));
+        }
+        Expression::Equa(Box::new(Equation {
+            operation: Operation::Sub,
+            element1: Box::new(read(whole, &string[..last_sub])?),
+            element2: Box::new(read(whole, &string[last_sub+1..])?),
+        }))
+    } else if let Some(negated) = string.strip_prefix('-') {
+        // leading minus, -3 stays a constant but -x^2 becomes 0-(x^2)
+        match Fraction::from_str(string) {
+            Ok(frac) => Expression::Constant(frac),
+            Err(_) => Expression::Equa(Box::new(Equation {
                 operation: Operation::Sub,
-                element1: Box::new(Expression::from(&string[..last_sub])),
-                element2: Box::new(Expression::from(&string[last_sub+1..])),
-            }))
-        } else if last_mult != 0 {
-            Expression::Equa(Box::new(Equation {
-                operation: Operation::Mult,
-                element1: Box::new(Expression::from(&string[..last_mult])),
-                element2: Box::new(Expression::from(&string[last_mult+1..])),
-            }))
-        } else if last_div != 0 {
-            Expression::Equa(Box::new(Equation {
-                operation: Operation::Div,
-                element1: Box::new(Expression::from(&string[..last_div])),
-                element2: Box::new(Expression::from(&string[last_div+1..])),
-            }))
-        } else if last_exp != 0 {
-            Expression::Equa(Box::new(Equation {
-                operation: Operation::Exp,
-                element1: Box::new(Expression::from(&string[..last_exp])),
-                element2: Box::new(Expression::from(&string[last_exp+1..])),
-            }))
-        } else if last_implied != 0 {
-            let x = &string[..last_implied];
-            let y = &string[last_implied..];
-            Expression::Equa(Box::new(Equation {
-                operation: Operation::Mult,
-                element1: Box::new(Expression::from(&string[..last_implied])),
-                element2: Box::new(Expression::from(&string[last_implied..])),
-            }))
-        } else if last_func != 0 { 
-            Expression::Equa(Box::new(Equation {
-                operation: Operation::Func,
-                element1: Box::new(Expression::from(&string[..last_func])),
-                element2: Box::new(Expression::from(&string[last_func..])),
+                element1: Box::new(Expression::Constant(Fraction::from(0))),
+                element2: Box::new(read(whole, negated)?),
+            })),
+        }
+    } else if last_mult != 0 {
+        Expression::Equa(Box::new(Equation {
+            operation: Operation::Mult,
+            element1: Box::new(read(whole, &string[..last_mult])?),
+            element2: Box::new(read(whole, &string[last_mult+1..])?),
+        }))
+    } else if last_div != 0 {
+        Expression::Equa(Box::new(Equation {
+            operation: Operation::Div,
+            element1: Box::new(read(whole, &string[..last_div])?),
+            element2: Box::new(read(whole, &string[last_div+1..])?),
+        }))
+    } else if last_implied != 0 {
+        Expression::Equa(Box::new(Equation {
+            operation: Operation::Mult,
+            element1: Box::new(read(whole, &string[..last_implied])?),
+            element2: Box::new(read(whole, &string[last_implied..])?),
+        }))
+    } else if last_exp != 0 {
+        Expression::Equa(Box::new(Equation {
+            operation: Operation::Exp,
+            element1: Box::new(read(whole, &string[..last_exp])?),
+            element2: Box::new(read(whole, &string[last_exp+1..])?),
+        }))
+    } else if last_func != 0 { 
+        Expression::Equa(Box::new(Equation {
+            operation: Operation::Func,
+            element1: Box::new(read(whole, &string[..last_func])?),
+            element2: Box::new(read(whole, &string[last_func..])?),
+        }))
+    } else if last_spec != 0 {
+        let mut trig = true;
+        
+        let name = match &string[..last_spec] {
+            "sum" => {
+                // sum(n,lower,upper,summand)
+                let args = split_args(&string[last_spec..]);
+                if args.len() != 4 {
+                    return Err(at(whole, string, "sum needs an index, two bounds and a summand, sum(n,a,b,...)"));
+                }
+                return Ok(Expression::Equa(Box::new(Equation {
+                    operation: Operation::Sum(get_name(whole, args[0])?),
+                    element1: Box::new(Expression::Equa(Box::new(Equation {
+                        operation: Operation::Bounds,
+                        element1: Box::new(read(whole, args[1])?),
+                        element2: Box::new(read(whole, args[2])?),
+                    }))),
+                    element2: Box::new(read(whole, args[3])?),
+                })))
+            },
+            "int" => {
+                // int(t,lower,upper,integrand)
+                let args = split_args(&string[last_spec..]);
+                if args.len() != 4 {
+                    return Err(at(whole, string, "int needs a variable, two bounds and an integrand, int(t,a,b,...)"));
+                }
+                return Ok(Expression::Equa(Box::new(Equation {
+                    operation: Operation::Integral(get_name(whole, args[0])?),
+                    element1: Box::new(Expression::Equa(Box::new(Equation {
+                        operation: Operation::Bounds,
+                        element1: Box::new(read(whole, args[1])?),
+                        element2: Box::new(read(whole, args[2])?),
+                    }))),
+                    element2: Box::new(read(whole, args[3])?),
+                })))
+            },
+            "abs" => return Ok(equa(Operation::Abs, Expression::from("1"), read(whole, &string[last_spec..])?)),
+            "floor" => return Ok(equa(Operation::Floor, Expression::from("1"), read(whole, &string[last_spec..])?)),
+            "ceil" => return Ok(equa(Operation::Ceil, Expression::from("1"), read(whole, &string[last_spec..])?)),
+            "sqrt" => return Ok(equa(Operation::Root, Expression::from("2"), read(whole, &string[last_spec..])?)),
+            "cbrt" => return Ok(equa(Operation::Root, Expression::from("3"), read(whole, &string[last_spec..])?)),
+            "root" => {
+                // root(n,x)
+                let args = split_args(&string[last_spec..]);
+                if args.len() != 2 {
+                    return Err(at(whole, string, "root needs an index and a radicand, root(n,...)"));
+                }
+                return Ok(equa(Operation::Root, read(whole, args[0])?, read(whole, args[1])?));
+            },
+            "sin" => "s",
+            "cos" => "c",
+            "tan" => "t",
+            "arcsin" => "S",
+            "arccos" => "C",
+            "arctan" => "T",
+            "ln" => {trig=false; "e"}
+            "log" => {trig=false; "10"}
+            s if s.starts_with("log") => {trig=false; &s[4..]}
+            name => return Err(at(whole, string, &format!("{} isn't a function the calculator knows", name))),
+        };
+        if trig { Expression::Equa(Box::new(Equation {
+            operation: Operation::Trig,
+            element1: Box::new(read(whole, name)?),
+            element2: Box::new(read(whole, &string[last_spec..])?),
             }))
-        } else if last_spec != 0 {
-            let mut trig = true;
-            
-            let name = match &string[..last_spec] {
-                "sin" => "s",
-                "cos" => "c",
-                "tan" => "t",
-                "arcsin" => "S",
-                "arccos" => "C",
-                "arctan" => "T",
-                "ln" => {trig=false; "e"}
-                "log" => {trig=false; "10"}
-                s if s.starts_with("log") => {trig=false; &s[4..]}
-                _ => panic!("last spec but doesnt match")
-            };
-            if trig { Expression::Equa(Box::new(Equation {
-                operation: Operation::Trig,
-                element1: Box::new(Expression::from(name)),
-                element2: Box::new(Expression::from(&string[last_spec..])),
-                }))
-            } else { Expression::Equa(Box::new(Equation {
-                operation: Operation::Log,
-                element1: Box::new(Expression::from(name)),
-                element2: Box::new(Expression::from(&string[last_spec..])),
-                }))
-            }
-        } else if last_deriv != 0 {
-            //println!!("{} is a deriv",&string[..last_deriv]);
-            Expression::Equa(Box::new(Equation {
-                operation: Operation::Deriv,
-                element1: Box::new(Expression::from(&string[..last_deriv])),
-                element2: Box::new(Expression::Variable('!')), // element 2 is meaningless for deriv
+        } else { Expression::Equa(Box::new(Equation {
+            operation: Operation::Log,
+            element1: Box::new(read(whole, name)?),
+            element2: Box::new(read(whole, &string[last_spec..])?),
             }))
-        } else {
-           let as_frac = Fraction::from_str(string);
-
-           match as_frac {
-               Ok(frac) => Expression::Constant(frac),
-               Err(error) => {
-                   Expression::Variable(get_name(string))
-               },
-           }
-        }             
-    } 
+        }
+    } else if last_deriv != 0 {
+        //println!!("{} is a deriv",&string[..last_deriv]);
+        Expression::Equa(Box::new(Equation {
+            operation: Operation::Deriv,
+            element1: Box::new(read(whole, &string[..last_deriv])?),
+            element2: Box::new(Expression::Variable('!')), // element 2 is meaningless for deriv
+        }))
+    } else {
+        match Fraction::from_str(string) {
+            Ok(frac) => Expression::Constant(frac),
+            Err(_) => Expression::Variable(get_name(whole, string)?),
+        }
+    })
+}
+
+impl Expression {
+    /// The tree for the ASCII in string, panics on what parse turns down
+    pub fn from(string: &str) -> Expression {
+        Expression::parse(string).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Reads ASCII like 3x^2+sin(x) into a tree, whitespace is dropped first. What can't be
+    /// read is an error at the character it went wrong at
+    pub fn parse(string: &str) -> Result<Expression, ParseError> {
+        let (squeezed, columns): (String, Vec<usize>) = string.chars().enumerate()
+            .filter(|(_, c)| !c.is_whitespace())
+            .map(|(i, c)| (c, i))
+            .unzip();
+        read(&squeezed, &squeezed).map_err(|error| ParseError {
+            column: columns.get(error.column).copied().unwrap_or(string.chars().count()),
+            message: error.message,
+        })
+    }
 
-    pub fn evaluate(&self, bank: &Bank, x: Fraction) -> Fraction {
+    pub fn evaluate(&self, bank: &Workspace, x: Fraction) -> Fraction {
         if let Expression::Equa(equation) = self {
-            return match &equation.operation {
+            match &equation.operation {
                 Operation::Add => 
                     equation.element1.evaluate(bank, x) + equation.element2.evaluate(bank, x),
                 Operation::Sub => 
@@ -277,8 +453,11 @@ impl Expression {
                     equation.element1.evaluate(bank, x) / equation.element2.evaluate(bank, x),
                 Operation::Exp =>
                     power(equation.element1.evaluate(bank, x), equation.element2.evaluate(bank, x)),
+                Operation::Func if !is_function(bank, &equation.element1) =>
+                    equation.element1.evaluate(bank, x) * equation.element2.evaluate(bank, x),
                 Operation::Func => {
-                    get_expression(bank,&equation.element1).evaluate(bank,x)
+                    let arg = equation.element2.evaluate(bank, x);
+                    get_expression(bank,&equation.element1).evaluate(bank,arg)
                     //if let Expression::Variable(f) = *equation.element1 {
                     //        match bank.get(&f).unwrap() {
                     //            Letter::Function(Value::Defined(value)) => 
@@ -301,13 +480,25 @@ impl Expression {
                     let element1_cpy = copy_expression(&equation.element1);
                     derivative::eval_deriv(element1_cpy, bank, x).evaluate(bank, x)
                 }
-                _   => panic!("No OP BUT EQUATION"),
+                Operation::Sum(n) => {
+                    series::evaluate_sum(*n, &equation.element1, &equation.element2, bank, x)
+                }
+                Operation::Integral(_) => integral::evaluate_node(bank, self, x),
+                Operation::Piecewise | Operation::Piece => piecewise::evaluate_node(bank, self, x),
+                Operation::Abs => equation.element2.evaluate(bank, x).abs(),
+                Operation::Floor => equation.element2.evaluate(bank, x).floor(),
+                Operation::Ceil => equation.element2.evaluate(bank, x).ceil(),
+                Operation::Root => root(equation.element1.evaluate(bank, x), equation.element2.evaluate(bank, x)),
+                Operation::Bounds => panic!("bounds only make sense inside a sum or integral"),
+                Operation::Relation(_) => panic!("a condition is true or false, not a number"),
             }
         } else {
             match self {
                 Expression::Constant(constant) => *constant,
                 Expression::Variable('x')      => x,
-                Expression::Variable(name)     => if let Letter::Variable(Value::Defined(v)) = bank.get(&name).unwrap() {
+                Expression::Variable(name) if (*name == 'e' || *name == 'π') && !matches!(bank.get(name), Some(Letter::Variable(Value::Defined(_)))) =>
+                                                  Fraction::from(if *name == 'e' { std::f64::consts::E } else { std::f64::consts::PI }),
+                Expression::Variable(name)     => if let Letter::Variable(Value::Defined(v)) = bank.get(name).unwrap() {
                                                     v.evaluate(bank, x)  
                                                   } else {
                                                     panic!("TRYING TO FIND UNDEFIEND VALUE")
@@ -316,16 +507,104 @@ impl Expression {
                 }
         }
     }
+
+    /// Evaluates with f64s instead of fractions, which is what numeric methods want since
+    /// fractions built from floats overflow quickly
+    pub fn approximate(&self, bank: &Workspace, x: f64) -> f64 {
+        self.approximate_with(bank, &[('x', x)])
+    }
+
+    /// Like approximate but with any letters bound, e.g. [('x', 1.0), ('y', 2.0)] for F(x,y).
+    /// Bound letters win over the bank, e and π are known constants
+    pub fn approximate_with(&self, bank: &Workspace, vars: &[(char, f64)]) -> f64 {
+        match self {
+            Expression::Constant(constant) => constant.to_f64().unwrap_or(f64::NAN),
+            Expression::Variable(name) => {
+                if let Some((_, value)) = vars.iter().find(|(v, _)| v == name) {
+                    return *value;
+                }
+                match (name, bank.get(name)) {
+                    (_, Some(Letter::Variable(Value::Defined(v)))) => v.approximate_with(bank, vars),
+                    ('e', _) => std::f64::consts::E,
+                    ('π', _) => std::f64::consts::PI,
+                    _ => panic!("TRYING TO FIND UNDEFIEND VALUE {}", name),
+                }
+            },
+            Expression::Equa(equation) => {
+                let a = || equation.element1.approximate_with(bank, vars);
+                let b = || equation.element2.approximate_with(bank, vars);
+                match &equation.operation {
+                    Operation::Add => a() + b(),
+                    Operation::Sub => a() - b(),
+                    Operation::Mult => a() * b(),
+                    Operation::Div => a() / b(),
+                    Operation::Exp => match (a(), &*equation.element2) {
+                        // odd roots of negatives are real, (-8)^(2/3) = 4
+                        (base, Expression::Constant(n)) if base < 0.0 && n.denom().is_some_and(|d| d % 2 == 1) && *n.denom().unwrap() != 1 => {
+                            let magnitude = base.abs().powf(n.to_f64().unwrap());
+                            if n.numer().is_some_and(|m| m % 2 == 1) { -magnitude } else { magnitude }
+                        },
+                        (base, _) => base.powf(b()),
+                    },
+                    Operation::Func if !is_function(bank, &equation.element1) => a() * b(),
+                    Operation::Func => {
+                        // unlike evaluate the argument is actually plugged in
+                        let arg = b();
+                        get_expression(bank, &equation.element1).approximate_with(bank, &[('x', arg)])
+                    },
+                    Operation::Trig => {
+                        let arg = b();
+                        match *equation.element1 {
+                            Expression::Variable('s') => arg.sin(),
+                            Expression::Variable('c') => arg.cos(),
+                            Expression::Variable('t') => arg.tan(),
+                            Expression::Variable('S') => arg.asin(),
+                            Expression::Variable('C') => arg.acos(),
+                            Expression::Variable('T') => arg.atan(),
+                            _ => panic!("tried to find trig function {}", equation.element1),
+                        }
+                    },
+                    Operation::Log => {
+                        if let Expression::Variable('e') = *equation.element1 {
+                            b().ln()
+                        } else {
+                            b().log(a())
+                        }
+                    },
+                    Operation::Deriv => {
+                        let element1_cpy = copy_expression(&equation.element1);
+                        derivative::eval_deriv(element1_cpy, bank, Fraction::from(0)).approximate_with(bank, vars)
+                    },
+                    Operation::Sum(n) => {
+                        series::approximate_sum(*n, &equation.element1, &equation.element2, bank, vars)
+                    },
+                    Operation::Integral(_) => integral::approximate_node(bank, self, vars),
+                    Operation::Piecewise | Operation::Piece => piecewise::approximate_node(bank, self, vars),
+                    Operation::Abs => b().abs(),
+                    Operation::Floor => b().floor(),
+                    Operation::Ceil => b().ceil(),
+                    Operation::Root => match (a(), b()) {
+                        // odd roots of negatives are real
+                        (n, radicand) if radicand < 0.0 && n.fract() == 0.0 && n % 2.0 != 0.0 => -(-radicand).powf(1.0 / n),
+                        (n, radicand) => radicand.powf(1.0 / n),
+                    },
+                    Operation::Bounds => panic!("bounds only make sense inside a sum or integral"),
+                    Operation::Relation(_) => panic!("a condition is true or false, not a number"),
+                }
+            },
+        }
+    }
 }
 
 impl Letter {
     pub fn get_inside(&self) -> &Value {
-        return match self {
+        match self {
             Letter::Function(x) => x,
             Letter::Variable(x) => x,
+            Letter::Accumulation(x) => x,
         }
     }
-    pub fn evaluate(&self, bank: &Bank, x: Fraction) -> Fraction {
+    pub fn evaluate(&self, bank: &Workspace, x: Fraction) -> Fraction {
         let inside = self.get_inside();
         if let Value::Defined(value) = inside {
             value.evaluate(bank, x)
@@ -346,14 +625,14 @@ impl Letter {
 
 impl fmt::Display for Expression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        return if let Expression::Equa(equation) = self {
+        if let Expression::Equa(equation) = self {
             match &equation.operation {
                 Operation::Add => write!(f,"({})+({})",*equation.element1,*equation.element2),
                 Operation::Sub => write!(f,"({})-({})",*equation.element1,*equation.element2),
                 Operation::Mult => write!(f,"({})({})",*equation.element1,*equation.element2), //×
                 Operation::Div => write!(f,"({})/({})",*equation.element1,*equation.element2),
                 Operation::Func => write!(f,"({}({}))",*equation.element1,*equation.element2),
-                Operation::Exp => write!(f,"({}^({}))",*equation.element1,*equation.element2),
+                Operation::Exp => write!(f,"(({})^({}))",*equation.element1,*equation.element2),
                 Operation::Trig => {
                     if let Expression::Variable(name) = *equation.element1 {
                         match name {
@@ -372,17 +651,43 @@ impl fmt::Display for Expression {
                     let ten = Fraction::from(10);
                     match *equation.element1 {
                         Expression::Variable('e') => write!(f,"(ln({}))",*equation.element2),
-                        Expression::Variable(v) => write!(f,"(log{}({}))",v,*equation.element2),
+                        Expression::Variable(v) => write!(f,"(log_{}({}))",v,*equation.element2),
                         Expression::Constant(c) => { if c == ten { 
                                                         write!(f,"(log({}))",*equation.element2)
                                                     } else { 
-                                                        write!(f,"(log{}({}))",c,*equation.element2)
+                                                        write!(f,"(log_{}({}))",c,*equation.element2)
                                                     }
                         },
                         _ => panic!("something is up in the log section of display"),
                     }},
                 Operation::Deriv => write!(f,"{}'",*equation.element1),
-                 _ => panic!("No OP BUT EQUATION"),
+                Operation::Sum(n) => write!(f,"(sum({},{},({})))",n,*equation.element1,*equation.element2),
+                Operation::Integral(t) => write!(f,"(int({},{},({})))",t,*equation.element1,*equation.element2),
+                Operation::Bounds => write!(f,"{},{}",*equation.element1,*equation.element2),
+                Operation::Piecewise | Operation::Piece => {
+                    let pieces: Vec<String> = piecewise::pieces(self).iter()
+                        .map(|(condition, value)| format!("{} if {}", value, condition))
+                        .collect();
+                    write!(f,"{{{}}}",pieces.join("; "))
+                },
+                // a<=x<b is written out as a chain
+                Operation::Relation(Relation::And) => match &*equation.element2 {
+                    Expression::Equa(second) => if let Operation::Relation(relation) = second.operation {
+                        write!(f,"{}{}({})",*equation.element1,relation.symbol(),*second.element2)
+                    } else {
+                        panic!("the second half of a chained condition is a relation")
+                    },
+                    _ => panic!("the second half of a chained condition is a relation"),
+                },
+                Operation::Relation(relation) => write!(f,"({}){}({})",*equation.element1,relation.symbol(),*equation.element2),
+                Operation::Abs => write!(f,"(|{}|)",*equation.element2),
+                Operation::Floor => write!(f,"(floor({}))",*equation.element2),
+                Operation::Ceil => write!(f,"(ceil({}))",*equation.element2),
+                Operation::Root => match *equation.element1 {
+                    Expression::Constant(n) if n == Fraction::from(2) => write!(f,"(sqrt({}))",*equation.element2),
+                    Expression::Constant(n) if n == Fraction::from(3) => write!(f,"(cbrt({}))",*equation.element2),
+                    _ => write!(f,"(root({},{}))",*equation.element1,*equation.element2),
+                },
             }
         } else {
             match self {
@@ -404,6 +709,14 @@ impl fmt::Display for Letter {
     }
 }
 
+// n(n+1) parses as a function call, it is only one if n is a function in the bank
+fn is_function(bank: &Workspace, name: &Expression) -> bool {
+    match name {
+        Expression::Variable(v) => matches!(bank.get(v), Some(Letter::Function(_) | Letter::Accumulation(_))),
+        _ => true,
+    }
+}
+
 fn is_implied_mult(before: char, current: char) -> bool {
     let mut both_number = true;
     for character in [before,current] {
@@ -411,7 +724,7 @@ fn is_implied_mult(before: char, current: char) -> bool {
             '+' | '-' | '*' | '/' | '^' | '\'' => return false, 
             _ => ()
         }
-        both_number = both_number && character.is_numeric();
+        both_number = both_number && (character.is_numeric() || character == '.');
     }
     !both_number && before != '(' && current != ')'
 }
@@ -420,43 +733,71 @@ fn is_func(before: char, current: char) -> bool {
     (current == '(') && (before.is_alphabetic() | (before == '\'') )
 }
 
+// names which are followed by brackets but are not bank functions, longest first
+const SPECIAL_NAMES: [&str; 16] = ["arcsin", "arccos", "arctan", "floor", "sqrt", "cbrt", "root", "ceil",
+    "sin", "cos", "tan", "log", "sum", "int", "abs", "ln"];
+
 fn is_special(text: &str, start: usize) -> bool {
-    if text.len()-start >= 6 {
-        match &text[start..start+6] {
-            "arcsin" | "arctan" | "arccos" => return true,
-            _ => (),
-        };
-        match &text[start..start+3] {
-            "cos" | "tan" | "sin" | "log" => return true,
-            _ => (),
-        };
-    } else if text.len()-start >= 4 {
-        match &text[start..start+3] {
-            "cos" | "tan" | "sin" | "log" => return true,
-            _ => (),
-        };
-        match &text[start..start+2] {
-            "ln" => return true,
-            _ => (),
-        };
-    } else if text.len()-start >= 3 {
-        match &text[start..start+2] {
-            "ln" => return true,
-            _ => (),
-        };
+    SPECIAL_NAMES.iter().any(|name| text[start..].starts_with(name))
+}
+
+fn get_name(whole: &str, string: &str) -> Result<char, ParseError> {
+    let mut chars = string.chars();
+    match (chars.next(), chars.next()) {
+        (Some(name), None) if !"+-*/^(){},.;<>=|'".contains(name) => Ok(name),
+        _ => Err(at(whole, string, &format!("{} isn't a number or a letter", string))),
     }
-    return false;
 }
 
-fn get_name(string: &str) -> char {
-    //println!!("string: {}\nlen: {}", string, string.len());
-    assert_eq!(1, string.len());
-    let name = string.clone();
-    let name = name.chars().nth(0).unwrap();
-    name
+// an error at the start of string, a part of whole
+fn at(whole: &str, string: &str, message: &str) -> ParseError {
+    let start = (string.as_ptr() as usize).saturating_sub(whole.as_ptr() as usize).min(whole.len());
+    let column = whole.char_indices().take_while(|(i, _)| *i < start).count();
+    ParseError { column, message: String::from(message) }
+}
+
+// splits "(a,b,c)" into ["a","b","c"] ignoring commas inside brackets
+fn split_args(string: &str) -> Vec<&str> {
+    let inner = if is_wrapped(string) { &string[1..string.len()-1] } else { string };
+    let mut args = Vec::new();
+    let mut bracket_counter = 0;
+    let mut start = 0;
+    for (i, character) in inner.char_indices() {
+        match character {
+            '(' => bracket_counter += 1,
+            ')' => bracket_counter -= 1,
+            ',' if bracket_counter == 0 => {
+                args.push(&inner[start..i]);
+                start = i+1;
+            },
+            _ => (),
+        }
+    }
+    args.push(&inner[start..]);
+    args
 }
 
 fn power(base: Fraction, exponent: Fraction) -> Fraction {
+    // whole exponents stay exact as long as they fit
+    if exponent.denom() == Some(&1) {
+        let n = *exponent.numer().unwrap() as usize;
+        if let Some(answer) = num::checked_pow(base, n) {
+            return if exponent < Fraction::from(0) { Fraction::from(1) / answer } else { answer };
+        }
+    }
+    // odd roots of negatives are real, (-8)^(-2/3) = 1/4, and exact when the root is
+    if let (Some(&q), Some(_)) = (exponent.denom(), exponent.numer()) {
+        if let Some(root) = exact_root(base, q) {
+            if let Some(answer) = checked_power(root, exponent * Fraction::from(q)) {
+                return answer;
+            }
+        }
+        if base < Fraction::from(0) && q % 2 == 1 {
+            let magnitude = power(-base, exponent);
+            let odd = exponent.numer().is_some_and(|p| p % 2 == 1);
+            return if odd { -magnitude } else { magnitude };
+        }
+    }
     let base = base.to_f64().unwrap();
     let exponent = exponent.to_f64().unwrap();
 
@@ -464,6 +805,42 @@ fn power(base: Fraction, exponent: Fraction) -> Fraction {
     Fraction::from(answer)
 }
 
+// base^n for a whole n, None when it doesn't fit
+fn checked_power(base: Fraction, n: Fraction) -> Option<Fraction> {
+    let answer = num::checked_pow(base, *n.numer()? as usize)?;
+    Some(if n < Fraction::from(0) { Fraction::from(1) / answer } else { answer })
+}
+
+// the qth root of x when it is rational, odd roots of negatives included
+fn exact_root(x: Fraction, q: u64) -> Option<Fraction> {
+    if x < Fraction::from(0) && q.is_multiple_of(2) {
+        return None;
+    }
+    let integer_root = |n: u64| -> Option<u64> {
+        let guess = (n as f64).powf(1.0 / q as f64).round() as u64;
+        (guess.saturating_sub(1)..=guess + 1).find(|r| r.checked_pow(q as u32) == Some(n))
+    };
+    let root = Fraction::new(integer_root(*x.numer()?)?, integer_root(*x.denom()?)?);
+    Some(if x < Fraction::from(0) { -root } else { root })
+}
+
+// exact when it comes out rational, sqrt(9/4) = 3/2
+fn root(index: Fraction, radicand: Fraction) -> Fraction {
+    if let (Some(n), Some(1)) = (index.numer(), index.denom()) {
+        if index > Fraction::from(0) {
+            if let Some(answer) = exact_root(radicand, *n) {
+                return answer;
+            }
+            if radicand < Fraction::from(0) && n.is_multiple_of(2) {
+                return Fraction::nan();
+            }
+        }
+    }
+    let (index, radicand) = (index.to_f64().unwrap(), radicand.to_f64().unwrap());
+    let magnitude = radicand.abs().powf(1.0 / index);
+    Fraction::from(if radicand < 0.0 { -magnitude } else { magnitude })
+}
+
 fn solve_trig(name: &Expression, arg: Fraction) -> Fraction {
     let arg = arg.to_f64().unwrap();
 
@@ -472,9 +849,9 @@ fn solve_trig(name: &Expression, arg: Fraction) -> Fraction {
             's' => arg.sin(),
             'c' => arg.cos(),
             't' => arg.tan(),
-            'S' => arg.sin().asin(),
-            'C' => arg.cos().acos(),
-            'T' => arg.tan().atan(),
+            'S' => arg.asin(),
+            'C' => arg.acos(),
+            'T' => arg.atan(),
             _ => panic!("tried to find trig function {}",n),
         }
     } else {
@@ -483,7 +860,7 @@ fn solve_trig(name: &Expression, arg: Fraction) -> Fraction {
     Fraction::from(answer)
 }
 
-fn solve_log(base: &Expression, arg: Fraction, bank: &Bank, x: Fraction) -> Fraction {
+fn solve_log(base: &Expression, arg: Fraction, bank: &Workspace, x: Fraction) -> Fraction {
     let arg = arg.to_f64().unwrap();
 
     let answer = if let &Expression::Variable(e) = base {
@@ -499,6 +876,142 @@ fn solve_log(base: &Expression, arg: Fraction, bank: &Bank, x: Fraction) -> Frac
     Fraction::from(answer)
 }
 
+impl Relation {
+    fn symbol(&self) -> &str {
+        match self {
+            Relation::Less => "<",
+            Relation::LessEqual => "<=",
+            Relation::Greater => ">",
+            Relation::GreaterEqual => ">=",
+            Relation::Equal => "=",
+            Relation::And => "and",
+        }
+    }
+}
+
+// |x| to abs(x), a bar opens at the start, after an operator or bracket and after another
+// opening bar, otherwise it closes the innermost one
+fn bars(string: &str) -> Option<String> {
+    let mut out = String::new();
+    let mut open = 0;
+    let mut last = '(';
+    for character in string.chars() {
+        if character == '|' {
+            if open == 0 || "+-*/^(,;{".contains(last) || last == '\u{0}' {
+                out.push_str("abs(");
+                open += 1;
+                last = '\u{0}';
+                continue;
+            }
+            out.push(')');
+            open -= 1;
+        } else {
+            out.push(character);
+        }
+        last = character;
+    }
+    if open == 0 { Some(out) } else { None }
+}
+
+// whether the first { closes at the very end, {a}+{b} isn't one piecewise
+fn is_braced(s: &str) -> bool {
+    let mut depth = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            _ => (),
+        }
+        if depth == 0 {
+            return i == s.len() - 1;
+        }
+    }
+    false
+}
+
+// the inside of {x^2 if x<1; 2x-1 if x>=1} once the spaces are gone
+fn piecewise_from(whole: &str, string: &str) -> Result<Expression, ParseError> {
+    let mut pieces = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, character) in string.char_indices() {
+        match character {
+            '(' | '{' => depth += 1,
+            ')' | '}' => depth -= 1,
+            ';' if depth == 0 => {
+                pieces.push(&string[start..i]);
+                start = i+1;
+            },
+            _ => (),
+        }
+    }
+    pieces.push(&string[start..]);
+
+    let mut parsed: Vec<Expression> = pieces.iter().map(|piece| {
+        let Some((value, condition)) = piece.split_once("if") else {
+            return Err(at(whole, piece, "each piece needs a value, if and a condition"));
+        };
+        Ok(equa(Operation::Piece, relation_from(whole, condition)?, read(whole, value)?))
+    }).collect::<Result<_, _>>()?;
+
+    let mut rest = parsed.pop().unwrap();
+    while let Some(piece) = parsed.pop() {
+        rest = equa(Operation::Piecewise, piece, rest);
+    }
+    Ok(rest)
+}
+
+// x<1, 1<=x<3 and so on
+fn relation_from(whole: &str, string: &str) -> Result<Expression, ParseError> {
+    let mut depth = 0;
+    let mut found: Vec<(usize, usize, Relation)> = Vec::new();
+    let bytes = string.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'(' | b'{' => depth += 1,
+            b')' | b'}' => depth -= 1,
+            b'<' | b'>' | b'=' if depth == 0 => {
+                let equal = bytes.get(i+1) == Some(&b'=') && bytes[i] != b'=';
+                let relation = match (bytes[i], equal) {
+                    (b'<', false) => Relation::Less,
+                    (b'<', true) => Relation::LessEqual,
+                    (b'>', false) => Relation::Greater,
+                    (b'>', true) => Relation::GreaterEqual,
+                    _ => Relation::Equal,
+                };
+                let width = if equal { 2 } else { 1 };
+                found.push((i, width, relation));
+                i += width;
+                continue;
+            },
+            _ => (),
+        }
+        i += 1;
+    }
+
+    for &(i, width, _) in &found {
+        let sign = &string[i..i+width];
+        if i == 0 {
+            return Err(at(whole, sign, &format!("{} needs something before it", sign)));
+        }
+        if found.iter().any(|(j, _, _)| *j == i + width) || i + width == string.len() {
+            return Err(at(whole, sign, &format!("{} needs something after it", sign)));
+        }
+    }
+    Ok(match found.as_slice() {
+        [(i, width, relation)] =>
+            equa(Operation::Relation(*relation), read(whole, &string[..*i])?, read(whole, &string[i+width..])?),
+        [(i, width, first), (j, width2, second)] => {
+            let middle = &string[i+width..*j];
+            equa(Operation::Relation(Relation::And),
+                equa(Operation::Relation(*first), read(whole, &string[..*i])?, read(whole, middle)?),
+                equa(Operation::Relation(*second), read(whole, middle)?, read(whole, &string[j+width2..])?))
+        },
+        _ => return Err(at(whole, string, &format!("{} isn't a condition like x<1 or 0<=x<1", string))),
+    })
+}
+
 fn is_wrapped(s: &str) -> bool {
     let not_last = &s[..s.len()-1];
     //println!!("not last: {}",not_last);
@@ -517,8 +1030,132 @@ fn is_wrapped(s: &str) -> bool {
     true 
 }
 
+fn equa(operation: Operation, element1: Expression, element2: Expression) -> Expression {
+    Expression::Equa(Box::new(Equation {
+        operation,
+        element1: Box::new(element1),
+        element2: Box::new(element2),
+    }))
+}
+
+// how tightly a node holds together when fractions are drawn stacked, anything looser than
+// its spot gets brackets in the LaTeX, MathML and 2D layouts
+fn precedence(e: &Expression) -> u8 {
+    match e {
+        Expression::Constant(c) if *c < Fraction::from(0) => 1,
+        Expression::Constant(c) if c.denom().is_some_and(|d| *d != 1) => 3,
+        Expression::Constant(_) | Expression::Variable(_) => 4,
+        Expression::Equa(equation) => match equation.operation {
+            Operation::Piecewise | Operation::Piece | Operation::Relation(_) | Operation::Bounds => 0,
+            Operation::Add | Operation::Sub | Operation::Sum(_) | Operation::Integral(_) => 1,
+            Operation::Mult => 2,
+            Operation::Div | Operation::Exp => 3,
+            _ => 4,
+        },
+    }
+}
+
+// -3 and 0-u, which can lead a product without brackets
+fn negative(e: &Expression) -> bool {
+    match e {
+        Expression::Constant(c) => *c < Fraction::from(0),
+        Expression::Equa(equation) => equation.operation == Operation::Sub && *equation.element1 == Expression::Constant(Fraction::from(0)),
+        _ => false,
+    }
+}
+
+// whether var appears free in e, trig names and sum indexes don't count
+fn contains(e: &Expression, var: char) -> bool {
+    match e {
+        Expression::Variable(v) => *v == var,
+        Expression::Equa(equation) => match equation.operation {
+            Operation::Sum(n) | Operation::Integral(n) if n == var => contains(&equation.element1, var),
+            Operation::Trig => contains(&equation.element2, var),
+            _ => contains(&equation.element1, var) || contains(&equation.element2, var),
+        },
+        _ => false,
+    }
+}
+
+// whether approximate_with can put a number on e with vars bound
+fn known(bank: &Workspace, e: &Expression, vars: &[char]) -> bool {
+    match e {
+        Expression::Constant(_) => true,
+        Expression::Variable(v) => vars.contains(v) || *v == 'e' || *v == 'π'
+            || matches!(bank.get(v), Some(Letter::Variable(Value::Defined(_)))),
+        Expression::Equa(equation) => match equation.operation {
+            Operation::Trig => known(bank, &equation.element2, vars),
+            Operation::Func if is_function(bank, &equation.element1) => known(bank, &equation.element2, vars),
+            Operation::Integral(t) => known(bank, &equation.element1, vars)
+                && known(bank, &equation.element2, &[vars, &[t]].concat()),
+            Operation::Deriv | Operation::Sum(_) => false,
+            _ => known(bank, &equation.element1, vars) && known(bank, &equation.element2, vars),
+        },
+    }
+}
+
+/// e with every bank function call written out in terms of its argument, f'(u) included,
+/// and roots as powers, so the symbolic code only has to deal with plain expressions
+pub fn expand(bank: &Workspace, e: &Expression) -> Expression {
+    match e {
+        Expression::Equa(equation) => {
+            let (a, b) = (&*equation.element1, &*equation.element2);
+            match (equation.operation, a) {
+                (Operation::Func, Expression::Variable(_)) if is_function(bank, a) =>
+                    substitute(&expand(bank, &get_expression(bank, a)), 'x', &expand(bank, b)),
+                (Operation::Func, Expression::Equa(inner)) if inner.operation == Operation::Deriv => {
+                    let derivative = simplify::simplify(&derivative::ddx(&expand(bank, &Expression::from(&format!("{}(x)", inner.element1)))));
+                    substitute(&derivative, 'x', &expand(bank, b))
+                },
+                (Operation::Func, _) if !is_function(bank, a) => equa(Operation::Mult, expand(bank, a), expand(bank, b)),
+                (Operation::Func | Operation::Trig | Operation::Log | Operation::Deriv, _) =>
+                    equa(equation.operation, copy_expression(a), expand(bank, b)),
+                // roots are powers as far as the symbolic code is concerned
+                (Operation::Root, _) =>
+                    equa(Operation::Exp, expand(bank, b), equa(Operation::Div, Expression::from("1"), expand(bank, a))),
+                _ => equa(equation.operation, expand(bank, a), expand(bank, b)),
+            }
+        },
+        _ => copy_expression(e),
+    }
+}
+
 pub fn copy_expression(e: &Expression) -> Expression {
-    let e_str = format!("{}",e);
-    Expression::from(&e_str)
+    // copied node by node, going through a string would turn the constant 1/2 into a division
+    match e {
+        Expression::Constant(c) => Expression::Constant(*c),
+        Expression::Variable(v) => Expression::Variable(*v),
+        Expression::Equa(equation) =>
+            equa(equation.operation, copy_expression(&equation.element1), copy_expression(&equation.element2)),
+    }
 }
 
+/// Replaces every free `var` in `e` with `value`, used for evaluating in letters other than x
+pub fn substitute(e: &Expression, var: char, value: &Expression) -> Expression {
+    match e {
+        Expression::Variable(v) if *v == var => copy_expression(value),
+        Expression::Equa(equation) => {
+            // n(n+1) with n being replaced was a product all along
+            let operation = match (equation.operation, &*equation.element1) {
+                (Operation::Func, Expression::Variable(v)) if *v == var => Operation::Mult,
+                (operation, _) => operation,
+            };
+            // names of functions and derivatives are left alone, a sum over var binds it
+            let element1 = match operation {
+                Operation::Func | Operation::Trig | Operation::Deriv => copy_expression(&equation.element1),
+                _ => substitute(&equation.element1, var, value),
+            };
+            let element2 = match equation.operation {
+                Operation::Sum(n) | Operation::Integral(n) if n == var => copy_expression(&equation.element2),
+                Operation::Deriv => copy_expression(&equation.element2),
+                _ => substitute(&equation.element2, var, value),
+            };
+            Expression::Equa(Box::new(Equation {
+                operation,
+                element1: Box::new(element1),
+                element2: Box::new(element2),
+            }))
+        },
+        _ => copy_expression(e),
+    }
+}
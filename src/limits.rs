@@ -0,0 +1,66 @@
+use super::*;
+
+/// Which way x comes in from
+#[derive(PartialEq)]
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+pub enum Side {
+    Left,
+    Right,
+    Both,
+}
+
+// how far off a neighbour can be and still count as continuous
+const NEARBY: f64 = 1e-7;
+
+/// lim x->a of e, a can be ±inf. Gives ±inf for a blow up and NaN when there is no limit
+pub fn limit(bank: &Workspace, e: &Expression, a: f64, side: Side) -> f64 {
+    limit_in(bank, e, 'x', a, side)
+}
+
+/// limit with respect to var
+pub fn limit_in(bank: &Workspace, e: &Expression, var: char, a: f64, side: Side) -> f64 {
+    let e = expand(bank, e);
+    limit_of(|x| e.approximate_with(bank, &[(var, x)]), a, side)
+}
+
+/// The numeric engine under limit, f is sampled closer and closer to a and the samples are
+/// extrapolated with numeric::sequence_limit
+pub fn limit_of<F: Fn(f64) -> f64>(f: F, a: f64, side: Side) -> f64 {
+    if a.is_infinite() {
+        return numeric::sequence_limit(|n| f(a.signum() * n));
+    }
+    match side {
+        Side::Left => one_sided(&f, a, -1.0),
+        Side::Right => one_sided(&f, a, 1.0),
+        Side::Both => {
+            let (left, right) = (one_sided(&f, a, -1.0), one_sided(&f, a, 1.0));
+            if left == right || (left - right).abs() < 1e-6 * left.abs().max(1.0) { left } else { f64::NAN }
+        },
+    }
+}
+
+fn one_sided<F: Fn(f64) -> f64>(f: &F, a: f64, direction: f64) -> f64 {
+    // f defined and matching its neighbour means there's nothing to extrapolate
+    let (at, near) = (f(a), f(a + direction * NEARBY));
+    if at.is_finite() && (near - at).abs() < 1e-5 * at.abs().max(1.0) {
+        return at;
+    }
+    numeric::sequence_limit(|n| f(a + direction / n))
+}
+
+/// x as an exact Expression when it is within tolerance of a simple fraction or a fraction of π,
+/// whichever has the smaller denominator so π doesn't come out as 355/113
+pub fn exact_value(x: f64, tolerance: f64) -> Option<Expression> {
+    let rational = numeric::nice_fraction(x, tolerance);
+    let k = numeric::nice_fraction(x / std::f64::consts::PI, tolerance)
+        .filter(|k| k.denom() <= Some(&12) && *k != Fraction::from(0));
+
+    match (rational, k) {
+        (Some(rational), Some(k)) if k.denom() < rational.denom() =>
+            Some(simplify::simplify(&Expression::from(&format!("({})(π)", k)))),
+        (Some(rational), _) => Some(Expression::Constant(rational)),
+        (None, Some(k)) => Some(simplify::simplify(&Expression::from(&format!("({})(π)", k)))),
+        (None, None) => None,
+    }
+}
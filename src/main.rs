@@ -0,0 +1,132 @@
+use ap_calc::server::Server;
+use ap_calc::session::{self, Session};
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::process;
+
+const PROMPT: &str = "> ";
+
+const USAGE: &str = "\
+usage: ap_calc                                  start the calculator
+       ap_calc run FILE [--format text|json]    run a script, exits 1 when a line fails
+       ap_calc serve --stdio                    answer JSON-RPC 2.0, a request a line";
+
+fn main() {
+    session::quiet_panics();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let code = match args.as_slice() {
+        [] => { repl(); 0 },
+        ["run", file] => run(file, "text"),
+        ["run", file, "--format", format] | ["run", "--format", format, file] if ["text", "json"].contains(format) => run(file, format),
+        ["serve", "--stdio"] => { serve(); 0 },
+        _ => { eprintln!("{}", USAGE); 2 },
+    };
+    process::exit(code);
+}
+
+// 0 when every line worked, 1 when one didn't and 2 when the file couldn't be read
+fn run(file: &str, format: &str) -> i32 {
+    let text = match fs::read_to_string(file) {
+        Ok(text) => text,
+        Err(error) => { eprintln!("can't read {}: {}", file, error); return 2 },
+    };
+    let steps = Session::new().script(&text);
+    if format == "json" {
+        let steps: Vec<String> = steps.iter().map(|step| format!("  {}", step.json())).collect();
+        // a step a line so two runs diff nicely
+        if steps.is_empty() { println!("[]") } else { println!("[\n{}\n]", steps.join(",\n")) }
+    } else {
+        for step in &steps {
+            println!("{}", step.text());
+        }
+    }
+    if steps.iter().any(|step| step.result.is_err()) { 1 } else { 0 }
+}
+
+fn serve() {
+    let mut server = Server::new();
+    let mut stdout = io::stdout();
+    for line in io::stdin().lock().lines() {
+        let Ok(line) = line else { break };
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = server.handle(&line) {
+            writeln!(stdout, "{}", response).ok();
+            stdout.flush().ok();
+        }
+    }
+}
+
+// AP_CALC_HISTORY when it is set, ~/.ap_calc_history otherwise
+fn history_file() -> Option<PathBuf> {
+    match std::env::var_os("AP_CALC_HISTORY") {
+        Some(path) => Some(PathBuf::from(path)),
+        None => std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".ap_calc_history")),
+    }
+}
+
+fn repl() {
+    let path = history_file();
+    let mut history: Vec<String> = path.as_ref()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map_or(Vec::new(), |text| text.lines().map(String::from).collect());
+    let mut session = Session::new();
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    loop {
+        print!("{}", PROMPT);
+        io::stdout().flush().ok();
+        let Some(Ok(typed)) = lines.next() else { break };
+        let mut line = typed.trim_end().to_string();
+
+        // !! is the last line again and !n is line n of history
+        if let Some(recall) = line.strip_prefix('!') {
+            let found = match recall {
+                "!" => history.last(),
+                n => n.parse::<usize>().ok().and_then(|n| n.checked_sub(1)).and_then(|n| history.get(n)),
+            };
+            match found {
+                Some(found) => {
+                    line = found.clone();
+                    println!("{}{}", PROMPT, line);
+                },
+                None => {
+                    eprintln!("{}^ nothing in history there", " ".repeat(PROMPT.len() + 1));
+                    continue;
+                },
+            }
+        }
+        match line.trim() {
+            "" => continue,
+            "quit" | "exit" => break,
+            "history" => {
+                for (i, old) in history.iter().enumerate() {
+                    println!("{:>4}  {}", i + 1, old);
+                }
+                continue;
+            },
+            _ => {},
+        }
+        remember(&mut history, path.as_ref(), &line);
+
+        match session.run(&line) {
+            Ok(output) if output.is_empty() => {},
+            Ok(output) => println!("{}", output),
+            Err(error) => eprintln!("{}", error.pointer(PROMPT.len())),
+        }
+    }
+    println!();
+}
+
+fn remember(history: &mut Vec<String>, path: Option<&PathBuf>, line: &str) {
+    history.push(line.to_string());
+    if let Some(path) = path {
+        if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path) {
+            writeln!(file, "{}", line).ok();
+        }
+    }
+}
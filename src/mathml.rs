@@ -0,0 +1,253 @@
+use super::*;
+
+const NAMESPACE: &str = "<math xmlns=\"http://www.w3.org/1998/Math/MathML\">";
+
+impl Expression {
+    /// Presentation MathML, what the expression looks like, for screen readers and the web
+    pub fn to_mathml(&self) -> String {
+        format!("{}{}</math>", NAMESPACE, presentation(self))
+    }
+
+    /// Content MathML, what the expression means, built from apply with plus, times, sin,
+    /// ln, diff and so on
+    pub fn to_content_mathml(&self) -> String {
+        format!("{}{}</math>", NAMESPACE, content(self))
+    }
+}
+
+fn row(parts: &[String]) -> String {
+    format!("<mrow>{}</mrow>", parts.concat())
+}
+
+fn mo(symbol: &str) -> String {
+    format!("<mo>{}</mo>", symbol)
+}
+
+fn fenced(inside: String, open: &str, close: &str) -> String {
+    row(&[mo(open), inside, mo(close)])
+}
+
+fn operand(e: &Expression, needs: u8) -> String {
+    if precedence(e) < needs { fenced(presentation(e), "(", ")") } else { presentation(e) }
+}
+
+fn number(c: &Fraction) -> String {
+    if c.is_nan() {
+        return "<mtext>undefined</mtext>".to_string();
+    }
+    let magnitude = if c.is_infinite() {
+        "<mi>∞</mi>".to_string()
+    } else {
+        match (c.numer(), c.denom()) {
+            (Some(n), Some(1)) => format!("<mn>{}</mn>", n),
+            (Some(n), Some(d)) => format!("<mfrac><mn>{}</mn><mn>{}</mn></mfrac>", n, d),
+            _ => "<mtext>undefined</mtext>".to_string(),
+        }
+    };
+    if *c < Fraction::from(0) { row(&[mo("-"), magnitude]) } else { magnitude }
+}
+
+fn trig_name(name: &Expression) -> &str {
+    match name {
+        Expression::Variable('s') => "sin",
+        Expression::Variable('c') => "cos",
+        Expression::Variable('t') => "tan",
+        Expression::Variable('S') => "arcsin",
+        Expression::Variable('C') => "arccos",
+        Expression::Variable('T') => "arctan",
+        _ => panic!("{} isn't a trig function", name),
+    }
+}
+
+fn relation_symbol(relation: Relation) -> &'static str {
+    match relation {
+        Relation::Less => "&lt;",
+        Relation::LessEqual => "≤",
+        Relation::Greater => "&gt;",
+        Relation::GreaterEqual => "≥",
+        Relation::Equal => "=",
+        Relation::And => panic!("a chain is two comparisons"),
+    }
+}
+
+// f(x), the invisible function application keeps screen readers from saying f times x
+fn applied(name: String, argument: &Expression) -> String {
+    row(&[name, mo("&#x2061;"), fenced(presentation(argument), "(", ")")])
+}
+
+// f, f′ or f″ with the primes counted off the nested Deriv nodes
+fn primed(e: &Expression) -> (String, usize) {
+    match e {
+        Expression::Equa(equation) if equation.operation == Operation::Deriv => {
+            let (name, primes) = primed(&equation.element1);
+            (name, primes + 1)
+        },
+        _ => (presentation(e), 0),
+    }
+}
+
+fn presentation(e: &Expression) -> String {
+    let equation = match e {
+        Expression::Constant(c) => return number(c),
+        Expression::Variable(v) => return format!("<mi>{}</mi>", v),
+        Expression::Equa(equation) => equation,
+    };
+    let (a, b) = (&*equation.element1, &*equation.element2);
+    match equation.operation {
+        Operation::Add if negative(b) => row(&[operand(a, 1), mo("+"), fenced(presentation(b), "(", ")")]),
+        Operation::Add => row(&[operand(a, 1), mo("+"), operand(b, 1)]),
+        Operation::Sub if *a == Expression::Constant(Fraction::from(0)) => row(&[mo("-"), operand(b, 2)]),
+        Operation::Sub => row(&[operand(a, 1), mo("-"), operand(b, 2)]),
+        Operation::Mult => {
+            let left = if negative(a) { presentation(a) } else { operand(a, 2) };
+            // 2·3 needs a visible dot, 3x an invisible one
+            let numbers = matches!(b, Expression::Constant(_));
+            row(&[left, mo(if numbers { "·" } else { "&#x2062;" }), operand(b, 2)])
+        },
+        Operation::Div => format!("<mfrac>{}{}</mfrac>", presentation(a), presentation(b)),
+        Operation::Exp => format!("<msup>{}{}</msup>", operand(a, 4), presentation(b)),
+        Operation::Func => applied(presentation(a), b),
+        Operation::Trig => applied(format!("<mi>{}</mi>", trig_name(a)), b),
+        Operation::Log => match a {
+            Expression::Variable('e') => applied("<mi>ln</mi>".to_string(), b),
+            Expression::Constant(c) if *c == Fraction::from(10) => applied("<mi>log</mi>".to_string(), b),
+            _ => applied(format!("<msub><mi>log</mi>{}</msub>", presentation(a)), b),
+        },
+        Operation::Deriv => {
+            let (name, primes) = primed(e);
+            let marks = match primes {
+                1 => "′".to_string(),
+                2 => "″".to_string(),
+                3 => "‴".to_string(),
+                n => "′".repeat(n),
+            };
+            format!("<msup>{}{}</msup>", name, mo(&marks))
+        },
+        Operation::Sum(n) | Operation::Integral(n) => {
+            let (lower, upper) = match a {
+                Expression::Equa(bounds) => (presentation(&bounds.element1), presentation(&bounds.element2)),
+                _ => panic!("a sum or integral's first element is its bounds"),
+            };
+            if let Operation::Sum(_) = equation.operation {
+                let start = row(&[format!("<mi>{}</mi>", n), mo("="), lower]);
+                row(&[format!("<munderover>{}{}{}</munderover>", mo("∑"), start, upper), operand(b, 2)])
+            } else {
+                let differential = row(&["<mi>d</mi>".to_string(), format!("<mi>{}</mi>", n)]);
+                row(&[format!("<msubsup>{}{}{}</msubsup>", mo("∫"), lower, upper), presentation(b), mo("&#x2062;"), differential])
+            }
+        },
+        Operation::Bounds => row(&[presentation(a), mo(","), presentation(b)]),
+        Operation::Piecewise | Operation::Piece => {
+            let rows: Vec<String> = piecewise::pieces(e).iter()
+                .map(|(condition, value)| format!("<mtr><mtd>{}</mtd><mtd><mtext>if </mtext>{}</mtd></mtr>", presentation(value), presentation(condition)))
+                .collect();
+            row(&[mo("{"), format!("<mtable>{}</mtable>", rows.concat())])
+        },
+        Operation::Relation(Relation::And) => match b {
+            Expression::Equa(second) => match second.operation {
+                Operation::Relation(r) => row(&[presentation(a), mo(relation_symbol(r)), presentation(&second.element2)]),
+                _ => panic!("the second half of a chained condition is a relation"),
+            },
+            _ => panic!("the second half of a chained condition is a relation"),
+        },
+        Operation::Relation(r) => row(&[presentation(a), mo(relation_symbol(r)), presentation(b)]),
+        Operation::Abs => fenced(presentation(b), "|", "|"),
+        Operation::Floor => fenced(presentation(b), "⌊", "⌋"),
+        Operation::Ceil => fenced(presentation(b), "⌈", "⌉"),
+        Operation::Root => match a {
+            Expression::Constant(n) if *n == Fraction::from(2) => format!("<msqrt>{}</msqrt>", presentation(b)),
+            _ => format!("<mroot>{}{}</mroot>", presentation(b), presentation(a)),
+        },
+    }
+}
+
+fn apply(parts: &[String]) -> String {
+    format!("<apply>{}</apply>", parts.concat())
+}
+
+fn element(name: &str) -> String {
+    format!("<{}/>", name)
+}
+
+fn content_number(c: &Fraction) -> String {
+    if c.is_nan() {
+        return element("notanumber");
+    }
+    if c.is_infinite() {
+        return if *c < Fraction::from(0) { apply(&[element("minus"), element("infinity")]) } else { element("infinity") };
+    }
+    let sign = if *c < Fraction::from(0) { "-" } else { "" };
+    match (c.numer(), c.denom()) {
+        (Some(n), Some(1)) => format!("<cn type=\"integer\">{}{}</cn>", sign, n),
+        (Some(n), Some(d)) => format!("<cn type=\"rational\">{}{}<sep/>{}</cn>", sign, n, d),
+        _ => element("notanumber"),
+    }
+}
+
+// the function a name stands for, f′ is diff applied to f
+fn function(e: &Expression) -> String {
+    match e {
+        Expression::Variable(v) => format!("<ci type=\"function\">{}</ci>", v),
+        _ => content(e),
+    }
+}
+
+fn content(e: &Expression) -> String {
+    let equation = match e {
+        Expression::Constant(c) => return content_number(c),
+        Expression::Variable('π') => return element("pi"),
+        Expression::Variable('e') => return element("exponentiale"),
+        Expression::Variable(v) => return format!("<ci>{}</ci>", v),
+        Expression::Equa(equation) => equation,
+    };
+    let (a, b) = (&*equation.element1, &*equation.element2);
+    let binary = |name: &str| apply(&[element(name), content(a), content(b)]);
+    let unary = |name: &str| apply(&[element(name), content(b)]);
+    match equation.operation {
+        Operation::Add => binary("plus"),
+        Operation::Sub if *a == Expression::Constant(Fraction::from(0)) => unary("minus"),
+        Operation::Sub => binary("minus"),
+        Operation::Mult => binary("times"),
+        Operation::Div => binary("divide"),
+        Operation::Exp => binary("power"),
+        Operation::Func => apply(&[function(a), content(b)]),
+        Operation::Trig => unary(trig_name(a)),
+        Operation::Log => match a {
+            Expression::Variable('e') => unary("ln"),
+            Expression::Constant(c) if *c == Fraction::from(10) => unary("log"),
+            _ => apply(&[element("log"), format!("<logbase>{}</logbase>", content(a)), content(b)]),
+        },
+        Operation::Deriv => apply(&[element("diff"), function(a)]),
+        Operation::Sum(n) | Operation::Integral(n) => {
+            let (lower, upper) = match a {
+                Expression::Equa(bounds) => (content(&bounds.element1), content(&bounds.element2)),
+                _ => panic!("a sum or integral's first element is its bounds"),
+            };
+            let name = if let Operation::Sum(_) = equation.operation { "sum" } else { "int" };
+            apply(&[element(name), format!("<bvar><ci>{}</ci></bvar>", n),
+                format!("<lowlimit>{}</lowlimit>", lower), format!("<uplimit>{}</uplimit>", upper), content(b)])
+        },
+        Operation::Bounds => format!("<interval closure=\"closed\">{}{}</interval>", content(a), content(b)),
+        Operation::Piecewise | Operation::Piece => {
+            let pieces: Vec<String> = piecewise::pieces(e).iter()
+                .map(|(condition, value)| format!("<piece>{}{}</piece>", content(value), content(condition)))
+                .collect();
+            format!("<piecewise>{}</piecewise>", pieces.concat())
+        },
+        Operation::Relation(relation) => binary(match relation {
+            Relation::Less => "lt",
+            Relation::LessEqual => "leq",
+            Relation::Greater => "gt",
+            Relation::GreaterEqual => "geq",
+            Relation::Equal => "eq",
+            Relation::And => "and",
+        }),
+        Operation::Abs => unary("abs"),
+        Operation::Floor => unary("floor"),
+        Operation::Ceil => unary("ceiling"),
+        Operation::Root => match a {
+            Expression::Constant(n) if *n == Fraction::from(2) => unary("root"),
+            _ => apply(&[element("root"), format!("<degree>{}</degree>", content(a)), content(b)]),
+        },
+    }
+}
@@ -0,0 +1,142 @@
+use super::*;
+use applications::Bound;
+use derivative::derivative;
+use integral::{Definite, Method};
+use simplify::simplify;
+
+/// A particle moving along a line, everything is a function of time written as x like the
+/// rest of the bank
+#[derive(Debug)]
+pub struct Motion {
+    pub position: Expression,
+    pub velocity: Expression,
+    pub acceleration: Expression,
+    // (t0, s(t0)) when the position is the velocity integrated from t0
+    start: Option<(Expression, Expression)>,
+}
+
+/// What the speed is doing on an interval of time
+#[derive(PartialEq)]
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+pub enum Speed {
+    SpeedingUp,
+    SlowingDown,
+}
+
+/// A stretch of time from start to end
+#[derive(Debug)]
+pub struct Interval {
+    pub start: Bound,
+    pub end: Bound,
+    pub speed: Speed,
+}
+
+impl Motion {
+    /// The motion of a particle whose position is bank function s
+    pub fn from_position(bank: &Workspace, s: char) -> Motion {
+        let position = expand(bank, &Expression::from(&format!("{}(x)", s)));
+        let velocity = derivative(bank, &position);
+        let acceleration = derivative(bank, &velocity);
+        Motion { position, velocity, acceleration, start: None }
+    }
+
+    /// The motion of a particle whose velocity is bank function v and that is at s0 when t = t0,
+    /// the position is s0 + ∫ v from t0 to x
+    pub fn from_velocity(bank: &Workspace, v: char, t0: &Expression, s0: &Expression) -> Motion {
+        let velocity = simplify(&expand(bank, &Expression::from(&format!("{}(x)", v))));
+        let acceleration = derivative(bank, &velocity);
+        let position = Expression::from(&format!("({})+(int(t,{},x,({})))", s0, t0, substitute(&velocity, 'x', &Expression::Variable('t'))));
+        Motion { position, velocity, acceleration, start: Some((copy_expression(t0), copy_expression(s0))) }
+    }
+
+    /// Where the particle is at time t
+    pub fn position_at(&self, bank: &Workspace, t: &Expression) -> Definite {
+        match &self.start {
+            Some((t0, s0)) => {
+                let moved = integral::integrate(bank, &self.velocity, t0, t);
+                Definite {
+                    value: s0.approximate_with(bank, &[]) + moved.value,
+                    exact: moved.exact.map(|exact| simplify(&Expression::from(&format!("({})+({})", s0, exact)))),
+                    method: moved.method,
+                }
+            },
+            None => {
+                let exact = simplify(&substitute(&self.position, 'x', t));
+                Definite { value: exact.approximate_with(bank, &[]), exact: Some(exact), method: Method::FundamentalTheorem }
+            },
+        }
+    }
+
+    /// The times in [a, b] where the velocity changes sign. A velocity that only touches 0
+    /// doesn't turn the particle around
+    pub fn direction_changes(&self, bank: &Workspace, a: f64, b: f64) -> Vec<Bound> {
+        let v = |t: f64| self.velocity.approximate(bank, t);
+        numeric::roots(v, a, b).into_iter()
+            .filter(|t| *t > a && *t < b && v(t - 1e-6) * v(t + 1e-6) < 0.0)
+            .map(applications::bound)
+            .collect()
+    }
+
+    /// [a, b] split where v or a is 0, speeding up where they have the same sign and slowing
+    /// down where they don't. Neighbouring pieces doing the same thing are joined
+    pub fn speed(&self, bank: &Workspace, a: f64, b: f64) -> Vec<Interval> {
+        let mut cuts: Vec<f64> = numeric::roots(|t| self.velocity.approximate(bank, t), a, b);
+        cuts.extend(numeric::roots(|t| self.acceleration.approximate(bank, t), a, b));
+        cuts.retain(|t| *t > a + 1e-9 && *t < b - 1e-9);
+        cuts.sort_by(|p, q| p.total_cmp(q));
+        cuts.dedup_by(|p, q| (*p - *q).abs() < 1e-9);
+        cuts.insert(0, a);
+        cuts.push(b);
+
+        let mut intervals: Vec<Interval> = Vec::new();
+        for window in cuts.windows(2) {
+            let middle = (window[0] + window[1]) / 2.0;
+            let product = self.velocity.approximate(bank, middle) * self.acceleration.approximate(bank, middle);
+            let speed = if product > 0.0 { Speed::SpeedingUp } else { Speed::SlowingDown };
+            match intervals.last_mut() {
+                Some(last) if last.speed == speed => last.end = applications::bound(window[1]),
+                _ => intervals.push(Interval { start: applications::bound(window[0]), end: applications::bound(window[1]), speed }),
+            }
+        }
+        intervals
+    }
+
+    /// Whether the particle is speeding up at time t, v and a have the same sign
+    pub fn speeding_up_at(&self, bank: &Workspace, t: f64) -> bool {
+        self.velocity.approximate(bank, t) * self.acceleration.approximate(bank, t) > 0.0
+    }
+
+    /// The change in position from a to b, ∫ v
+    pub fn displacement(&self, bank: &Workspace, a: &Expression, b: &Expression) -> Definite {
+        integral::integrate(bank, &self.velocity, a, b)
+    }
+
+    /// The distance travelled from a to b, ∫ |v| worked out as the sum of |∫ v| between
+    /// direction changes
+    pub fn total_distance(&self, bank: &Workspace, a: &Expression, b: &Expression) -> Definite {
+        let (low, high) = (a.approximate_with(bank, &[]), b.approximate_with(bank, &[]));
+        let mut points = vec![Bound { value: low, exact: Some(copy_expression(a)) }];
+        points.extend(self.direction_changes(bank, low, high));
+        points.push(Bound { value: high, exact: Some(copy_expression(b)) });
+
+        let pieces: Vec<Definite> = points.windows(2).map(|window| match (&window[0].exact, &window[1].exact) {
+            (Some(start), Some(end)) => integral::integrate(bank, &self.velocity, start, end),
+            _ => Definite {
+                value: numeric::simpson(|t| self.velocity.approximate(bank, t), window[0].value, window[1].value, 1000),
+                exact: None,
+                method: Method::Numeric,
+            },
+        }).collect();
+
+        let exact = pieces.iter()
+            .map(|piece| piece.exact.as_ref().map(|e| format!("{}({})", if piece.value < 0.0 { "-" } else { "+" }, e)))
+            .collect::<Option<Vec<String>>>()
+            .map(|terms| simplify(&Expression::from(&format!("0{}", terms.concat()))));
+        Definite {
+            value: pieces.iter().map(|piece| piece.value.abs()).sum(),
+            method: if exact.is_some() { Method::FundamentalTheorem } else { Method::Numeric },
+            exact,
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use super::*;
+
+/// Composite Simpson's rule with n (made even) panels
+pub fn simpson<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, n: usize) -> f64 {
+    let n = if n % 2 == 1 { n + 1 } else { n.max(2) };
+    let h = (b - a) / n as f64;
+
+    let mut total = f(a) + f(b);
+    for i in 1..n {
+        let weight = if i % 2 == 1 { 4.0 } else { 2.0 };
+        total += weight * f(a + h * i as f64);
+    }
+    total * h / 3.0
+}
+
+/// Estimates lim n->inf of a sequence by sampling at n = 16, 32, ... and using Aitken's
+/// delta squared on the last three finite samples, which is exact for errors like c/n^p.
+/// Gives ±inf when the samples run off and NaN when they settle on nothing
+pub fn sequence_limit<F: Fn(f64) -> f64>(f: F) -> f64 {
+    let samples: Vec<f64> = (4..=12)
+        .map(|k| f(2f64.powi(k)))
+        .collect();
+    let finite: Vec<f64> = samples.iter().copied().take_while(|s| s.is_finite()).collect();
+
+    if finite.len() < samples.len() {
+        // ran into infinity part way, trust the direction it was heading
+        if let Some(s) = samples.iter().find(|s| s.is_infinite()) {
+            return *s;
+        }
+    }
+    if finite.len() < 3 {
+        return f64::NAN;
+    }
+
+    let (f1, f2, f3) = (finite[finite.len()-3], finite[finite.len()-2], finite[finite.len()-1]);
+    let d1 = f2 - f1;
+    let d2 = f3 - f2;
+
+    if d2.abs() < 1e-12 * f3.abs().max(1.0) {
+        return f3;
+    }
+    if f3.abs() > 1e12 && f3.abs() > f2.abs() {
+        return f3.signum() * f64::INFINITY;
+    }
+    // steps that aren't shrinking mean growth like ln(n) or oscillation
+    if d2.abs() >= 0.9 * d1.abs() {
+        return if d1.signum() == d2.signum() { d2.signum() * f64::INFINITY } else { f64::NAN };
+    }
+
+    // repeating aitken cleans up errors like c/(n+1) that aren't a pure power
+    let mut accelerated = finite;
+    while accelerated.len() >= 3 {
+        let next: Vec<f64> = accelerated.windows(3)
+            .map(|w| {
+                let (d1, d2) = (w[1] - w[0], w[2] - w[1]);
+                if d2 == d1 { w[2] } else { w[2] - d2 * d2 / (d2 - d1) }
+            })
+            .collect();
+        if next.iter().any(|v| !v.is_finite()) {
+            break;
+        }
+        accelerated = next;
+    }
+    accelerated[accelerated.len()-1]
+}
+
+/// The simplest fraction within tolerance (relative) of x if one has a denominator under
+/// 1000, so numeric answers like 0.3333333 can be shown as 1/3
+pub fn nice_fraction(x: f64, tolerance: f64) -> Option<Fraction> {
+    if !x.is_finite() {
+        return None;
+    }
+    for denom in 1..1000u64 {
+        let numer = (x.abs() * denom as f64).round();
+        if (numer / denom as f64 - x.abs()).abs() <= tolerance * x.abs().max(1.0) {
+            let frac = Fraction::new(numer as u64, denom);
+            return Some(if x < 0.0 { -frac } else { frac });
+        }
+    }
+    None
+}
+
+// pieces the root finder's grid splits [a, b] into
+const GRID: usize = 1000;
+
+/// Roots of f on [a, b]: sign changes on a grid narrowed down by bisection, plus the places
+/// |f| dips to 0 without crossing like x^2. Sign changes across a blow up like 1/x are left out
+pub fn roots<F: Fn(f64) -> f64>(f: F, a: f64, b: f64) -> Vec<f64> {
+    let h = (b - a) / GRID as f64;
+    let points: Vec<(f64, f64)> = (0..=GRID).map(|i| { let x = a + h * i as f64; (x, f(x)) }).collect();
+    let mut found: Vec<f64> = Vec::new();
+
+    for (i, window) in points.windows(2).enumerate() {
+        let ((x1, y1), (x2, y2)) = (window[0], window[1]);
+        if !y1.is_finite() || !y2.is_finite() {
+            continue;
+        }
+        let root = if y1 == 0.0 {
+            Some(x1)
+        } else if y1.signum() != y2.signum() && y2 != 0.0 {
+            let x = bisect(&f, x1, x2);
+            // a real root has a small value, a pole a huge one
+            if f(x).abs() < 1e-6 * y1.abs().max(y2.abs()).max(1.0) { Some(x) } else { None }
+        } else if i > 0 && points[i-1].1.is_finite() && y1.abs() < points[i-1].1.abs() && y1.abs() <= y2.abs() {
+            let x = golden_minimum(|x| f(x).abs(), points[i-1].0, x2);
+            if f(x).abs() < 1e-9 { Some(x) } else { None }
+        } else {
+            None
+        };
+        if let Some(x) = root {
+            if found.last().is_none_or(|last| (x - last).abs() > 1e-7) {
+                found.push(x);
+            }
+        }
+    }
+    if points[GRID].1 == 0.0 && found.last().is_none_or(|last| (b - last).abs() > 1e-7) {
+        found.push(b);
+    }
+    found
+}
+
+fn bisect<F: Fn(f64) -> f64>(f: &F, mut low: f64, mut high: f64) -> f64 {
+    let sign = f(low).signum();
+    for _ in 0..100 {
+        let middle = (low + high) / 2.0;
+        let value = f(middle);
+        if value == 0.0 {
+            return middle;
+        }
+        if value.signum() == sign { low = middle } else { high = middle }
+    }
+    (low + high) / 2.0
+}
+
+/// Where f is smallest on [low, high] for f with a single dip there
+pub fn golden_minimum<F: Fn(f64) -> f64>(f: F, mut low: f64, mut high: f64) -> f64 {
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    for _ in 0..200 {
+        let (c, d) = (high - ratio * (high - low), low + ratio * (high - low));
+        if f(c) < f(d) { high = d } else { low = c }
+    }
+    (low + high) / 2.0
+}
@@ -0,0 +1,232 @@
+use super::*;
+use applications::Bound;
+use integral::{Definite, Method};
+use limits::Side;
+use simplify::simplify;
+
+/// What a piecewise function does where it switches pieces. left and right are the one
+/// sided limits and the slopes are the one sided limits of the derivative's pieces
+#[derive(Debug)]
+pub struct Breakpoint {
+    pub x: Bound,
+    pub value: f64,
+    pub left: f64,
+    pub right: f64,
+    pub left_slope: f64,
+    pub right_slope: f64,
+    pub continuous: bool,
+    pub differentiable: bool,
+}
+
+/// The (condition, value) of each piece of a Piecewise or Piece node, in order
+pub fn pieces(e: &Expression) -> Vec<(&Expression, &Expression)> {
+    match e {
+        Expression::Equa(equation) => match equation.operation {
+            Operation::Piece => vec![(&*equation.element1, &*equation.element2)],
+            Operation::Piecewise => [pieces(&equation.element1), pieces(&equation.element2)].concat(),
+            _ => panic!("{} isn't piecewise", e),
+        },
+        _ => panic!("{} isn't piecewise", e),
+    }
+}
+
+fn is_piecewise(e: &Expression) -> bool {
+    matches!(e, Expression::Equa(equation) if matches!(equation.operation, Operation::Piecewise | Operation::Piece))
+}
+
+// the pieces put back together, first to last
+fn rebuild(mut list: Vec<(Expression, Expression)>) -> Expression {
+    let (condition, value) = list.pop().expect("a piecewise function needs a piece");
+    let mut rest = equa(Operation::Piece, condition, value);
+    while let Some((condition, value)) = list.pop() {
+        rest = equa(Operation::Piecewise, equa(Operation::Piece, condition, value), rest);
+    }
+    rest
+}
+
+fn relation(condition: &Expression) -> (Relation, &Expression, &Expression) {
+    match condition {
+        Expression::Equa(equation) => match equation.operation {
+            Operation::Relation(relation) => (relation, &equation.element1, &equation.element2),
+            _ => panic!("{} isn't a condition", condition),
+        },
+        _ => panic!("{} isn't a condition", condition),
+    }
+}
+
+fn compare<T: PartialOrd>(relation: Relation, left: T, right: T) -> bool {
+    match relation {
+        Relation::Less => left < right,
+        Relation::LessEqual => left <= right,
+        Relation::Greater => left > right,
+        Relation::GreaterEqual => left >= right,
+        Relation::Equal => left == right,
+        Relation::And => panic!("a chain is two comparisons"),
+    }
+}
+
+fn holds(bank: &Workspace, condition: &Expression, vars: &[(char, f64)]) -> bool {
+    match relation(condition) {
+        (Relation::And, first, second) => holds(bank, first, vars) && holds(bank, second, vars),
+        (relation, left, right) => compare(relation, left.approximate_with(bank, vars), right.approximate_with(bank, vars)),
+    }
+}
+
+fn holds_exactly(bank: &Workspace, condition: &Expression, x: Fraction) -> bool {
+    match relation(condition) {
+        (Relation::And, first, second) => holds_exactly(bank, first, x) && holds_exactly(bank, second, x),
+        (relation, left, right) => compare(relation, left.evaluate(bank, x), right.evaluate(bank, x)),
+    }
+}
+
+/// The value of the first piece whose condition holds, NaN when none do
+pub fn evaluate_node(bank: &Workspace, node: &Expression, x: Fraction) -> Fraction {
+    match pieces(node).into_iter().find(|(condition, _)| holds_exactly(bank, condition, x)) {
+        Some((_, value)) => value.evaluate(bank, x),
+        None => Fraction::nan(),
+    }
+}
+
+/// approximate for a piecewise node, NaN where no condition holds
+pub fn approximate_node(bank: &Workspace, node: &Expression, vars: &[(char, f64)]) -> f64 {
+    match pieces(node).into_iter().find(|(condition, _)| holds(bank, condition, vars)) {
+        Some((_, value)) => value.approximate_with(bank, vars),
+        None => f64::NAN,
+    }
+}
+
+// where the conditions anywhere in e switch, the side of each comparison with var alone on
+// the other
+fn cuts(bank: &Workspace, e: &Expression, var: char, found: &mut Vec<Bound>) {
+    let equation = match e {
+        Expression::Equa(equation) => equation,
+        _ => return,
+    };
+    match equation.operation {
+        Operation::Relation(Relation::And) => {
+            cuts(bank, &equation.element1, var, found);
+            cuts(bank, &equation.element2, var, found);
+        },
+        Operation::Relation(_) => {
+            let (left, right) = (&*equation.element1, &*equation.element2);
+            let side = if *left == Expression::Variable(var) && !contains(right, var) { Some(right) }
+                else if *right == Expression::Variable(var) && !contains(left, var) { Some(left) }
+                else { None };
+            if let Some(side) = side {
+                let exact = simplify(side);
+                if !found.iter().any(|b| b.exact.as_ref() == Some(&exact)) {
+                    found.push(Bound { value: exact.approximate_with(bank, &[]), exact: Some(exact) });
+                }
+            }
+        },
+        _ => {
+            cuts(bank, &equation.element1, var, found);
+            cuts(bank, &equation.element2, var, found);
+        },
+    }
+}
+
+/// Where the conditions anywhere in e switch between pieces, left to right
+pub fn breaks(bank: &Workspace, e: &Expression, var: char) -> Vec<Bound> {
+    let mut found = Vec::new();
+    cuts(bank, e, var, &mut found);
+    found.sort_by(|p, q| p.value.total_cmp(&q.value));
+    found
+}
+
+fn agree(p: f64, q: f64) -> bool {
+    p.is_finite() && q.is_finite() && (p - q).abs() < 1e-6 * p.abs().max(1.0)
+}
+
+fn analyze(bank: &Workspace, e: &Expression, slope: &Expression) -> Vec<Breakpoint> {
+    breaks(bank, e, 'x').into_iter().map(|x| {
+        let a = x.value;
+        let (left, right) = (limits::limit(bank, e, a, Side::Left), limits::limit(bank, e, a, Side::Right));
+        let (left_slope, right_slope) = (limits::limit(bank, slope, a, Side::Left), limits::limit(bank, slope, a, Side::Right));
+        let value = e.approximate(bank, a);
+        let continuous = agree(left, value) && agree(right, value);
+        let differentiable = continuous && agree(left_slope, right_slope);
+        Breakpoint { x, value, left, right, left_slope, right_slope, continuous, differentiable }
+    }).collect()
+}
+
+/// Continuity and differentiability wherever e switches pieces
+pub fn breakpoints(bank: &Workspace, e: &Expression) -> Vec<Breakpoint> {
+    let e = expand(bank, e);
+    let slope = derivative::derivative(bank, &e);
+    analyze(bank, &e, &slope)
+}
+
+/// slope, the piece by piece derivative of e, with the breakpoints where e isn't
+/// differentiable taken out. That's how derivative::derivative checks the boundaries
+pub fn corners_removed(bank: &Workspace, e: &Expression, slope: &Expression) -> Expression {
+    let corners: Vec<f64> = analyze(bank, e, slope).into_iter()
+        .filter(|point| !point.differentiable)
+        .map(|point| point.x.value)
+        .collect();
+    if corners.is_empty() { copy_expression(slope) } else { strict(bank, slope, &corners) }
+}
+
+// <= and >= made strict at the corners, and pieces only for a corner dropped
+fn strict(bank: &Workspace, e: &Expression, corners: &[f64]) -> Expression {
+    let equation = match e {
+        Expression::Equa(equation) => equation,
+        _ => return copy_expression(e),
+    };
+    if !is_piecewise(e) {
+        return equa(equation.operation, strict(bank, &equation.element1, corners), strict(bank, &equation.element2, corners));
+    }
+    let at_corner = |side: &Expression| !contains(side, 'x') && corners.iter().any(|c| (side.approximate_with(bank, &[]) - c).abs() < 1e-9);
+    let tighten = |condition: &Expression| -> Option<Expression> {
+        let single = |condition: &Expression| -> Option<Expression> {
+            let (relation, left, right) = relation(condition);
+            let corner = at_corner(left) || at_corner(right);
+            let relation = match (relation, corner) {
+                (Relation::Equal, true) => return None,
+                (Relation::LessEqual, true) => Relation::Less,
+                (Relation::GreaterEqual, true) => Relation::Greater,
+                (relation, _) => relation,
+            };
+            Some(equa(Operation::Relation(relation), copy_expression(left), copy_expression(right)))
+        };
+        match relation(condition) {
+            (Relation::And, first, second) => Some(equa(Operation::Relation(Relation::And), single(first)?, single(second)?)),
+            _ => single(condition),
+        }
+    };
+    let kept: Vec<(Expression, Expression)> = pieces(e).into_iter()
+        .filter_map(|(condition, value)| Some((tighten(condition)?, strict(bank, value, corners))))
+        .collect();
+    if kept.is_empty() { copy_expression(e) } else { rebuild(kept) }
+}
+
+/// ∫ from a to b of a piecewise e split where it switches pieces, each stretch integrated
+/// with the piece that holds there. NaN when a stretch has no piece
+pub fn integrate(bank: &Workspace, e: &Expression, var: char, a: &Expression, b: &Expression) -> Definite {
+    let (low, high) = (a.approximate_with(bank, &[]), b.approximate_with(bank, &[]));
+    let mut points = vec![Bound { value: low, exact: Some(copy_expression(a)) }];
+    points.extend(breaks(bank, e, var).into_iter().filter(|x| x.value > low.min(high) && x.value < high.max(low)));
+    points.push(Bound { value: high, exact: Some(copy_expression(b)) });
+    if high < low {
+        let last = points.len() - 1;
+        points[1..last].reverse();
+    }
+
+    let parts: Option<Vec<Definite>> = points.windows(2).map(|window| {
+        let middle = (window[0].value + window[1].value) / 2.0;
+        let (_, value) = pieces(e).into_iter().find(|(condition, _)| holds(bank, condition, &[(var, middle)]))?;
+        let (start, end) = (window[0].exact.as_ref().unwrap(), window[1].exact.as_ref().unwrap());
+        Some(integral::integrate_in(bank, value, var, start, end))
+    }).collect();
+    let Some(parts) = parts else { return Definite { value: f64::NAN, exact: None, method: Method::Numeric } };
+
+    let exact = parts.iter()
+        .map(|part| part.exact.as_ref().map(|e| format!("({})", e)))
+        .collect::<Option<Vec<String>>>()
+        .map(|terms| simplify(&Expression::from(&terms.join("+"))));
+    Definite {
+        value: parts.iter().map(|part| part.value).sum(),
+        method: if exact.is_some() { Method::FundamentalTheorem } else { Method::Numeric },
+        exact,
+    }
+}
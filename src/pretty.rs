@@ -0,0 +1,344 @@
+use super::*;
+
+impl Expression {
+    /// Plain text with only the brackets the parser needs, 3x+2^x rather than
+    /// ((3)(x))+((2)^(x)). Expression::from reads it back to the same tree
+    pub fn pretty(&self) -> String {
+        text(self, false).0
+    }
+
+    /// pretty with x², √x, π, · and f′(x), which Expression::from also reads
+    pub fn unicode(&self) -> String {
+        text(self, true).0
+    }
+
+    /// A picture for the terminal with fractions stacked and exponents raised,
+    /// meant for looking at rather than reading back
+    pub fn layout(&self) -> String {
+        let block = layout(self);
+        block.lines.iter().map(|line| line.trim_end()).collect::<Vec<_>>().join("\n")
+    }
+}
+
+/// Unicode math as the ASCII Expression::from reads, x² is x^(2) and √x is sqrt(x)
+pub fn plain(string: &str) -> String {
+    let superscript = |c: char| "⁰¹²³⁴⁵⁶⁷⁸⁹⁻".chars().position(|s| s == c).map(|i| "0123456789-".chars().nth(i).unwrap());
+    let mut out = String::new();
+    let mut chars = string.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '·' => out.push('*'),
+            '′' => out.push('\''),
+            '−' => out.push('-'),
+            '∞' => out.push_str("inf"),
+            '≤' => out.push_str("<="),
+            '≥' => out.push_str(">="),
+            '√' | '∛' => {
+                out.push_str(if c == '√' { "sqrt" } else { "cbrt" });
+                // √2 and √x take one number or letter, √(x+1) brings its own brackets
+                match chars.peek() {
+                    Some(d) if d.is_ascii_digit() => {
+                        out.push('(');
+                        while let Some(d) = chars.next_if(|d| d.is_ascii_digit() || *d == '.') {
+                            out.push(d);
+                        }
+                        out.push(')');
+                    },
+                    Some(d) if d.is_alphabetic() => out.push_str(&format!("({})", chars.next().unwrap())),
+                    _ => (),
+                }
+            },
+            c if superscript(c).is_some() => {
+                out.push_str("^(");
+                out.push(superscript(c).unwrap());
+                while let Some(d) = chars.next_if(|d| superscript(*d).is_some()) {
+                    out.push(superscript(d).unwrap());
+                }
+                out.push(')');
+            },
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+// the order the parser splits in, loosest first: the last +, the last -, a leading -,
+// the last *, the last /, the last spot two things sit side by side and the last ^
+const ADD: u8 = 1;
+const SUB: u8 = 2;
+const NEGATIVE: u8 = 3;
+const TIMES: u8 = 4;
+const DIVIDE: u8 = 5;
+const BESIDE: u8 = 6;
+const POWER: u8 = 7;
+const ATOM: u8 = 8;
+
+fn wrap((text, _): (String, u8), fits: bool) -> String {
+    if fits { text } else { format!("({})", text) }
+}
+
+fn superscript(n: &Fraction) -> Option<String> {
+    if n.denom() != Some(&1) {
+        return None;
+    }
+    let digits = "⁰¹²³⁴⁵⁶⁷⁸⁹";
+    let sign = if *n < Fraction::from(0) { "⁻" } else { "" };
+    let number: String = n.numer()?.to_string().chars().map(|d| digits.chars().nth(d.to_digit(10).unwrap() as usize).unwrap()).collect();
+    Some(format!("{}{}", sign, number))
+}
+
+fn constant(c: &Fraction, unicode: bool) -> (String, u8) {
+    let negative = *c < Fraction::from(0);
+    let level = if negative { NEGATIVE } else if c.denom().is_some_and(|d| *d != 1) { DIVIDE } else { ATOM };
+    match (c.is_infinite(), unicode) {
+        (true, true) => (format!("{}∞", if negative { "-" } else { "" }), level),
+        (true, false) => (format!("{}inf", if negative { "-" } else { "" }), level),
+        _ => (format!("{}", c), level),
+    }
+}
+
+// the brackets each side needs are worked out from the parser's order, so what comes out
+// splits the same way going back in
+fn text(e: &Expression, unicode: bool) -> (String, u8) {
+    let equation = match e {
+        Expression::Constant(c) => return constant(c, unicode),
+        Expression::Variable(v) => return (v.to_string(), ATOM),
+        Expression::Equa(equation) => equation,
+    };
+    let (a, b) = (&*equation.element1, &*equation.element2);
+    let (left, right) = (|| text(a, unicode), || text(b, unicode));
+    let call = |name: &str| (format!("{}({})", name, right().0), ATOM);
+    match equation.operation {
+        Operation::Add => {
+            let right = right();
+            let fits = right.1 > ADD && right.1 != NEGATIVE;
+            (format!("{}+{}", left().0, wrap(right, fits)), ADD)
+        },
+        // -3 on its own would read back as the constant
+        Operation::Sub if *a == Expression::Constant(Fraction::from(0)) => {
+            let right = right();
+            let fits = right.1 >= TIMES && !matches!(b, Expression::Constant(_));
+            (format!("-{}", wrap(right, fits)), NEGATIVE)
+        },
+        Operation::Sub => {
+            let (left, right) = (left(), right());
+            let (fits_left, fits_right) = (left.1 >= SUB, right.1 > SUB && right.1 != NEGATIVE);
+            (format!("{}-{}", wrap(left, fits_left), wrap(right, fits_right)), SUB)
+        },
+        Operation::Mult => {
+            let (left, right) = (left(), right());
+            let left_text = wrap(left.clone(), left.1 >= BESIDE);
+            let right_text = wrap(right.clone(), right.1 > BESIDE);
+            // 2 3 would run together, x(1) would be a call and e^xx is hard to read
+            let digits = left_text.ends_with(|c: char| c.is_ascii_digit()) && right_text.starts_with(|c: char| c.is_ascii_digit() || c == '.');
+            let called = left_text.ends_with(|c: char| c.is_alphabetic() || c == '\'' || c == '′') && right_text.starts_with('(');
+            let exponent = left_text.rsplit_once('^').is_some_and(|(_, power)| power.chars().all(|c| c.is_alphanumeric()))
+                && right_text.starts_with(|c: char| c.is_alphanumeric());
+            if !digits && !called && !exponent {
+                (format!("{}{}", left_text, right_text), BESIDE)
+            } else {
+                let (fits_left, fits_right) = (left.1 >= TIMES, right.1 > TIMES);
+                (format!("{}{}{}", wrap(left, fits_left), if unicode { "·" } else { "*" }, wrap(right, fits_right)), TIMES)
+            }
+        },
+        Operation::Div => {
+            let (left, right) = (left(), right());
+            // 2/3 would read back as the constant
+            let both_whole = matches!((a, b), (Expression::Constant(_), Expression::Constant(_)));
+            let (fits_left, fits_right) = (left.1 >= DIVIDE, right.1 > DIVIDE && !both_whole);
+            (format!("{}/{}", wrap(left, fits_left), wrap(right, fits_right)), DIVIDE)
+        },
+        Operation::Exp => {
+            let left = left();
+            match (unicode, b) {
+                (true, Expression::Constant(n)) if superscript(n).is_some() => {
+                    let fits = left.1 == ATOM;
+                    (format!("{}{}", wrap(left, fits), superscript(n).unwrap()), POWER)
+                },
+                _ => {
+                    let right = right();
+                    let (fits_left, fits_right) = (left.1 >= POWER, right.1 == ATOM);
+                    (format!("{}^{}", wrap(left, fits_left), wrap(right, fits_right)), POWER)
+                },
+            }
+        },
+        Operation::Func => (format!("{}({})", left().0, right().0), ATOM),
+        Operation::Trig => call(match a {
+            Expression::Variable('s') => "sin",
+            Expression::Variable('c') => "cos",
+            Expression::Variable('t') => "tan",
+            Expression::Variable('S') => "arcsin",
+            Expression::Variable('C') => "arccos",
+            Expression::Variable('T') => "arctan",
+            _ => panic!("{} isn't a trig function", a),
+        }),
+        Operation::Log => match a {
+            Expression::Variable('e') => call("ln"),
+            Expression::Constant(c) if *c == Fraction::from(10) => call("log"),
+            _ => call(&format!("log_{}", left().0)),
+        },
+        Operation::Deriv => (format!("{}{}", left().0, if unicode { "′" } else { "'" }), ATOM),
+        Operation::Sum(n) | Operation::Integral(n) => {
+            let name = if let Operation::Sum(_) = equation.operation { "sum" } else { "int" };
+            (format!("{}({},{},{})", name, n, left().0, right().0), ATOM)
+        },
+        Operation::Bounds => (format!("{},{}", left().0, right().0), ATOM),
+        Operation::Piecewise | Operation::Piece => {
+            let pieces: Vec<String> = piecewise::pieces(e).iter()
+                .map(|(condition, value)| format!("{} if {}", text(value, unicode).0, text(condition, unicode).0))
+                .collect();
+            (format!("{{{}}}", pieces.join("; ")), ATOM)
+        },
+        Operation::Relation(Relation::And) => match b {
+            Expression::Equa(second) => match second.operation {
+                Operation::Relation(r) => (format!("{}{}{}", left().0, symbol(r, unicode), text(&second.element2, unicode).0), ATOM),
+                _ => panic!("the second half of a chained condition is a relation"),
+            },
+            _ => panic!("the second half of a chained condition is a relation"),
+        },
+        Operation::Relation(r) => (format!("{}{}{}", left().0, symbol(r, unicode), right().0), ATOM),
+        Operation::Abs => (format!("|{}|", right().0), ATOM),
+        Operation::Floor => call("floor"),
+        Operation::Ceil => call("ceil"),
+        Operation::Root => {
+            let radicand = right();
+            let single = radicand.0.chars().count() == 1 || (radicand.1 == ATOM && radicand.0.chars().all(|c| c.is_ascii_digit()));
+            match (a, unicode) {
+                (Expression::Constant(n), true) if *n == Fraction::from(2) || *n == Fraction::from(3) => {
+                    let sign = if *n == Fraction::from(2) { "√" } else { "∛" };
+                    (format!("{}{}", sign, wrap(radicand, single)), ATOM)
+                },
+                (Expression::Constant(n), false) if *n == Fraction::from(2) => call("sqrt"),
+                (Expression::Constant(n), false) if *n == Fraction::from(3) => call("cbrt"),
+                _ => (format!("root({},{})", left().0, radicand.0), ATOM),
+            }
+        },
+    }
+}
+
+fn symbol(relation: Relation, unicode: bool) -> &'static str {
+    match (relation, unicode) {
+        (Relation::LessEqual, true) => "≤",
+        (Relation::GreaterEqual, true) => "≥",
+        (relation, _) => match relation {
+            Relation::Less => "<",
+            Relation::LessEqual => "<=",
+            Relation::Greater => ">",
+            Relation::GreaterEqual => ">=",
+            Relation::Equal => "=",
+            Relation::And => panic!("a chain is two comparisons"),
+        },
+    }
+}
+
+// a rectangle of text, baseline is the row the rest of the line lines up with
+struct Block {
+    lines: Vec<String>,
+    baseline: usize,
+}
+
+impl Block {
+    fn from(text: &str) -> Block {
+        Block { lines: vec![text.to_string()], baseline: 0 }
+    }
+
+    fn width(&self) -> usize {
+        self.lines.iter().map(|line| line.chars().count()).max().unwrap_or(0)
+    }
+
+    fn height(&self) -> usize {
+        self.lines.len()
+    }
+
+    fn beside(self, other: Block) -> Block {
+        let above = self.baseline.max(other.baseline);
+        let below = (self.height() - self.baseline).max(other.height() - other.baseline);
+        let (first, second) = (self.padded(above, below), other.padded(above, below));
+        let width = first.width();
+        let lines = first.lines.iter().zip(&second.lines)
+            .map(|(p, q)| format!("{:<width$}{}", p, q, width = width))
+            .collect();
+        Block { lines, baseline: above }
+    }
+
+    // blank rows added so the baseline is `above` rows down and `below` rows from the bottom
+    fn padded(self, above: usize, below: usize) -> Block {
+        let width = self.width();
+        let blank = " ".repeat(width);
+        let mut lines = vec![blank.clone(); above - self.baseline];
+        lines.extend(self.lines.iter().map(|line| format!("{:<width$}", line, width = width)));
+        lines.extend(vec![blank; below - (self.height() - self.baseline)]);
+        Block { lines, baseline: above }
+    }
+
+    // each block is centered as a whole so an exponent stays over what it raises
+    fn over(self, denominator: Block) -> Block {
+        let width = self.width().max(denominator.width()) + 2;
+        let center = |block: Block| {
+            let gap = " ".repeat((width - block.width()) / 2);
+            let (above, below) = (block.baseline, block.height() - block.baseline);
+            block.padded(above, below).lines.into_iter().map(move |line| format!("{}{}", gap, line))
+        };
+        let height = self.height();
+        let mut lines: Vec<String> = center(self).collect();
+        lines.push("-".repeat(width));
+        lines.extend(center(denominator));
+        Block { lines, baseline: height }
+    }
+
+    fn raised(self, exponent: Block) -> Block {
+        let width = self.width();
+        let mut lines: Vec<String> = exponent.lines.iter().map(|line| format!("{}{}", " ".repeat(width), line)).collect();
+        lines.extend(self.lines);
+        Block { lines, baseline: exponent.height() + self.baseline }
+    }
+
+    fn bracketed(self) -> Block {
+        if self.height() == 1 {
+            return Block::from(&format!("({})", self.lines[0]));
+        }
+        let last = self.height() - 1;
+        let side = |i: usize, top: char, middle: char, bottom: char| if i == 0 { top } else if i == last { bottom } else { middle };
+        let width = self.width();
+        let lines = self.lines.iter().enumerate()
+            .map(|(i, line)| format!("{}{:<width$}{}", side(i, '/', '|', '\\'), line, side(i, '\\', '|', '/'), width = width))
+            .collect();
+        Block { lines, baseline: self.baseline }
+    }
+}
+
+fn laid(e: &Expression, needs: u8) -> Block {
+    if precedence(e) < needs { layout(e).bracketed() } else { layout(e) }
+}
+
+fn layout(e: &Expression) -> Block {
+    let equation = match e {
+        Expression::Constant(c) if c.denom().is_some_and(|d| *d > 1) => {
+            let fraction = Block::from(&c.numer().unwrap().to_string()).over(Block::from(&c.denom().unwrap().to_string()));
+            return if *c < Fraction::from(0) { Block::from("-").beside(fraction) } else { fraction };
+        },
+        Expression::Equa(equation) => equation,
+        _ => return Block::from(&e.pretty()),
+    };
+    let (a, b) = (&*equation.element1, &*equation.element2);
+    match equation.operation {
+        Operation::Add => laid(a, 1).beside(Block::from(" + ")).beside(laid(b, 2)),
+        Operation::Sub if *a == Expression::Constant(Fraction::from(0)) => Block::from("-").beside(laid(b, 2)),
+        Operation::Sub => laid(a, 1).beside(Block::from(" - ")).beside(laid(b, 2)),
+        Operation::Mult => laid(a, 2).beside(Block::from(" ")).beside(laid(b, 3)),
+        Operation::Div => layout(a).over(layout(b)),
+        Operation::Exp => laid(a, 4).raised(layout(b)),
+        Operation::Func | Operation::Trig | Operation::Log | Operation::Floor | Operation::Ceil => {
+            let name = e.pretty();
+            let name = &name[..name.find('(').unwrap()];
+            Block::from(name).beside(layout(b).bracketed())
+        },
+        Operation::Abs => {
+            let inside = layout(b);
+            let bar = Block { lines: vec!["|".to_string(); inside.height()], baseline: inside.baseline };
+            let bar2 = Block { lines: vec!["|".to_string(); inside.height()], baseline: inside.baseline };
+            bar.beside(inside).beside(bar2)
+        },
+        _ => Block::from(&e.pretty()),
+    }
+}
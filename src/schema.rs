@@ -0,0 +1,280 @@
+use super::*;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// the shape an Expression takes in JSON, Expression goes through this both ways
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+enum Node {
+    Constant { numerator: String, denominator: String },
+    Variable { name: char },
+    Operation {
+        operation: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        variable: Option<char>,
+        children: Vec<Node>,
+    },
+}
+
+const OPERATIONS: [(&str, Operation); 24] = [
+    ("add", Operation::Add),
+    ("sub", Operation::Sub),
+    ("mult", Operation::Mult),
+    ("div", Operation::Div),
+    ("func", Operation::Func),
+    ("exp", Operation::Exp),
+    ("trig", Operation::Trig),
+    ("log", Operation::Log),
+    ("deriv", Operation::Deriv),
+    ("sum", Operation::Sum('n')),
+    ("integral", Operation::Integral('x')),
+    ("bounds", Operation::Bounds),
+    ("piecewise", Operation::Piecewise),
+    ("piece", Operation::Piece),
+    ("less", Operation::Relation(Relation::Less)),
+    ("less_equal", Operation::Relation(Relation::LessEqual)),
+    ("greater", Operation::Relation(Relation::Greater)),
+    ("greater_equal", Operation::Relation(Relation::GreaterEqual)),
+    ("equal", Operation::Relation(Relation::Equal)),
+    ("and", Operation::Relation(Relation::And)),
+    ("abs", Operation::Abs),
+    ("floor", Operation::Floor),
+    ("ceil", Operation::Ceil),
+    ("root", Operation::Root),
+];
+
+fn operation_name(operation: Operation) -> &'static str {
+    let operation = match operation {
+        Operation::Sum(_) => Operation::Sum('n'),
+        Operation::Integral(_) => Operation::Integral('x'),
+        other => other,
+    };
+    OPERATIONS.iter().find(|(_, o)| *o == operation).map(|(name, _)| *name).unwrap()
+}
+
+// ±infinity is ±1/0 and NaN is 0/0
+fn numerator_and_denominator(c: &Fraction) -> (String, String) {
+    let sign = if *c < Fraction::from(0) { "-" } else { "" };
+    match (c.numer(), c.denom()) {
+        _ if c.is_nan() => (String::from("0"), String::from("0")),
+        _ if c.is_infinite() => (format!("{}1", sign), String::from("0")),
+        (Some(n), Some(d)) => (format!("{}{}", sign, n), format!("{}", d)),
+        _ => (String::from("0"), String::from("0")),
+    }
+}
+
+fn constant(numerator: &str, denominator: &str) -> Option<Fraction> {
+    let (negative, digits) = match numerator.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, numerator),
+    };
+    let (n, d) = (digits.parse::<u64>().ok()?, denominator.parse::<u64>().ok()?);
+    let c = match (n, d) {
+        (0, 0) => Fraction::nan(),
+        (_, 0) => Fraction::infinity(),
+        _ => Fraction::new(n, d),
+    };
+    Some(if negative { -c } else { c })
+}
+
+fn node(e: &Expression) -> Node {
+    match e {
+        Expression::Constant(c) => {
+            let (numerator, denominator) = numerator_and_denominator(c);
+            Node::Constant { numerator, denominator }
+        },
+        Expression::Variable(v) => Node::Variable { name: *v },
+        Expression::Equa(equation) => Node::Operation {
+            operation: String::from(operation_name(equation.operation)),
+            variable: match equation.operation {
+                Operation::Sum(v) | Operation::Integral(v) => Some(v),
+                _ => None,
+            },
+            children: vec![node(&equation.element1), node(&equation.element2)],
+        },
+    }
+}
+
+fn expression(node: Node) -> Result<Expression, String> {
+    match node {
+        Node::Constant { numerator, denominator } => constant(&numerator, &denominator)
+            .map(Expression::Constant)
+            .ok_or_else(|| format!("{}/{} isn't a fraction of whole numbers", numerator, denominator)),
+        Node::Variable { name } => Ok(Expression::Variable(name)),
+        Node::Operation { operation, variable, children } => {
+            let found = OPERATIONS.iter().find(|(name, _)| *name == operation).map(|(_, o)| *o);
+            let operation = match (found, variable) {
+                (None, _) => return Err(format!("there's no operation called {}", operation)),
+                (Some(Operation::Sum(_)), Some(v)) => Operation::Sum(v),
+                (Some(Operation::Integral(_)), Some(v)) => Operation::Integral(v),
+                (Some(Operation::Sum(_) | Operation::Integral(_)), None) => return Err(format!("a {} needs its variable", operation)),
+                (Some(_), Some(_)) => return Err(format!("a {} has no variable", operation)),
+                (Some(o), None) => o,
+            };
+            let [element1, element2]: [Node; 2] = children.try_into().map_err(|_| String::from("an operation has two children"))?;
+            Ok(equa(operation, expression(element1)?, expression(element2)?))
+        },
+    }
+}
+
+/// The JSON for an expression is a tree of nodes, each with a type
+///
+/// - `{"type": "constant", "numerator": "-3", "denominator": "4"}`, exact rationals as
+///   strings so nothing is lost to floats. The numerator has the sign, 1/0 is infinity,
+///   -1/0 minus infinity and 0/0 undefined
+/// - `{"type": "variable", "name": "x"}`, π and e are variables too
+/// - `{"type": "operation", "operation": "add", "children": [left, right]}`, always two
+///   children and sum and integral have a `"variable"` as well
+///
+/// The operations and their children are add, sub, mult, div and exp (left and right, -u is
+/// sub with 0 on the left), func (name, argument), trig (s c t for sin cos tan, S C T for
+/// their inverses, then the argument), log (base, argument), deriv (the function's name,
+/// then x), sum and integral (bounds, then the summand or integrand), bounds (lower,
+/// upper), piecewise (a piece, then the rest), piece (condition, value), less, less_equal,
+/// greater, greater_equal, equal and and (left, right, and joins the two comparisons of
+/// a < x < b), abs, floor and ceil (1, argument) and root (index, radicand)
+impl Serialize for Expression {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        node(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Expression {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Expression, D::Error> {
+        expression(Node::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+/// null when undefined, the expression otherwise
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Defined(e) => serializer.serialize_some(e),
+            Value::Undefined => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Value, D::Error> {
+        Ok(Option::<Expression>::deserialize(deserializer)?.map_or(Value::Undefined, Value::Defined))
+    }
+}
+
+/// `{"kind": "function", "value": ...}` with kind function, variable or accumulation and
+/// the value as for Value
+impl Serialize for Letter {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (kind, value) = match self {
+            Letter::Function(value) => ("function", value),
+            Letter::Variable(value) => ("variable", value),
+            Letter::Accumulation(value) => ("accumulation", value),
+        };
+        #[derive(Serialize)]
+        struct Entry<'a> { kind: &'a str, value: &'a Value }
+        Entry { kind, value }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Letter {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Letter, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Entry { kind: String, value: Value }
+        let entry = Entry::deserialize(deserializer)?;
+        match entry.kind.as_str() {
+            "function" => Ok(Letter::Function(entry.value)),
+            "variable" => Ok(Letter::Variable(entry.value)),
+            "accumulation" => Ok(Letter::Accumulation(entry.value)),
+            other => Err(D::Error::custom(format!("{} isn't a kind of letter", other))),
+        }
+    }
+}
+
+/// A map from each letter to its Letter, in alphabetical order
+impl Serialize for Workspace {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
+
+/// and read back through define_function, define_variable and define_accumulation, so a
+/// letter that depends on itself or uses one the map leaves out is turned down
+impl<'de> Deserialize<'de> for Workspace {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Workspace, D::Error> {
+        let mut letters: Vec<(char, Letter)> = HashMap::<char, Letter>::deserialize(deserializer)?.into_iter().collect();
+        letters.sort_by_key(|(name, _)| *name);
+        let mut workspace = Workspace::new();
+        for (name, letter) in &letters {
+            if let Value::Defined(rule) = letter.get_inside() {
+                if let Some(r) = workspace::references(rule).into_iter().find(|r| !letters.iter().any(|(other, _)| other == r)) {
+                    return Err(D::Error::custom(format!("{} uses {}, which isn't defined", name, r)));
+                }
+            }
+        }
+        for (name, letter) in letters {
+            let admitted = match letter {
+                Letter::Function(Value::Defined(rule)) => workspace.define_function(name, rule),
+                Letter::Variable(Value::Defined(value)) => workspace.define_variable(name, value),
+                Letter::Accumulation(Value::Defined(rule)) => workspace.define_accumulation(name, rule),
+                undefined => {
+                    workspace.letters.insert(name, undefined);
+                    Ok(())
+                },
+            };
+            admitted.map_err(D::Error::custom)?;
+        }
+        Ok(workspace)
+    }
+}
+
+// The results of the other modules derive Serialize and Deserialize, fields by name and
+// enum variants in snake_case, so they read much like their Rust
+//
+// - applications::Bound is {"value": 1.5707963, "exact": expression or null}
+// - integral::Definite is {"value", "exact", "method"} with method fundamental_theorem or numeric
+// - series::TestResult is {"test": "ratio", "verdict": "converges", "reason": "..."} and
+//   IntervalOfConvergence {"center", "radius", "left", "right"}, an end null when it wasn't checked
+// - theorems::Justification has theorem mean_value, rolle or intermediate_value, f, a, b,
+//   hypotheses as [text, held] pairs, met, fa, fb, target and the c values as Bounds
+// - tangent::Linearization has line, approximation, value, actual, error and estimate under,
+//   over or undetermined, and a tangent::Line is {"graph": y} or {"vertical": x}
+// - domain::IntervalSet is {"intervals", "excluded", "approximate"}, an Interval {"low",
+//   "high", "closed_low", "closed_high"} between Bounds, a Periodic {"start", "period"} and
+//   approximate null or the [a, b] that was worked out
+// - continuity::Point is {"x", "kind", "left", "right", "value"} with kind removable, jump,
+//   infinite or oscillating, and an Asymptote {"vertical": x}, {"horizontal": y} or
+//   {"slant": [m, b]}
+//
+// f64s in them that can be infinite or NaN go through number
+
+/// A number that can be infinite or NaN, which JSON has no numbers for, so those are the
+/// strings "inf", "-inf" and "nan"
+pub mod number {
+    use serde::de::Error as _;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(x: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        match *x {
+            x if x.is_finite() => serializer.serialize_f64(x),
+            x if x.is_nan() => serializer.serialize_str("nan"),
+            x if x > 0.0 => serializer.serialize_str("inf"),
+            _ => serializer.serialize_str("-inf"),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Number { Finite(f64), Text(String) }
+        match Number::deserialize(deserializer)? {
+            Number::Finite(x) => Ok(x),
+            Number::Text(text) => match text.as_str() {
+                "inf" => Ok(f64::INFINITY),
+                "-inf" => Ok(f64::NEG_INFINITY),
+                "nan" => Ok(f64::NAN),
+                other => Err(D::Error::custom(format!("{} isn't a number", other))),
+            },
+        }
+    }
+}
@@ -0,0 +1,557 @@
+use super::*;
+use num::CheckedAdd;
+
+#[derive(PartialEq)]
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Convergence {
+    Converges,
+    Diverges,
+    Inconclusive,
+}
+
+#[derive(PartialEq)]
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum SeriesTest {
+    NthTerm,
+    Geometric,
+    PSeries,
+    Integral,
+    Comparison,
+    LimitComparison,
+    Ratio,
+    Root,
+    AlternatingSeries,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TestResult {
+    pub test: SeriesTest,
+    pub verdict: Convergence,
+    pub reason: String,
+}
+
+/// Where a power series converges, endpoints are None when the radius is 0 or infinite
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IntervalOfConvergence {
+    pub center: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::schema::number"))]
+    pub radius: f64,
+    pub left: Option<Convergence>,
+    pub right: Option<Convergence>,
+}
+
+// the terms past this are checked when a test needs "eventually"
+const EVENTUALLY: f64 = 10.0;
+
+// a limit of a_n further from 0 than this is clearly not 0
+const NOT_ZERO: f64 = 1e-3;
+
+impl TestResult {
+    fn new(test: SeriesTest, verdict: Convergence, reason: String) -> TestResult {
+        TestResult { test, verdict, reason }
+    }
+}
+
+/// Splits a sum into its index, bounds and summand
+fn parts(series: &Expression) -> (char, &Expression, &Expression, &Expression) {
+    if let Expression::Equa(equation) = series {
+        if let (Operation::Sum(n), Expression::Equa(bounds)) = (equation.operation, &*equation.element1) {
+            return (n, &bounds.element1, &bounds.element2, &equation.element2);
+        }
+    }
+    panic!("{} is not a sum", series)
+}
+
+fn term(bank: &Workspace, summand: &Expression, index: char, n: f64) -> f64 {
+    summand.approximate_with(bank, &[(index, n)])
+}
+
+/// Value of a sum for Expression::evaluate, exact when the bounds are finite
+pub fn evaluate_sum(index: char, bounds: &Expression, summand: &Expression, bank: &Workspace, x: Fraction) -> Fraction {
+    let (lower, upper) = match bounds {
+        Expression::Equa(b) => (b.element1.evaluate(bank, x), b.element2.evaluate(bank, x)),
+        _ => panic!("sum without bounds"),
+    };
+    if upper.is_infinite() {
+        return Fraction::from(approximate_sum(index, bounds, summand, bank, &[('x', x.to_f64().unwrap())]));
+    }
+    let summand = substitute(summand, 'x', &Expression::Constant(x));
+    exact_sum(bank, &summand, index, lower, upper)
+        .unwrap_or_else(|| Fraction::from(approximate_sum(index, bounds, &summand, bank, &[])))
+}
+
+/// Value of a sum for Expression::approximate, infinite sums stop once the terms are tiny
+pub fn approximate_sum(index: char, bounds: &Expression, summand: &Expression, bank: &Workspace, vars: &[(char, f64)]) -> f64 {
+    let (lower, upper) = match bounds {
+        Expression::Equa(b) => (b.element1.approximate_with(bank, vars), b.element2.approximate_with(bank, vars)),
+        _ => panic!("sum without bounds"),
+    };
+    let mut vars: Vec<(char, f64)> = vars.iter().copied().filter(|(v, _)| *v != index).collect();
+    vars.push((index, lower));
+    let last = vars.len() - 1;
+
+    let upper = upper.min(lower + 100000.0);
+    let mut total = 0.0;
+    let mut previous = 0.0;
+    let mut n = lower;
+    while n <= upper {
+        vars[last].1 = n;
+        previous = total;
+        total += summand.approximate_with(bank, &vars);
+        n += 1.0;
+    }
+    // averaging the last two partial sums helps alternating series a lot
+    if upper.is_finite() && upper == lower + 100000.0 { (total + previous) / 2.0 } else { total }
+}
+
+fn exact_sum(bank: &Workspace, summand: &Expression, index: char, lower: Fraction, upper: Fraction) -> Option<Fraction> {
+    let mut total = Fraction::from(0);
+    let mut n = lower;
+    while n <= upper {
+        let a_n = substitute(summand, index, &Expression::Constant(n)).evaluate(bank, Fraction::from(0));
+        total = total.checked_add(&a_n)?;
+        n += Fraction::from(1);
+    }
+    Some(total)
+}
+
+/// S_upper, the sum of the terms from the lower bound up to and including upper, exactly
+/// when the fractions allow it
+pub fn partial_sum(bank: &Workspace, series: &Expression, upper: i64) -> Fraction {
+    let (index, lower, _, summand) = parts(series);
+    let lower = lower.evaluate(bank, Fraction::from(0));
+    exact_sum(bank, summand, index, lower, Fraction::from(upper)).unwrap_or_else(|| {
+        let lower = lower.to_f64().unwrap() as i64;
+        Fraction::from((lower..=upper).map(|n| term(bank, summand, index, n as f64)).sum::<f64>())
+    })
+}
+
+// slope of e in var if e is linear in it, checked on a few points
+fn linear_slope(bank: &Workspace, e: &Expression, var: char) -> Option<f64> {
+    let at = |v: f64| e.approximate_with(bank, &[(var, v)]);
+    let (e0, e1, e2) = (at(0.0), at(1.0), at(2.0));
+    if ((e2 - e1) - (e1 - e0)).abs() < 1e-9 { Some(e1 - e0) } else { None }
+}
+
+/// r for summands of the form c*r^(an+b), found from the structure of the summand
+fn geometric_ratio(bank: &Workspace, summand: &Expression, index: char) -> Option<f64> {
+    if !contains(summand, index) {
+        return Some(1.0);
+    }
+    let equation = match summand {
+        Expression::Equa(equation) => equation,
+        _ => return None,
+    };
+    let free1 = !contains(&equation.element1, index);
+    let free2 = !contains(&equation.element2, index);
+    match equation.operation {
+        Operation::Exp if free1 => {
+            let slope = linear_slope(bank, &equation.element2, index)?;
+            Some(equation.element1.approximate(bank, 0.0).powf(slope))
+        },
+        Operation::Mult => Some(geometric_ratio(bank, &equation.element1, index)? * geometric_ratio(bank, &equation.element2, index)?),
+        Operation::Div if free2 => geometric_ratio(bank, &equation.element1, index),
+        Operation::Div => Some(geometric_ratio(bank, &equation.element1, index)? / geometric_ratio(bank, &equation.element2, index)?),
+        _ => None,
+    }
+}
+
+/// p for summands of the form c/n^p or c*n^(-p)
+fn p_value(bank: &Workspace, summand: &Expression, index: char) -> Option<f64> {
+    let equation = match summand {
+        Expression::Equa(equation) => equation,
+        Expression::Variable(v) if *v == index => return Some(-1.0),
+        _ => return None,
+    };
+    let free1 = !contains(&equation.element1, index);
+    let free2 = !contains(&equation.element2, index);
+    match equation.operation {
+        Operation::Exp if *equation.element1 == Expression::Variable(index) && free2 =>
+            Some(-equation.element2.approximate(bank, 0.0)),
+        Operation::Div if free1 => Some(-p_value(bank, &equation.element2, index)?),
+        Operation::Div if free2 => p_value(bank, &equation.element1, index),
+        Operation::Mult if free1 => p_value(bank, &equation.element2, index),
+        Operation::Mult if free2 => p_value(bank, &equation.element1, index),
+        _ => None,
+    }
+}
+
+/// Diverges when a_n settles on a limit that is clearly not 0, says nothing otherwise. A
+/// limit the terms don't settle on or one too close to 0 to tell is left to the other tests
+pub fn nth_term_test(bank: &Workspace, series: &Expression) -> TestResult {
+    let (index, _, _, summand) = parts(series);
+    let limit = settled_limit(|n| term(bank, summand, index, n));
+    // a_n can swing between ±1 without a limit and still not go to 0, |a_n| shows it
+    let size = if limit.is_nan() { settled_limit(|n| term(bank, summand, index, n).abs()) } else { limit.abs() };
+
+    if limit.is_nan() && size > NOT_ZERO {
+        TestResult::new(SeriesTest::NthTerm, Convergence::Diverges,
+            format!("lim |a_n| = {} which is not 0, so a_n doesn't go to 0", size))
+    } else if limit.is_nan() {
+        TestResult::new(SeriesTest::NthTerm, Convergence::Inconclusive,
+            String::from("a_n doesn't settle on a limit so the nth term test says nothing"))
+    } else if limit.abs() > NOT_ZERO {
+        TestResult::new(SeriesTest::NthTerm, Convergence::Diverges,
+            format!("lim a_n = {} which is not 0", limit))
+    } else {
+        TestResult::new(SeriesTest::NthTerm, Convergence::Inconclusive,
+            String::from("lim a_n = 0 so the nth term test says nothing"))
+    }
+}
+
+pub fn geometric_test(bank: &Workspace, series: &Expression) -> TestResult {
+    let (index, _, _, summand) = parts(series);
+    // 0 is c*r^n for every r, and a sum of them converges
+    if !contains(summand, index) && summand.approximate(bank, 0.0) == 0.0 {
+        return TestResult::new(SeriesTest::Geometric, Convergence::Converges, String::from("every term is 0"));
+    }
+    match geometric_ratio(bank, summand, index) {
+        Some(r) if r.abs() < 1.0 => TestResult::new(SeriesTest::Geometric, Convergence::Converges,
+            format!("geometric with |r| = {} < 1", r.abs())),
+        Some(r) => TestResult::new(SeriesTest::Geometric, Convergence::Diverges,
+            format!("geometric with |r| = {} >= 1", r.abs())),
+        None => TestResult::new(SeriesTest::Geometric, Convergence::Inconclusive,
+            String::from("not a geometric series")),
+    }
+}
+
+pub fn p_series_test(bank: &Workspace, series: &Expression) -> TestResult {
+    let (index, _, _, summand) = parts(series);
+    match p_value(bank, summand, index) {
+        Some(p) if p > 1.0 => TestResult::new(SeriesTest::PSeries, Convergence::Converges,
+            format!("p-series with p = {} > 1", p)),
+        Some(p) => TestResult::new(SeriesTest::PSeries, Convergence::Diverges,
+            format!("p-series with p = {} <= 1", p)),
+        None => TestResult::new(SeriesTest::PSeries, Convergence::Inconclusive,
+            String::from("not a p-series")),
+    }
+}
+
+/// Needs f positive and decreasing past some point, decides by how ∫_N^2N f shrinks as N doubles
+pub fn integral_test(bank: &Workspace, series: &Expression) -> TestResult {
+    let (index, _, _, summand) = parts(series);
+    let f = |t: f64| term(bank, summand, index, t);
+
+    let positive_decreasing = (0..40).all(|k| {
+        let t = EVENTUALLY + k as f64 * 2.5;
+        f(t) > 0.0 && f(t + 0.5) <= f(t)
+    });
+    if !positive_decreasing {
+        return TestResult::new(SeriesTest::Integral, Convergence::Inconclusive,
+            String::from("f is not eventually positive and decreasing"));
+    }
+
+    let tails: Vec<f64> = (3..10)
+        .map(|k| 4f64.powi(k))
+        .map(|big_n| numeric::simpson(f, big_n, 2.0 * big_n, 200))
+        .take_while(|tail| tail.is_finite())
+        .collect();
+    if tails.len() < 3 {
+        return TestResult::new(SeriesTest::Integral, Convergence::Inconclusive,
+            String::from("f could not be integrated far enough out"));
+    }
+
+    let ratio = |i: usize| tails[i+1] / tails[i];
+    let last = tails.len() - 2;
+    let (q1, q2) = (ratio(last - 1), ratio(last));
+    if q2 >= 0.999 {
+        TestResult::new(SeriesTest::Integral, Convergence::Diverges,
+            String::from("∫ f dx over [N,2N] does not shrink so ∫_1^∞ f dx diverges"))
+    } else if q2 < 0.97 && (q1 - q2).abs() < 0.01 {
+        TestResult::new(SeriesTest::Integral, Convergence::Converges,
+            String::from("∫ f dx over [N,2N] shrinks geometrically so ∫_1^∞ f dx converges"))
+    } else {
+        TestResult::new(SeriesTest::Integral, Convergence::Inconclusive,
+            String::from("∫_1^∞ f dx is too close to the boundary to call numerically"))
+    }
+}
+
+/// Direct comparison against another sum with the same index, which is decided by the
+/// geometric or p-series tests
+pub fn comparison_test(bank: &Workspace, series: &Expression, other: &Expression) -> TestResult {
+    let (index, _, _, a) = parts(series);
+    let (other_index, _, _, b) = parts(other);
+    let other_verdict = known_verdict(bank, other);
+
+    let samples: Vec<(f64, f64)> = (0..200)
+        .map(|k| EVENTUALLY + k as f64)
+        .map(|n| (term(bank, a, index, n), term(bank, b, other_index, n)))
+        .collect();
+    let smaller = samples.iter().all(|(a, b)| 0.0 <= *a && a <= b);
+    let bigger = samples.iter().all(|(a, b)| 0.0 <= *b && b <= a);
+
+    match other_verdict {
+        Convergence::Converges if smaller => TestResult::new(SeriesTest::Comparison, Convergence::Converges,
+            format!("0 <= a_n <= {} which converges", simplify::simplify(b))),
+        Convergence::Diverges if bigger => TestResult::new(SeriesTest::Comparison, Convergence::Diverges,
+            format!("a_n >= {} >= 0 which diverges", simplify::simplify(b))),
+        _ => TestResult::new(SeriesTest::Comparison, Convergence::Inconclusive,
+            format!("comparing with {} says nothing", simplify::simplify(b))),
+    }
+}
+
+/// Needs a_n and b_n eventually positive, then a finite positive lim a_n/b_n means both
+/// series do the same thing
+pub fn limit_comparison_test(bank: &Workspace, series: &Expression, other: &Expression) -> TestResult {
+    let (index, _, _, a) = parts(series);
+    let (other_index, _, _, b) = parts(other);
+    let b = simplify::simplify(b);
+    let positive = (0..200).map(|k| EVENTUALLY + k as f64)
+        .all(|n| term(bank, a, index, n) > 0.0 && term(bank, &b, other_index, n) > 0.0);
+    if !positive {
+        return TestResult::new(SeriesTest::LimitComparison, Convergence::Inconclusive,
+            format!("a_n and {} are not both eventually positive", b));
+    }
+    let limit = settled_limit(|n| term(bank, a, index, n) / term(bank, &b, other_index, n));
+
+    if limit.is_finite() && limit > 1e-9 {
+        let verdict = known_verdict(bank, other);
+        TestResult::new(SeriesTest::LimitComparison, verdict,
+            format!("lim a_n/b_n = {} with b_n = {} which {}", limit, b, describe(verdict)))
+    } else {
+        TestResult::new(SeriesTest::LimitComparison, Convergence::Inconclusive,
+            format!("lim a_n/b_n = {} so comparing with {} says nothing", limit, b))
+    }
+}
+
+pub fn ratio_test(bank: &Workspace, series: &Expression) -> TestResult {
+    let (index, _, _, summand) = parts(series);
+    let limit = settled_limit(|n| (term(bank, summand, index, n + 1.0) / term(bank, summand, index, n)).abs());
+    limit_against_one(SeriesTest::Ratio, "lim |a_(n+1)/a_n|", limit)
+}
+
+pub fn root_test(bank: &Workspace, series: &Expression) -> TestResult {
+    let (index, _, _, summand) = parts(series);
+    let limit = settled_limit(|n| (term(bank, summand, index, n).abs().ln() / n).exp());
+    limit_against_one(SeriesTest::Root, "lim |a_n|^(1/n)", limit)
+}
+
+fn limit_against_one(test: SeriesTest, name: &str, limit: f64) -> TestResult {
+    if limit.is_nan() || (limit - 1.0).abs() < 1e-4 {
+        TestResult::new(test, Convergence::Inconclusive, format!("{} = {} so the test says nothing", name, limit))
+    } else if limit < 1.0 {
+        TestResult::new(test, Convergence::Converges, format!("{} = {} < 1", name, limit))
+    } else {
+        TestResult::new(test, Convergence::Diverges, format!("{} = {} > 1", name, limit))
+    }
+}
+
+pub fn alternating_series_test(bank: &Workspace, series: &Expression) -> TestResult {
+    let (index, _, _, summand) = parts(series);
+    let terms: Vec<f64> = (0..200).map(|k| term(bank, summand, index, EVENTUALLY + k as f64)).collect();
+
+    let alternates = terms.windows(2).all(|w| w[0] * w[1] < 0.0);
+    if !alternates {
+        return TestResult::new(SeriesTest::AlternatingSeries, Convergence::Inconclusive,
+            String::from("the terms do not alternate in sign"));
+    }
+    let decreasing = terms.windows(2).all(|w| w[1].abs() <= w[0].abs());
+    let size = |n: f64| term(bank, summand, index, n).abs();
+    let limit = settled_limit(size);
+    // 1/ln(n) creeps to 0 too slowly to see, along n = 2^k it's 1/(k ln 2)
+    let limit = if limit.abs() < 1e-6 { limit } else { settled_limit(|k| size(2f64.powf(k))) };
+
+    if limit.abs() < 1e-6 && decreasing {
+        TestResult::new(SeriesTest::AlternatingSeries, Convergence::Converges,
+            String::from("alternating with |a_n| decreasing to 0"))
+    } else if limit.is_nan() || limit.abs() >= 1e-6 {
+        // it can only ever show convergence, the nth term test is the one for divergence
+        TestResult::new(SeriesTest::AlternatingSeries, Convergence::Inconclusive,
+            String::from("alternating but |a_n| isn't seen to go to 0 so the test says nothing"))
+    } else {
+        TestResult::new(SeriesTest::AlternatingSeries, Convergence::Inconclusive,
+            String::from("|a_n| goes to 0 but is not decreasing"))
+    }
+}
+
+// geometric and p-series are the comparisons whose behaviour is known outright
+fn known_verdict(bank: &Workspace, series: &Expression) -> Convergence {
+    let geometric = geometric_test(bank, series);
+    if geometric.verdict != Convergence::Inconclusive {
+        return geometric.verdict;
+    }
+    p_series_test(bank, series).verdict
+}
+
+fn describe(verdict: Convergence) -> &'static str {
+    match verdict {
+        Convergence::Converges => "converges",
+        Convergence::Diverges => "diverges",
+        Convergence::Inconclusive => "is unknown",
+    }
+}
+
+// lim a_n from sequence_limit, checked since the extrapolation puts a number on anything,
+// sin(n) included. The terms further out have to sit by it and the same extrapolation a few
+// terms along has to agree, NaN when they don't
+fn settled_limit<F: Fn(f64) -> f64>(a: F) -> f64 {
+    let limit = numeric::sequence_limit(&a);
+    let along = numeric::sequence_limit(|n| a(n + 7.0));
+    let agree = if limit.is_infinite() { along == limit } else { (along - limit).abs() < 1e-4 * limit.abs().max(1.0) };
+    // the furthest stretches that haven't overflowed
+    let stretches: Vec<Vec<f64>> = [100.0, 300.0, 1000.0, 3000.0].iter()
+        .map(|start| (0..8).map(|k| a(start + k as f64)).collect::<Vec<f64>>())
+        .filter(|stretch| stretch.iter().all(|t| t.is_finite()))
+        .collect();
+    let settles = match stretches.as_slice() {
+        [.., near, far] if limit.is_infinite() => {
+            let smallest = far.iter().fold(f64::INFINITY, |m, t| m.min(t.abs()));
+            far.iter().all(|t| t.signum() == limit.signum()) && near.iter().all(|t| t.abs() < smallest)
+        },
+        [.., far] => far.iter().all(|t| (t - limit).abs() < 1e-2 * limit.abs().max(1.0)),
+        [] => false,
+    };
+    if agree && settles { limit } else { f64::NAN }
+}
+
+/// The 1/n^p that a_n behaves like, from how a_n scales when n doubles
+fn comparable_p_series(bank: &Workspace, series: &Expression) -> Option<Expression> {
+    let (index, lower, upper, summand) = parts(series);
+    let at = |n: f64| term(bank, summand, index, n).abs();
+    let scale = |n: f64| -(at(2.0 * n) / at(n)).log2();
+    // the furthest doublings that haven't overflowed, 3^n/(n3^n) does by n = 1024
+    let (p1, p2) = [256.0, 128.0, 64.0].iter().map(|n| (scale(*n), scale(2.0 * n))).find(|(_, p2)| p2.is_finite())?;
+    if (p1 - p2).abs() > 0.01 {
+        return None;
+    }
+    // round to the nearest half, 1/sqrt(n) style comparisons are common
+    // + 0 so a p of -0 prints as 0
+    let p = (p2 * 2.0).round() / 2.0 + 0.0;
+    Some(Expression::from(&format!("sum({},{},{},(1/(({})^({}))))", index, lower, upper, index, p)))
+}
+
+/// Direct comparison of |a_n| with c/n^p for the p it scales like. With p > 1 the series
+/// converges absolutely, which settles ones like sin(n)/n^2 whose terms change sign
+pub fn absolute_comparison_test(bank: &Workspace, series: &Expression) -> TestResult {
+    let (index, _, _, summand) = parts(series);
+    let size = |n: f64| term(bank, summand, index, n).abs();
+    // the biggest of a run of terms, so sin(n)/n^2 scales like 1/n^2 even where sin(n) is small
+    let run = |n: f64| (0..32).map(|k| size(n + k as f64)).fold(0.0, f64::max);
+    let p = (-(run(4096.0) / run(256.0)).log2() / 4.0 * 2.0).round() / 2.0;
+    if !(p.is_finite() && p > 1.0) {
+        return TestResult::new(SeriesTest::Comparison, Convergence::Inconclusive,
+            String::from("|a_n| isn't bounded by a convergent p-series"));
+    }
+    let most = |from: usize, to: usize| (from..to).map(|n| size(n as f64) * (n as f64).powf(p)).fold(0.0, f64::max);
+    let (near, far) = (most(EVENTUALLY as usize, 500), most(500, 2000));
+    // |a_n| n^p staying put is what being bounded by c/n^p looks like
+    if far.is_finite() && far <= 1.1 * near {
+        TestResult::new(SeriesTest::Comparison, Convergence::Converges,
+            format!("|a_n| <= {}/n^{} which converges, so the series converges absolutely", applications::bound(near.max(far)), p))
+    } else {
+        TestResult::new(SeriesTest::Comparison, Convergence::Inconclusive,
+            format!("|a_n| n^{} keeps growing so comparing with 1/n^{} says nothing", p, p))
+    }
+}
+
+/// Runs every test that applies, in the order a student would reach for them
+pub fn convergence_tests(bank: &Workspace, series: &Expression) -> Vec<TestResult> {
+    let mut results = vec![
+        nth_term_test(bank, series),
+        geometric_test(bank, series),
+        p_series_test(bank, series),
+        alternating_series_test(bank, series),
+        ratio_test(bank, series),
+        root_test(bank, series),
+        integral_test(bank, series),
+    ];
+    if let Some(other) = comparable_p_series(bank, series) {
+        results.push(limit_comparison_test(bank, series, &other));
+    }
+    results.push(absolute_comparison_test(bank, series));
+    results
+}
+
+/// The verdict of the first test that is not inconclusive
+pub fn converges(bank: &Workspace, series: &Expression) -> Convergence {
+    convergence_tests(bank, series)
+        .iter()
+        .map(|result| result.verdict)
+        .find(|verdict| *verdict != Convergence::Inconclusive)
+        .unwrap_or(Convergence::Inconclusive)
+}
+
+/// Radius and interval of convergence for a power series in x. The center comes from the
+/// (x-a) being raised to a power of the index, the radius from the ratio test there and
+/// each endpoint is run back through the convergence tests. None when there's no (x-a)
+/// raised to a power of the index
+pub fn interval_of_convergence(bank: &Workspace, series: &Expression) -> Option<IntervalOfConvergence> {
+    let (index, lower, upper, summand) = parts(series);
+    let (base, exponent) = power_of_x(summand, index)?;
+
+    let u = |x: f64| base.approximate_with(bank, &[('x', x)]);
+    let slope = u(1.0) - u(0.0);
+    let center = -u(0.0) / slope + 0.0;
+    let k = linear_slope(bank, exponent, index).unwrap_or(1.0);
+
+    // where |x-a| is 1 the ratio test only sees the coefficients
+    let unit = center + 1.0 / slope;
+    let coefficient_ratio = numeric::sequence_limit(|n| {
+        let t = |n: f64| summand.approximate_with(bank, &[(index, n), ('x', unit)]);
+        (t(n + 1.0) / t(n)).abs()
+    });
+    // the acceleration can land a hair either side of 0 for things like 1/n^n
+    let radius = if coefficient_ratio < 1e-4 {
+        f64::INFINITY
+    } else {
+        coefficient_ratio.powf(-1.0 / k) / slope.abs()
+    };
+    // the ratio is only good to a few digits, snap to the fraction it is clearly heading for
+    let radius = numeric::nice_fraction(radius, 1e-4).map_or(radius, |r| r.to_f64().unwrap());
+    let lower_bound = lower.approximate(bank, 0.0);
+
+    if radius == 0.0 || !radius.is_finite() {
+        return Some(IntervalOfConvergence { center, radius, left: None, right: None });
+    }
+    let endpoint = |x: f64| {
+        let x = numeric::nice_fraction(x, 1e-9).unwrap_or_else(|| Fraction::from(x));
+        let at_x = substitute(summand, 'x', &Expression::Constant(x));
+        let endpoint_series = Expression::from(&format!("sum({},{},{},({}))", index, lower_bound, upper, at_x));
+        converges(bank, &endpoint_series)
+    };
+    Some(IntervalOfConvergence {
+        center,
+        radius,
+        left: Some(endpoint(center - radius)),
+        right: Some(endpoint(center + radius)),
+    })
+}
+
+// the (base)^(exponent) in a summand where base has x in it and exponent has the index
+fn power_of_x(e: &Expression, index: char) -> Option<(&Expression, &Expression)> {
+    if let Expression::Equa(equation) = e {
+        if equation.operation == Operation::Exp && contains(&equation.element1, 'x') && contains(&equation.element2, index) {
+            return Some((&equation.element1, &equation.element2));
+        }
+        return power_of_x(&equation.element1, index).or_else(|| power_of_x(&equation.element2, index));
+    }
+    None
+}
+
+impl fmt::Display for Convergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", describe(*self))
+    }
+}
+
+impl fmt::Display for IntervalOfConvergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.radius == 0.0 {
+            return write!(f, "x = {}", self.center);
+        }
+        if !self.radius.is_finite() {
+            return write!(f, "(-inf, inf)");
+        }
+        let left = if self.left == Some(Convergence::Converges) { '[' } else { '(' };
+        let right = if self.right == Some(Convergence::Converges) { ']' } else { ')' };
+        write!(f, "{}{}, {}{}", left, self.center - self.radius, self.center + self.radius, right)
+    }
+}
@@ -0,0 +1,258 @@
+use super::*;
+use serde_json::{json, Value as Json};
+use session::{Error, Session};
+
+// the error codes JSON-RPC 2.0 sets aside, and one of the server defined ones for maths that
+// didn't work out
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const CALCULATION_ERROR: i64 = -32000;
+
+// how many points plot_samples gives when samples is left out, and the most it will give
+const SAMPLES: usize = 101;
+const MAX_SAMPLES: usize = 10000;
+
+/// A JSON-RPC 2.0 error
+#[derive(Debug)]
+pub struct Fault {
+    pub code: i64,
+    pub message: String,
+    pub data: Option<Json>,
+}
+
+impl Fault {
+    fn new(code: i64, message: &str) -> Fault {
+        Fault { code, message: String::from(message), data: None }
+    }
+
+    // a calculator Error about one of the parameters, its column counts into that parameter
+    fn calculation(param: &str, error: Error) -> Fault {
+        let mut data = error.json();
+        data["param"] = json!(param);
+        Fault { code: CALCULATION_ERROR, message: error.message, data: Some(data) }
+    }
+
+    pub fn json(&self) -> Json {
+        match &self.data {
+            Some(data) => json!({"code": self.code, "message": self.message, "data": data}),
+            None => json!({"code": self.code, "message": self.message}),
+        }
+    }
+}
+
+/// JSON-RPC 2.0 over the calculator, what ap_calc serve --stdio runs. Each session named in
+/// the params has a Session of its own, so its own bank, and leaving the name out means
+/// "default". The methods are
+///
+/// define {definition} -> {display}
+/// evaluate {expression, x?} -> {expression, value}
+/// differentiate {expression, variable?} -> {expression}
+/// integrate {expression, from?, to?} -> {value, exact} or {expression} without bounds
+/// simplify {expression} -> {expression}
+/// render_latex {expression} -> {latex}
+/// plot_samples {expression, from, to, samples?} -> {points}
+/// close {} -> {closed}
+pub struct Server {
+    sessions: HashMap<String, Session>,
+}
+
+impl Default for Server {
+    fn default() -> Server {
+        Server::new()
+    }
+}
+
+impl Server {
+    pub fn new() -> Server {
+        Server { sessions: HashMap::new() }
+    }
+
+    /// Answers a line holding a request or a batch of them, None when there's nothing to
+    /// answer because they were all notifications
+    pub fn handle(&mut self, line: &str) -> Option<String> {
+        let response = match serde_json::from_str::<Json>(line) {
+            Err(problem) => Some(reply(Json::Null, Err(Fault::new(PARSE_ERROR, &problem.to_string())))),
+            Ok(Json::Array(requests)) if requests.is_empty() => Some(reply(Json::Null, Err(Fault::new(INVALID_REQUEST, "an empty batch")))),
+            Ok(Json::Array(requests)) => {
+                let responses: Vec<Json> = requests.iter().filter_map(|request| self.respond(request)).collect();
+                if responses.is_empty() { None } else { Some(Json::Array(responses)) }
+            },
+            Ok(request) => self.respond(&request),
+        };
+        response.map(|response| format!("{}", response))
+    }
+
+    fn respond(&mut self, request: &Json) -> Option<Json> {
+        let id = request.get("id").cloned();
+        let method = request.get("method").and_then(Json::as_str);
+        let params = request.get("params").cloned().unwrap_or(json!({}));
+        if request.get("jsonrpc").and_then(Json::as_str) != Some("2.0") || method.is_none() || !params.is_object() {
+            return Some(reply(id.unwrap_or(Json::Null), Err(Fault::new(INVALID_REQUEST, "a request is an object with jsonrpc \"2.0\", a method and params by name"))));
+        }
+        let result = self.call(method.unwrap_or_default(), &params);
+        // a notification has no id and gets no answer
+        id.map(|id| reply(id, result))
+    }
+
+    fn call(&mut self, method: &str, params: &Json) -> Result<Json, Fault> {
+        let name = match params.get("session") {
+            None => "default",
+            Some(Json::String(name)) => name,
+            Some(_) => return Err(Fault::new(INVALID_PARAMS, "session is a string")),
+        };
+        if method == "close" {
+            return Ok(json!({"closed": self.sessions.remove(name).is_some()}));
+        }
+        let session = self.sessions.entry(String::from(name)).or_default();
+
+        match method {
+            "define" => {
+                let display = session.define(text(params, "definition")?).map_err(|error| Fault::calculation("definition", error))?;
+                Ok(json!({"display": display}))
+            },
+            "evaluate" => {
+                let e = expression(session, params)?;
+                let x = match params.get("x") {
+                    None => None,
+                    Some(x) => Some(constant(x).ok_or_else(|| Fault::new(INVALID_PARAMS, "x is a number that fits in a fraction"))?),
+                };
+                let (e, value) = attempt(session, "expression", |session| {
+                    let e = match &x {
+                        Some(x) => session.resolve(&substitute(&e, 'x', x)),
+                        None => session.resolve(&e),
+                    };
+                    let value = if contains(&e, 'x') { Json::Null } else { decimal(e.approximate(&session.bank, 0.0)) };
+                    (e.pretty(), value)
+                })?;
+                Ok(json!({"expression": e, "value": value}))
+            },
+            "differentiate" => {
+                let variable = match params.get("variable").map(|v| v.as_str().map(|v| v.chars().collect::<Vec<char>>())) {
+                    None => 'x',
+                    Some(Some(v)) if v.len() == 1 && v[0].is_ascii_alphabetic() => v[0],
+                    _ => return Err(Fault::new(INVALID_PARAMS, "variable is a single letter")),
+                };
+                let e = symbolic(session, params)?;
+                let slope = attempt(session, "expression", |session| {
+                    let e = substitute(&e, variable, &Expression::Variable('x'));
+                    let slope = derivative::derivative(&session.bank, &session.resolve(&e));
+                    substitute(&slope, 'x', &Expression::Variable(variable)).pretty()
+                })?;
+                Ok(json!({"expression": slope}))
+            },
+            "integrate" => {
+                let e = expression(session, params)?;
+                match (number(session, params, "from")?, number(session, params, "to")?) {
+                    (Some(a), Some(b)) => {
+                        let (value, exact) = attempt(session, "expression", |session| {
+                            let definite = integral::integrate(&session.bank, &e, &a, &b);
+                            (definite.value, definite.exact.map(|exact| exact.pretty()))
+                        })?;
+                        Ok(json!({"value": decimal(value), "exact": exact}))
+                    },
+                    (None, None) => {
+                        let antiderivative = attempt(session, "expression", |session| {
+                            integral::antiderivative(&session.resolve(&e), 'x').map(|antiderivative| simplify::simplify(&antiderivative).pretty())
+                        })?;
+                        match antiderivative {
+                            Some(antiderivative) => Ok(json!({"expression": antiderivative})),
+                            None => Err(Fault::new(CALCULATION_ERROR, "no antiderivative was found, give from and to for a number")),
+                        }
+                    },
+                    _ => Err(Fault::new(INVALID_PARAMS, "from and to go together")),
+                }
+            },
+            "simplify" => {
+                let e = symbolic(session, params)?;
+                let simple = attempt(session, "expression", |session| session.resolve(&e).pretty())?;
+                Ok(json!({"expression": simple}))
+            },
+            "render_latex" => {
+                let e = symbolic(session, params)?;
+                let latex = attempt(session, "expression", |_| e.to_latex())?;
+                Ok(json!({"latex": latex}))
+            },
+            "plot_samples" => {
+                let e = expression(session, params)?;
+                let (a, b) = match (number(session, params, "from")?, number(session, params, "to")?) {
+                    (Some(a), Some(b)) => attempt(session, "from", |session| (a.approximate(&session.bank, 0.0), b.approximate(&session.bank, 0.0)))?,
+                    _ => return Err(Fault::new(INVALID_PARAMS, "plot_samples needs from and to")),
+                };
+                let samples = match params.get("samples") {
+                    None => SAMPLES,
+                    Some(n) => match n.as_u64() {
+                        Some(n) if (2..=MAX_SAMPLES as u64).contains(&n) => n as usize,
+                        _ => return Err(Fault::new(INVALID_PARAMS, &format!("samples is a whole number from 2 to {}", MAX_SAMPLES))),
+                    },
+                };
+                if !(a.is_finite() && b.is_finite() && a < b) {
+                    return Err(Fault::new(INVALID_PARAMS, "from and to are finite with from below to"));
+                }
+                let points: Vec<Json> = attempt(session, "expression", |session| {
+                    let e = session.resolve(&e);
+                    (0..samples).map(|i| {
+                        let x = a + (b - a) * i as f64 / (samples - 1) as f64;
+                        json!([decimal(x), decimal(e.approximate(&session.bank, x))])
+                    }).collect()
+                })?;
+                Ok(json!({"points": points}))
+            },
+            _ => Err(Fault::new(METHOD_NOT_FOUND, &format!("there's no method called {}", method))),
+        }
+    }
+}
+
+fn reply(id: Json, result: Result<Json, Fault>) -> Json {
+    match result {
+        Ok(result) => json!({"jsonrpc": "2.0", "result": result, "id": id}),
+        Err(fault) => json!({"jsonrpc": "2.0", "error": fault.json(), "id": id}),
+    }
+}
+
+fn text<'a>(params: &'a Json, key: &str) -> Result<&'a str, Fault> {
+    params.get(key).and_then(Json::as_str).ok_or_else(|| Fault::new(INVALID_PARAMS, &format!("{} is missing or isn't a string", key)))
+}
+
+fn expression(session: &Session, params: &Json) -> Result<Expression, Fault> {
+    session.parse(text(params, "expression")?).map_err(|error| Fault::calculation("expression", error))
+}
+
+// an expression that is only worked with, so its letters don't need values
+fn symbolic(session: &Session, params: &Json) -> Result<Expression, Fault> {
+    session.parse_symbolic(text(params, "expression")?).map_err(|error| Fault::calculation("expression", error))
+}
+
+fn attempt<T, F: FnOnce(&Session) -> T>(session: &Session, param: &str, work: F) -> Result<T, Fault> {
+    session.attempt(work).map_err(|error| Fault::calculation(param, error))
+}
+
+// a bound given as a number or as an expression like "pi/2"
+fn number(session: &Session, params: &Json, key: &str) -> Result<Option<Expression>, Fault> {
+    match params.get(key) {
+        None => Ok(None),
+        Some(x @ Json::Number(_)) => Ok(Some(constant(x).ok_or_else(|| Fault::new(INVALID_PARAMS, &format!("{} is too big for a fraction", key)))?)),
+        Some(Json::String(text)) => {
+            let e = session.parse(text).map_err(|error| Fault::calculation(key, error))?;
+            let e = attempt(session, key, |session| session.resolve(&e))?;
+            if contains(&e, 'x') {
+                return Err(Fault::new(INVALID_PARAMS, &format!("{} can't depend on x", key)));
+            }
+            Ok(Some(e))
+        },
+        Some(_) => Err(Fault::new(INVALID_PARAMS, &format!("{} is a number or an expression", key))),
+    }
+}
+
+// a JSON number as the Constant it is, None when it isn't a number or a Fraction can't hold it
+fn constant(x: &Json) -> Option<Expression> {
+    let x = Fraction::from(x.as_f64()?);
+    if x.is_nan() { None } else { Some(Expression::Constant(x)) }
+}
+
+// whole numbers without a .0 on the end, and null for NaN and the infinities which JSON has
+// no numbers for
+fn decimal(x: f64) -> Json {
+    if x.fract() == 0.0 && x.abs() < 1e15 { json!(x as i64) } else { json!(x) }
+}
@@ -0,0 +1,563 @@
+use super::*;
+#[cfg(feature = "cli")]
+use serde_json::{json, Value as Json};
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+const HELP: &str = "\
+f(x) = 3x^2+1        define a function, any one letter works for the variable
+a = 5                define a variable
+f(2), f'(x), a+1     work something out, exactly when it can be
+d/dx sin(x)^2        differentiate
+integrate f 0 1      definite integral
+table f 0 10 1       values from 0 to 10 in steps of 1
+limit f 0 [left|right]
+roots f -5 5         zeros on [-5, 5]
+extrema f -5 5       local and absolute extrema on [-5, 5]
+save lesson.txt      write the definitions to a file
+load lesson.txt      replace them with the ones in a file";
+
+// the most rows table will print
+const MAX_ROWS: usize = 1000;
+
+thread_local! {
+    // whether this thread is inside attempt, whose panics come back as an Error
+    static ATTEMPTING: Cell<bool> = const { Cell::new(false) };
+}
+
+static QUIET: Once = Once::new();
+
+/// Stops the panic hook printing the panics Session::attempt catches, the rest are printed as
+/// before. The hook is the whole process's so this is for programs like ap_calc, not libraries
+pub fn quiet_panics() {
+    QUIET.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !ATTEMPTING.with(Cell::get) {
+                hook(info);
+            }
+        }));
+    });
+}
+
+/// What went wrong with a line, column is the character the trouble starts at
+#[derive(Debug)]
+pub struct Error {
+    pub message: String,
+    pub column: Option<usize>,
+}
+
+impl Error {
+    fn at(column: usize, message: &str) -> Error {
+        Error { message: String::from(message), column: Some(column) }
+    }
+
+    fn new(message: &str) -> Error {
+        Error { message: String::from(message), column: None }
+    }
+
+    /// The message behind a ^ under the offending character, indent is how far in the line
+    /// was printed
+    pub fn pointer(&self, indent: usize) -> String {
+        match self.column {
+            Some(column) => format!("{}^ {}", " ".repeat(indent + column), self.message),
+            None => self.message.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.column {
+            Some(column) => write!(f, "column {}: {}", column + 1, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+#[cfg(feature = "cli")]
+impl Error {
+    /// {"message": ..., "column": ...} with the column counted from 1, null when there isn't one
+    pub fn json(&self) -> Json {
+        json!({"message": self.message, "column": self.column.map(|column| column + 1)})
+    }
+}
+
+/// A line of a script and what came of it, line counts from 1
+#[derive(Debug)]
+pub struct Step {
+    pub line: usize,
+    pub input: String,
+    pub result: Result<String, Error>,
+}
+
+impl Step {
+    /// The input after a prompt and the output under it, a ^ under the input for an error
+    pub fn text(&self) -> String {
+        let output = match &self.result {
+            Ok(output) => output.clone(),
+            Err(error) => Error { message: format!("error on line {}: {}", self.line, error.message), column: error.column }.pointer(2),
+        };
+        if output.is_empty() { format!("> {}", self.input) } else { format!("> {}\n{}", self.input, output) }
+    }
+
+    #[cfg(feature = "cli")]
+    pub fn json(&self) -> Json {
+        match &self.result {
+            Ok(output) => json!({"line": self.line, "input": self.input, "output": output}),
+            Err(error) => json!({"line": self.line, "input": self.input, "error": error.json()}),
+        }
+    }
+}
+
+/// A calculator session, the letters defined so far and the commands that use them
+pub struct Session {
+    pub bank: Workspace,
+}
+
+impl Default for Session {
+    fn default() -> Session {
+        Session::new()
+    }
+}
+
+impl Session {
+    pub fn new() -> Session {
+        Session { bank: Workspace::new() }
+    }
+
+    /// Runs one line, a definition like f(x) = 3x^2+1, a command like integrate f 0 1 or
+    /// something to work out like f(2), and gives back what to print
+    pub fn run(&mut self, line: &str) -> Result<String, Error> {
+        let words = words(line);
+        let Some(&(_, first)) = words.first() else { return Ok(String::new()) };
+        if first.starts_with('#') {
+            return Ok(String::new());
+        }
+        match first {
+            "help" => Ok(String::from(HELP)),
+            "integrate" | "table" | "limit" | "roots" | "extrema" => self.command(line, &words),
+            "save" | "load" => self.file(line, &words),
+            _ if first.starts_with("d/d") => self.differentiate(line, &words),
+            _ if definition(line).is_some() => self.define(line),
+            _ => self.work_out(line, 0),
+        }
+    }
+
+    /// Runs a script a line at a time, blank lines and # comments are left out. A line that
+    /// fails doesn't stop the ones after it
+    pub fn script(&mut self, text: &str) -> Vec<Step> {
+        text.lines().enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+            .map(|(i, line)| Step { line: i + 1, input: String::from(line.trim_end()), result: self.run(line) })
+            .collect()
+    }
+
+    /// Defines a function like f(x) = 3x^2+1 or a variable like a = 5, a variable's value is
+    /// worked out then and there
+    pub fn define(&mut self, line: &str) -> Result<String, Error> {
+        let Some(equals) = definition(line) else { return Err(Error::at(0, "a definition needs an =")) };
+        let left: Vec<(usize, char)> = line.chars().enumerate().take(equals).filter(|(_, c)| !c.is_whitespace()).collect();
+        let body = &line[byte(line, equals + 1)..];
+        let name = match left.first() {
+            Some(&(column, name)) if !name.is_ascii_alphabetic() => return Err(Error::at(column, "names are a single letter")),
+            Some(&(_, name)) => name,
+            None => return Err(Error::at(equals, "nothing to define")),
+        };
+        if name == 'x' || name == 'e' {
+            return Err(Error::at(left[0].0, &format!("{} can't be redefined", name)));
+        }
+        if body.trim().is_empty() {
+            return Err(Error::at(equals, "nothing after ="));
+        }
+        match left.as_slice() {
+            [_] => {
+                self.check(body, equals + 1, Some(&[]))?;
+                let value = self.read(body, equals + 1)?;
+                let value = self.attempt(|session| session.resolve(&value))?;
+                let display = format!("{} = {}", name, value.pretty());
+                self.bank.define_variable(name, value).map_err(|error| Error::at(equals + 1, &error.message))?;
+                Ok(display)
+            },
+            [_, (_, '('), (_, variable), (_, ')')] if variable.is_ascii_alphabetic() => {
+                let variable = *variable;
+                self.check(body, equals + 1, Some(&[variable]))?;
+                let rule = self.read(body, equals + 1)?;
+                let in_x = substitute(&rule, variable, &Expression::Variable('x'));
+                self.bank.define_function(name, in_x).map_err(|error| Error::at(equals + 1, &error.message))?;
+                Ok(format!("{}({}) = {}", name, variable, rule.pretty()))
+            },
+            _ => Err(Error::at(left[1].0, "write a definition as f(x) = ... or a = ...")),
+        }
+    }
+
+    fn file(&mut self, line: &str, words: &[(usize, &str)]) -> Result<String, Error> {
+        let (start, command) = words[0];
+        let Some(&(column, _)) = words.get(1) else { return Err(Error::at(start, &format!("usage: {} FILE", command))) };
+        let path = line[byte(line, column)..].trim();
+        if command == "save" {
+            self.bank.save(path).map_err(|error| Error::at(column, &format!("can't write {}: {}", path, error)))?;
+            return Ok(format!("saved {} definitions", self.bank.len()));
+        }
+        match Workspace::load(path) {
+            Ok(bank) => {
+                self.bank = bank;
+                Ok(format!("loaded {} definitions", self.bank.len()))
+            },
+            Err(errors) => {
+                let errors: Vec<String> = errors.iter().map(|error| format!("{}", error)).collect();
+                Err(Error::new(&errors.join("\n")))
+            },
+        }
+    }
+
+    fn differentiate(&self, line: &str, words: &[(usize, &str)]) -> Result<String, Error> {
+        let (start, first) = words[0];
+        let mut variable = first.chars().skip(3);
+        let variable = match (variable.next(), variable.next()) {
+            (Some(v), None) if v.is_ascii_alphabetic() => v,
+            _ => return Err(Error::at(start, "write a derivative as d/dx ...")),
+        };
+        let Some(&(column, _)) = words.get(1) else { return Err(Error::at(start, "nothing to differentiate")) };
+        let text = &line[byte(line, column)..];
+        self.check(text, column, Some(&[variable]))?;
+        let e = self.read(text, column)?;
+        self.attempt(|session| {
+            let e = substitute(&e, variable, &Expression::Variable('x'));
+            let slope = derivative::derivative(&session.bank, &session.resolve(&e));
+            substitute(&slope, 'x', &Expression::Variable(variable)).pretty()
+        })
+    }
+
+    // f(2) comes out 13, f'(x) comes out 6x and sin(1) as itself and its decimal
+    fn work_out(&self, line: &str, column: usize) -> Result<String, Error> {
+        let text = &line[byte(line, column)..];
+        self.check(text, column, Some(&['x']))?;
+        let e = self.read(text, column)?;
+        self.attempt(|session| {
+            let e = session.resolve(&e);
+            match e {
+                _ if contains(&e, 'x') => e.pretty(),
+                Expression::Constant(_) => e.pretty(),
+                _ => format!("{} ≈ {}", e.pretty(), applications::bound(e.approximate(&session.bank, 0.0))),
+            }
+        })
+    }
+
+    fn command(&self, line: &str, words: &[(usize, &str)]) -> Result<String, Error> {
+        let (start, name) = words[0];
+        // the numbers come last so the function can be a whole expression with spaces in it
+        let (numbers, usage) = match name {
+            "integrate" => (2, "integrate f a b"),
+            "table" => (3, "table f start stop step"),
+            "limit" if words.last().is_some_and(|(_, w)| *w == "left" || *w == "right") => (2, "limit f a left"),
+            "limit" => (1, "limit f a"),
+            "roots" => (2, "roots f a b"),
+            _ => (2, "extrema f a b"),
+        };
+        if words.len() < numbers + 2 {
+            return Err(Error::at(start, &format!("usage: {}", usage)));
+        }
+        let (function, arguments) = words[1..].split_at(words.len() - 1 - numbers);
+        let end = arguments[0].0;
+        let f = self.expression(&line[byte(line, function[0].0)..byte(line, end)], function[0].0, Some(&['x']))?;
+        let number = |i: usize| self.number(arguments[i].1, arguments[i].0);
+
+        match name {
+            "integrate" => {
+                let (a, b) = (self.bound(arguments[0].1, arguments[0].0)?, self.bound(arguments[1].1, arguments[1].0)?);
+                self.attempt(|session| {
+                    let definite = integral::integrate(&session.bank, &f, &a, &b);
+                    match definite.exact {
+                        Some(exact) => exact.pretty(),
+                        None => format!("{}", applications::bound(definite.value)),
+                    }
+                })
+            },
+            "table" => {
+                let (from, to, step) = (number(0)?, number(1)?, number(2)?);
+                if step <= 0.0 {
+                    return Err(Error::at(arguments[2].0, "the step has to be positive"));
+                }
+                if (to - from) / step > MAX_ROWS as f64 {
+                    return Err(Error::at(arguments[2].0, &format!("more than {} rows, take a bigger step", MAX_ROWS)));
+                }
+                self.attempt(|session| {
+                    let f = session.resolve(&f);
+                    let rows: Vec<(String, String)> = (0..).map(|i| from + step * i as f64)
+                        .take_while(|x| *x <= to + step * 1e-9)
+                        .map(|x| (format!("{}", applications::bound(x)), show(f.approximate(&session.bank, x))))
+                        .collect();
+                    let width = rows.iter().map(|(x, _)| x.chars().count()).max().unwrap_or(1).max(1);
+                    let lines: Vec<String> = [(String::from("x"), String::from("y"))].into_iter().chain(rows)
+                        .map(|(x, y)| format!("{:>width$} | {}", x, y))
+                        .collect();
+                    lines.join("\n")
+                })
+            },
+            "limit" => {
+                let a = number(0)?;
+                let side = match arguments.get(1).map(|(_, w)| *w) {
+                    Some("left") => limits::Side::Left,
+                    Some("right") => limits::Side::Right,
+                    _ => limits::Side::Both,
+                };
+                self.attempt(|session| show(limits::limit(&session.bank, &f, a, side)))
+            },
+            "roots" => {
+                let (a, b) = self.interval(arguments)?;
+                self.attempt(|session| {
+                    let f = session.resolve(&f);
+                    let roots: Vec<String> = numeric::roots(|x| f.approximate(&session.bank, x), a, b).into_iter()
+                        .map(|x| format!("{}", applications::bound(x)))
+                        .collect();
+                    if roots.is_empty() { format!("no roots on [{}, {}]", a, b) } else { roots.join(", ") }
+                })
+            },
+            _ => {
+                let (a, b) = self.interval(arguments)?;
+                self.attempt(|session| session.extrema(&f, a, b))
+            },
+        }
+    }
+
+    // critical points are where f' is 0, each one is a max or min when f' changes sign there,
+    // and the absolute ones are the largest and smallest of those and the endpoints
+    fn extrema(&self, f: &Expression, a: f64, b: f64) -> String {
+        let f = self.resolve(f);
+        let slope = derivative::derivative(&self.bank, &f);
+        let value = |x: f64| f.approximate(&self.bank, x);
+        let h = (b - a) * 1e-6;
+        let mut lines: Vec<String> = Vec::new();
+        let mut candidates = vec![a, b];
+
+        for c in numeric::roots(|x| slope.approximate(&self.bank, x), a, b) {
+            let (left, right) = (slope.approximate(&self.bank, c - h), slope.approximate(&self.bank, c + h));
+            let kind = match (left > 0.0, right > 0.0) {
+                _ if c - h < a || c + h > b => "endpoint",
+                (true, false) => "local max",
+                (false, true) => "local min",
+                _ => "no extremum",
+            };
+            lines.push(format!("{} {} at x = {}", kind, show(value(c)), applications::bound(c)));
+            candidates.push(c);
+        }
+        let candidates: Vec<(f64, f64)> = candidates.into_iter().map(|x| (x, value(x))).filter(|(_, y)| y.is_finite()).collect();
+        let highest = candidates.iter().copied().fold(None, |best: Option<(f64, f64)>, p| if best.is_some_and(|b| b.1 >= p.1) { best } else { Some(p) });
+        let lowest = candidates.iter().copied().fold(None, |best: Option<(f64, f64)>, p| if best.is_some_and(|b| b.1 <= p.1) { best } else { Some(p) });
+        if let (Some(high), Some(low)) = (highest, lowest) {
+            lines.push(format!("absolute max {} at x = {}", show(high.1), applications::bound(high.0)));
+            lines.push(format!("absolute min {} at x = {}", show(low.1), applications::bound(low.0)));
+        }
+        lines.retain(|line| !line.starts_with("endpoint"));
+        lines.join("\n")
+    }
+
+    /// text as an Expression in x, mistakes come back pointed at like they are for run. A
+    /// bank function's name on its own stands for the function
+    pub fn parse(&self, text: &str) -> Result<Expression, Error> {
+        self.expression(text, 0, Some(&['x']))
+    }
+
+    /// parse with any letter allowed, for working with the expression rather than its value
+    pub fn parse_symbolic(&self, text: &str) -> Result<Expression, Error> {
+        self.expression(text, 0, None)
+    }
+
+    fn expression(&self, text: &str, column: usize, free: Option<&[char]>) -> Result<Expression, Error> {
+        let name: Vec<char> = text.trim().chars().collect();
+        if let [name] = name.as_slice() {
+            if is_function(&self.bank, &Expression::Variable(*name)) {
+                return Ok(Expression::from(&format!("{}(x)", name)));
+            }
+        }
+        self.check(text, column, free)?;
+        self.read(text, column)
+    }
+
+    // text, which starts at column in the line, read with the parser's errors moved along to
+    // match
+    fn read(&self, text: &str, column: usize) -> Result<Expression, Error> {
+        Expression::parse(text).map_err(|error| Error::at(column + error.column, &error.message))
+    }
+
+    fn bound(&self, word: &str, column: usize) -> Result<Expression, Error> {
+        self.check(word, column, Some(&[]))?;
+        let e = self.read(word, column)?;
+        self.attempt(|session| session.resolve(&e))
+    }
+
+    fn number(&self, word: &str, column: usize) -> Result<f64, Error> {
+        match word {
+            "inf" | "∞" => return Ok(f64::INFINITY),
+            "-inf" | "-∞" => return Ok(f64::NEG_INFINITY),
+            _ => {},
+        }
+        let e = self.bound(word, column)?;
+        let x = self.attempt(|session| e.approximate(&session.bank, 0.0))?;
+        if x.is_nan() { Err(Error::at(column, "not a number")) } else { Ok(x) }
+    }
+
+    fn interval(&self, arguments: &[(usize, &str)]) -> Result<(f64, f64), Error> {
+        let (a, b) = (self.number(arguments[0].1, arguments[0].0)?, self.number(arguments[1].1, arguments[1].0)?);
+        if a.is_infinite() || b.is_infinite() {
+            return Err(Error::at(arguments[if a.is_infinite() { 0 } else { 1 }].0, "the interval has to be finite"));
+        }
+        if a >= b {
+            return Err(Error::at(arguments[1].0, "the interval has to go left to right"));
+        }
+        Ok((a, b))
+    }
+
+    /// e with the bank functions written out, the defined variables put in and simplified
+    pub fn resolve(&self, e: &Expression) -> Expression {
+        let mut e = expand(&self.bank, e);
+        for (name, letter) in self.bank.iter() {
+            if let Letter::Variable(Value::Defined(value)) = letter {
+                e = substitute(&e, name, value);
+            }
+        }
+        simplify::simplify(&e)
+    }
+
+    /// Runs work on the session, when the maths panics it comes back as an Error. The panic
+    /// is still printed by the panic hook unless quiet_panics was called
+    pub fn attempt<T, F: FnOnce(&Session) -> T>(&self, work: F) -> Result<T, Error> {
+        let outer = ATTEMPTING.with(|attempting| attempting.replace(true));
+        let result = panic::catch_unwind(AssertUnwindSafe(|| work(self)));
+        ATTEMPTING.with(|attempting| attempting.set(outer));
+        // what the panic says is about the code, not the maths
+        result.map_err(|_| Error::new("couldn't work that out"))
+    }
+
+    // the mistakes Expression::from can't point at: brackets, stray characters, operators
+    // missing a side and letters nothing was defined for. column is where text starts in the
+    // line and free are the letters that don't need a value, None when none of them do
+    fn check(&self, text: &str, column: usize, free: Option<&[char]>) -> Result<(), Error> {
+        let chars: Vec<(usize, char)> = text.chars().enumerate().map(|(i, c)| (column + i, c)).collect();
+        let mut open: Vec<(usize, char)> = Vec::new();
+        for &(at, c) in &chars {
+            match c {
+                '(' | '{' => open.push((at, c)),
+                ')' | '}' => match open.pop() {
+                    Some((_, o)) if (o == '(') == (c == ')') => {},
+                    Some((_, o)) => return Err(Error::at(at, &format!("{} doesn't close {}", c, o))),
+                    None => return Err(Error::at(at, &format!("{} with nothing to close", c))),
+                },
+                _ if c.is_ascii_alphanumeric() || c.is_whitespace() || "+-*/^,.'|<>=;π∞".contains(c) => {},
+                _ if "⁰¹²³⁴⁵⁶⁷⁸⁹⁻√∛·′−≤≥".contains(c) => {},
+                _ => return Err(Error::at(at, &format!("{} isn't something the calculator knows", c))),
+            }
+        }
+        if let Some(&(at, c)) = open.last() {
+            return Err(Error::at(at, &format!("{} is never closed", c)));
+        }
+        if chars.iter().filter(|(_, c)| *c == '|').count() % 2 == 1 {
+            let (at, _) = chars.iter().rev().find(|(_, c)| *c == '|').unwrap();
+            return Err(Error::at(*at, "| has no partner"));
+        }
+
+        let solid: Vec<(usize, char)> = chars.iter().copied().filter(|(_, c)| !c.is_whitespace()).collect();
+        if solid.is_empty() {
+            return Err(Error::at(column, "nothing to work out"));
+        }
+        for (i, &(at, c)) in solid.iter().enumerate() {
+            let before = if i > 0 { Some(solid[i-1].1) } else { None };
+            let after = solid.get(i + 1).map(|(_, c)| *c);
+            match c {
+                '+' | '*' | '/' | '^' if before.is_none_or(|b| "(+-*/^,{".contains(b)) =>
+                    return Err(Error::at(at, &format!("{} needs something before it", c))),
+                '+' | '-' | '*' | '/' | '^' if after.is_none_or(|a| ")+*/^,}".contains(a)) =>
+                    return Err(Error::at(at, &format!("{} needs something after it", c))),
+                ')' if before == Some('(') => return Err(Error::at(at, "nothing inside the brackets")),
+                '.' if !before.is_some_and(|b| b.is_ascii_digit()) && !after.is_some_and(|a| a.is_ascii_digit()) =>
+                    return Err(Error::at(at, ". isn't part of a number")),
+                _ => {},
+            }
+            // 2 3 would be read as 23
+            if c.is_ascii_digit() && before.is_some_and(|b| b.is_ascii_digit()) && solid[i-1].0 + 1 != at {
+                return Err(Error::at(at, "missing an operator between the numbers"));
+            }
+        }
+
+        let Some(free) = free else { return Ok(()) };
+        // sums and integrals bring a letter of their own, sum(n,... and int(t,..., and pieces
+        // have their if
+        let mut bound: Vec<char> = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let (at, c) = chars[i];
+            let rest: String = chars[i..].iter().map(|(_, c)| *c).collect();
+            let braces = chars[..i].iter().filter(|(_, c)| *c == '{').count() > chars[..i].iter().filter(|(_, c)| *c == '}').count();
+            match SPECIAL_NAMES.iter().chain(["pi", "inf"].iter()).find(|name| rest.starts_with(*name)) {
+                Some(&name) if name == "sum" || name == "int" => {
+                    let letter: Vec<char> = rest.chars().skip(3).take(3).collect();
+                    if let ['(', letter, ','] = letter.as_slice() {
+                        if letter.is_ascii_alphabetic() {
+                            bound.push(*letter);
+                        }
+                    }
+                    i += name.len();
+                },
+                Some(name) => i += name.len(),
+                None if braces && rest.starts_with("if") => i += 2,
+                None => {
+                    if c.is_ascii_alphabetic() && !(c == 'e' || free.contains(&c) || bound.contains(&c) || self.bank.contains(&c)) {
+                        let message = if c == 'x' { String::from("x has no value here") } else { format!("{} isn't defined", c) };
+                        return Err(Error::at(at, &message));
+                    }
+                    i += 1;
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The words of a line with the column each starts at
+fn words(line: &str) -> Vec<(usize, &str)> {
+    let mut found = Vec::new();
+    let mut start: Option<(usize, usize)> = None;
+    for (column, (index, c)) in line.char_indices().enumerate() {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some((column, index)),
+            (true, Some((column, from))) => { found.push((column, &line[from..index])); start = None },
+            _ => {},
+        }
+    }
+    if let Some((column, from)) = start {
+        found.push((column, &line[from..]));
+    }
+    found
+}
+
+// the column of the = in a definition, one inside braces or next to < and > is a comparison
+fn definition(line: &str) -> Option<usize> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut depth = 0;
+    for (i, c) in chars.iter().enumerate() {
+        match c {
+            '(' | '{' => depth += 1,
+            ')' | '}' => depth -= 1,
+            '=' if depth == 0 && !(i > 0 && "<>=".contains(chars[i-1])) && chars.get(i + 1) != Some(&'=') => return Some(i),
+            _ => {},
+        }
+    }
+    None
+}
+
+// the byte a column starts at
+fn byte(line: &str, column: usize) -> usize {
+    line.char_indices().nth(column).map_or(line.len(), |(i, _)| i)
+}
+
+
+fn show(y: f64) -> String {
+    match y {
+        _ if y.is_nan() => String::from("undefined"),
+        f64::INFINITY => String::from("inf"),
+        f64::NEG_INFINITY => String::from("-inf"),
+        _ => format!("{}", applications::bound(y)),
+    }
+}
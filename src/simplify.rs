@@ -0,0 +1,464 @@
+use super::*;
+use num::{CheckedAdd, CheckedSub, CheckedMul, CheckedDiv};
+
+/// Folds constants and drops the 0s and 1s that ddx and the integrator leave behind,
+/// repeating until nothing changes
+pub fn simplify(e: &Expression) -> Expression {
+    let mut current = copy_expression(e);
+    loop {
+        let next = simplify_once(&current);
+        if next == current {
+            return next;
+        }
+        current = next;
+    }
+}
+
+fn constant(e: &Expression) -> Option<Fraction> {
+    match e {
+        Expression::Constant(c) => Some(*c),
+        _ => None,
+    }
+}
+
+fn is(e: &Expression, value: i64) -> bool {
+    constant(e) == Some(Fraction::from(value))
+}
+
+fn trig_name(e: &Expression) -> char {
+    match e {
+        Expression::Variable(name) => *name,
+        _ => '?',
+    }
+}
+
+fn simplify_once(e: &Expression) -> Expression {
+    let equation = match e {
+        Expression::Equa(equation) => equation,
+        _ => return copy_expression(e),
+    };
+    let operation = equation.operation;
+
+    // names, derivatives and sum bounds are left as they are
+    let a = match operation {
+        Operation::Func | Operation::Trig | Operation::Log | Operation::Deriv | Operation::Sum(_) =>
+            copy_expression(&equation.element1),
+        _ => simplify_once(&equation.element1),
+    };
+    let b = match operation {
+        Operation::Deriv => copy_expression(&equation.element2),
+        _ => simplify_once(&equation.element2),
+    };
+    let (ca, cb) = (constant(&a), constant(&b));
+
+    match operation {
+        Operation::Add => {
+            if let (Some(x), Some(y)) = (ca, cb) {
+                if let Some(sum) = x.checked_add(&y) { return Expression::Constant(sum) }
+            }
+            if is(&a, 0) { return b }
+            if is(&b, 0) { return a }
+            if let Some(negated) = negation(&b) { return equa(Operation::Sub, a, negated) }
+            if a == b { return equa(Operation::Mult, Expression::from("2"), a) }
+            if let Some(combined) = like_terms(&a, &b, 1) { return combined }
+            if let Some(c) = pythagorean(&a, &b) { return c }
+            if let Some(collected) = collect(&equa(Operation::Add, copy_expression(&a), copy_expression(&b))) { return collected }
+        },
+        Operation::Sub => {
+            if let (Some(x), Some(y)) = (ca, cb) {
+                if let Some(difference) = x.checked_sub(&y) { return Expression::Constant(difference) }
+            }
+            if is(&b, 0) { return a }
+            if a == b { return Expression::from("0") }
+            if let Some(negated) = negation(&b) { return equa(Operation::Add, a, negated) }
+            if let (Some(x), true) = (cb, is(&a, 0)) { return Expression::Constant(-x) }
+            if let Some(combined) = like_terms(&a, &b, -1) { return combined }
+            // 0-cu = (-c)u
+            if let (true, Expression::Equa(inner)) = (is(&a, 0), &b) {
+                if let (Operation::Mult, Some(c)) = (inner.operation, constant(&inner.element1)) {
+                    return equa(Operation::Mult, Expression::Constant(-c), copy_expression(&inner.element2));
+                }
+            }
+            if let Some(collected) = collect(&equa(Operation::Sub, copy_expression(&a), copy_expression(&b))) { return collected }
+        },
+        Operation::Mult => {
+            if let (Some(x), Some(y)) = (ca, cb) {
+                if let Some(product) = x.checked_mul(&y) { return Expression::Constant(product) }
+            }
+            if is(&a, 0) || is(&b, 0) { return Expression::from("0") }
+            if is(&a, 1) { return b }
+            if is(&b, 1) { return a }
+            if is(&a, -1) { return equa(Operation::Sub, Expression::from("0"), b) }
+            // c(0-u) = (-c)u
+            if let (Some(x), Some(negated), None) = (ca, negation(&b), cb) {
+                return equa(Operation::Mult, Expression::Constant(-x), negated);
+            }
+            // u(0-v) = 0-uv
+            if let (None, Some(negated), None) = (ca, negation(&b), cb) {
+                return equa(Operation::Sub, Expression::from("0"), equa(Operation::Mult, a, negated));
+            }
+            // constants go in front so they can meet up
+            if cb.is_some() && ca.is_none() { return equa(Operation::Mult, b, a) }
+            if let (None, None) = (ca, cb) {
+                if let Some((c, rest)) = leading_constant(&a) {
+                    return equa(Operation::Mult, Expression::Constant(c), equa(Operation::Mult, rest, b));
+                }
+                if let Some((c, rest)) = leading_constant(&b) {
+                    return equa(Operation::Mult, Expression::Constant(c), equa(Operation::Mult, a, rest));
+                }
+                // u^p u^q = u^(p+q)
+                let ((base_a, p), (base_b, q)) = (power_parts(&a), power_parts(&b));
+                if base_a == base_b {
+                    if let Some(sum) = p.checked_add(&q) {
+                        return equa(Operation::Exp, base_a, Expression::Constant(sum));
+                    }
+                }
+            }
+            if let (Some(x), Expression::Equa(inner)) = (ca, &b) {
+                if let (Operation::Mult, Some(y)) = (inner.operation, constant(&inner.element1)) {
+                    if let Some(product) = x.checked_mul(&y) {
+                        return equa(Operation::Mult, Expression::Constant(product), copy_expression(&inner.element2));
+                    }
+                }
+            }
+            if let Expression::Equa(inner) = &b {
+                if inner.operation == Operation::Div && is(&inner.element1, 1) {
+                    return equa(Operation::Div, a, copy_expression(&inner.element2));
+                }
+                // c1(c2/u) = (c1 c2)/u
+                if let (Operation::Div, Some(x), Some(y)) = (inner.operation, ca, constant(&inner.element1)) {
+                    if let Some(product) = x.checked_mul(&y) {
+                        return equa(Operation::Div, Expression::Constant(product), copy_expression(&inner.element2));
+                    }
+                }
+                // c1(u/c2) = (c1/c2)u, what the power rule leaves after integrating 3x^2
+                if let (Operation::Div, Some(x), Some(y)) = (inner.operation, ca, constant(&inner.element2)) {
+                    if let Some(quotient) = x.checked_div(&y) {
+                        if y != Fraction::from(0) {
+                            return equa(Operation::Mult, Expression::Constant(quotient), copy_expression(&inner.element1));
+                        }
+                    }
+                }
+            }
+        },
+        Operation::Div => {
+            if let (Some(x), Some(y)) = (ca, cb) {
+                if let Some(quotient) = x.checked_div(&y) {
+                    if y != Fraction::from(0) { return Expression::Constant(quotient) }
+                }
+            }
+            if is(&b, 1) { return a }
+            if is(&a, 0) && !is(&b, 0) { return Expression::from("0") }
+            // u/(1/c) = cu
+            if let Some(y) = cb {
+                if y.numer() == Some(&1) && y.denom() != Some(&1) {
+                    return equa(Operation::Mult, Expression::Constant(Fraction::from(*y.denom().unwrap()) * y.signum()), a);
+                }
+            }
+            // c1/(c2 u) = (c1/c2)/u
+            if let (Some(x), Expression::Equa(inner)) = (ca, &b) {
+                if let (Operation::Mult, Some(y)) = (inner.operation, constant(&inner.element1)) {
+                    if let (Some(quotient), false) = (x.checked_div(&y), y == Fraction::from(0)) {
+                        return equa(Operation::Div, Expression::Constant(quotient), copy_expression(&inner.element2));
+                    }
+                }
+            }
+            // a/(p/q) = (aq)/p
+            if let Expression::Equa(inner) = &b {
+                if inner.operation == Operation::Div {
+                    let numerator = equa(Operation::Mult, a, copy_expression(&inner.element2));
+                    return equa(Operation::Div, numerator, copy_expression(&inner.element1));
+                }
+            }
+            // (cu)/d = (c/d)u
+            if let (Some(d), Some((c, rest))) = (cb, leading_constant(&a)) {
+                if let (Some(quotient), false) = (c.checked_div(&d), d == Fraction::from(0)) {
+                    return equa(Operation::Mult, Expression::Constant(quotient), rest);
+                }
+            }
+            if a == b { return Expression::from("1") }
+        },
+        Operation::Exp => {
+            if let (Some(x), Some(y)) = (ca, cb) {
+                if y.denom() == Some(&1) && *y.numer().unwrap() < 64 {
+                    return Expression::Constant(power(x, y));
+                }
+            }
+            // 8^(2/3) = 4 when the root comes out even
+            if let (Some(x), Some(y)) = (ca, cb) {
+                if let Some(root) = exact_root(x, *y.denom().unwrap_or(&1)) {
+                    if y.denom() != Some(&1) && *y.numer().unwrap_or(&64) < 64 {
+                        return Expression::Constant(power(root, Fraction::from(*y.numer().unwrap()) * y.signum()));
+                    }
+                }
+            }
+            if is(&b, 1) { return a }
+            if is(&b, 0) { return Expression::from("1") }
+            if is(&a, 1) { return Expression::from("1") }
+            // (u^c1)^c2 = u^(c1 c2), but not (u^2)^(1/2) which is |u|
+            if let (Expression::Equa(inner), Some(y)) = (&a, cb) {
+                if let (Operation::Exp, Some(x)) = (inner.operation, constant(&inner.element2)) {
+                    let even = |n: Option<&u64>| n.is_some_and(|n| n.is_multiple_of(2));
+                    if let (Some(product), false) = (x.checked_mul(&y), even(x.numer()) && even(y.denom())) {
+                        return equa(Operation::Exp, copy_expression(&inner.element1), Expression::Constant(product));
+                    }
+                }
+            }
+            // (cu)^n = (c^n)(u^n) for whole n
+            if let (Some((c, rest)), Some(n)) = (leading_constant(&a), cb) {
+                if n.denom() == Some(&1) && *n.numer().unwrap() < 64 {
+                    return equa(Operation::Mult, Expression::Constant(power(c, n)), equa(Operation::Exp, rest, b));
+                }
+            }
+            // (0-u)^n = u^n for even n
+            if let (Some(negated), Some(n)) = (negation(&a), cb) {
+                if n.denom() == Some(&1) && n.numer().is_some_and(|n| n.is_multiple_of(2)) {
+                    return equa(Operation::Exp, negated, b);
+                }
+            }
+            // e^(ln u) = u
+            if let (Expression::Variable('e'), Expression::Equa(inner)) = (&a, &b) {
+                if inner.operation == Operation::Log && *inner.element1 == Expression::Variable('e') {
+                    return copy_expression(&inner.element2);
+                }
+            }
+        },
+        Operation::Log => {
+            if is(&b, 1) { return Expression::from("0") }
+            if a == b { return Expression::from("1") }
+            // ln(e^u) = u
+            if let Expression::Equa(inner) = &b {
+                if inner.operation == Operation::Exp && *inner.element1 == a {
+                    return copy_expression(&inner.element2);
+                }
+            }
+        },
+        Operation::Trig => {
+            match (trig_name(&a), is(&b, 0), is(&b, 1)) {
+                ('s' | 't' | 'S' | 'T', true, _) => return Expression::from("0"),
+                ('c', true, _) => return Expression::from("1"),
+                ('C', _, true) => return Expression::from("0"),
+                _ => (),
+            }
+            if let Some(value) = exact_trig(trig_name(&a), &b) {
+                return value;
+            }
+        },
+        Operation::Abs => {
+            if let Some(x) = cb { return Expression::Constant(x.abs()) }
+            // |0-u| = |u|
+            if let Some(negated) = negation(&b) { return equa(Operation::Abs, a, negated) }
+            // a number made of π and e like |e| or |(2^(1/2))/2| has a sign to read off
+            let empty = Workspace::new();
+            if known(&empty, &b, &[]) {
+                match b.approximate_with(&empty, &[]) {
+                    v if v > 0.0 => return b,
+                    v if v < 0.0 => return equa(Operation::Sub, Expression::from("0"), b),
+                    _ => (),
+                }
+            }
+        },
+        Operation::Floor => if let Some(x) = cb { return Expression::Constant(x.floor()) },
+        Operation::Ceil => if let Some(x) = cb { return Expression::Constant(x.ceil()) },
+        Operation::Root => {
+            if let (Some(n), Some(x)) = (ca, cb) {
+                if let (Some(q), Some(1)) = (n.numer(), n.denom()) {
+                    if let Some(root) = exact_root(x, *q) { return Expression::Constant(root) }
+                }
+            }
+        },
+        _ => (),
+    }
+    equa(operation, a, b)
+}
+
+// (c, u) for cu
+fn leading_constant(e: &Expression) -> Option<(Fraction, Expression)> {
+    match e {
+        Expression::Equa(equation) if equation.operation == Operation::Mult =>
+            Some((constant(&equation.element1)?, copy_expression(&equation.element2))),
+        _ => None,
+    }
+}
+
+// (u, n) for u^n with n constant, anything else is u^1
+fn power_parts(e: &Expression) -> (Expression, Fraction) {
+    match e {
+        Expression::Equa(equation) if equation.operation == Operation::Exp && constant(&equation.element2).is_some() =>
+            (copy_expression(&equation.element1), constant(&equation.element2).unwrap()),
+        _ => (copy_expression(e), Fraction::from(1)),
+    }
+}
+
+// (c1 ± c2)u for c1u ± c2u, a bare u counts as 1u
+fn like_terms(a: &Expression, b: &Expression, sign: i64) -> Option<Expression> {
+    let ((c1, u1), (c2, u2)) = (coefficient(a), coefficient(b));
+    if u1 != u2 || constant(&u1).is_some() {
+        return None;
+    }
+    let c = c1.checked_add(&(c2 * Fraction::from(sign)))?;
+    Some(equa(Operation::Mult, Expression::Constant(c), u1))
+}
+
+// (c, u) for cu, a bare u is 1u
+fn coefficient(e: &Expression) -> (Fraction, Expression) {
+    leading_constant(e).unwrap_or_else(|| (Fraction::from(1), copy_expression(e)))
+}
+
+// a sum with the same u turning up in more than one place, like π/6 + 9(π/12) - 2π,
+// gathered into one term per u with the constants last
+fn collect(e: &Expression) -> Option<Expression> {
+    let mut terms: Vec<(Fraction, Expression)> = Vec::new();
+    let mut merged = false;
+    gather(e, Fraction::from(1), &mut terms, &mut merged)?;
+    if !merged {
+        return None;
+    }
+    let (numbers, rest): (Vec<_>, Vec<_>) = terms.into_iter()
+        .filter(|(c, _)| *c != Fraction::from(0))
+        .partition(|(_, u)| constant(u).is_some());
+    let mut sum: Option<Expression> = None;
+    for (c, u) in rest.into_iter().chain(numbers.into_iter().map(|(c, _)| (c, Expression::from("1")))) {
+        let size = if sum.is_some() { c.abs() } else { c };
+        let term = if is(&u, 1) { Expression::Constant(size) }
+            else if size == Fraction::from(1) { u }
+            else if size == Fraction::from(-1) { equa(Operation::Sub, Expression::from("0"), u) }
+            else { equa(Operation::Mult, Expression::Constant(size), u) };
+        sum = Some(match sum {
+            None => term,
+            Some(sum) if c < Fraction::from(0) => equa(Operation::Sub, sum, term),
+            Some(sum) => equa(Operation::Add, sum, term),
+        });
+    }
+    Some(sum.unwrap_or_else(|| Expression::from("0")))
+}
+
+// the (c, u) terms of e scaled by k, numbers are kept under u = 1
+fn gather(e: &Expression, k: Fraction, terms: &mut Vec<(Fraction, Expression)>, merged: &mut bool) -> Option<()> {
+    if let Expression::Equa(equation) = e {
+        let (a, b) = (&*equation.element1, &*equation.element2);
+        match (equation.operation, constant(a), constant(b)) {
+            (Operation::Add, _, _) => return gather(a, k, terms, merged).and_then(|_| gather(b, k, terms, merged)),
+            (Operation::Sub, _, _) => return gather(a, k, terms, merged).and_then(|_| gather(b, -k, terms, merged)),
+            (Operation::Mult, Some(c), _) => return gather(b, k.checked_mul(&c)?, terms, merged),
+            (Operation::Div, _, Some(d)) if d != Fraction::from(0) => return gather(a, k.checked_div(&d)?, terms, merged),
+            _ => (),
+        }
+    }
+    let (c, u) = match constant(e) {
+        Some(c) => (k.checked_mul(&c)?, Expression::from("1")),
+        None => (k, copy_expression(e)),
+    };
+    if !c.is_finite() {
+        return None;
+    }
+    match terms.iter_mut().find(|(_, v)| *v == u) {
+        Some((total, _)) => {
+            *total = total.checked_add(&c)?;
+            // numbers meeting up alone would undo forms like y = 2 - 4(x-4)
+            *merged |= constant(&u).is_none();
+        },
+        None => terms.push((c, u)),
+    }
+    Some(())
+}
+
+// c sin(u)^2 + c cos(u)^2 = c
+fn pythagorean(a: &Expression, b: &Expression) -> Option<Expression> {
+    let square = |e: &Expression| -> Option<(char, Expression)> {
+        match e {
+            Expression::Equa(power) if power.operation == Operation::Exp && is(&power.element2, 2) => match &*power.element1 {
+                Expression::Equa(trig) if trig.operation == Operation::Trig =>
+                    Some((trig_name(&trig.element1), copy_expression(&trig.element2))),
+                _ => None,
+            },
+            _ => None,
+        }
+    };
+    let ((c1, u1), (c2, u2)) = (coefficient(a), coefficient(b));
+    let ((name1, arg1), (name2, arg2)) = (square(&u1)?, square(&u2)?);
+    match (name1, name2) {
+        ('s', 'c') | ('c', 's') if c1 == c2 && arg1 == arg2 => Some(Expression::Constant(c1)),
+        _ => None,
+    }
+}
+
+// u for 0-u and -c for negative constants
+fn negation(e: &Expression) -> Option<Expression> {
+    match e {
+        Expression::Constant(c) if *c < Fraction::from(0) => Some(Expression::Constant(-*c)),
+        Expression::Equa(equation) if equation.operation == Operation::Sub && is(&equation.element1, 0) =>
+            Some(copy_expression(&equation.element2)),
+        _ => None,
+    }
+}
+
+// k for kπ
+fn pi_multiple(e: &Expression) -> Option<Fraction> {
+    match e {
+        Expression::Variable('π') => Some(Fraction::from(1)),
+        Expression::Equa(equation) => match (equation.operation, constant(&equation.element1), constant(&equation.element2)) {
+            (Operation::Mult, Some(k), _) => Some(k * pi_multiple(&equation.element2)?),
+            (Operation::Sub, Some(z), _) if z == Fraction::from(0) => Some(-pi_multiple(&equation.element2)?),
+            (Operation::Div, _, Some(d)) if d != Fraction::from(0) => Some(pi_multiple(&equation.element1)? / d),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// sin((n/12)π) for n from 0 to 12
+const SINES: [&str; 13] = ["0", "", "1/2", "(2^(1/2))/2", "(3^(1/2))/2", "", "1", "", "(3^(1/2))/2", "(2^(1/2))/2", "1/2", "", "0"];
+
+// the unit circle values, sin(π/6) = 1/2, arctan(1) = π/4 and so on
+fn exact_trig(name: char, arg: &Expression) -> Option<Expression> {
+    match name {
+        's' | 'c' | 't' => {
+            let twelfths = pi_multiple(arg)? * Fraction::from(12);
+            if twelfths.denom() != Some(&1) {
+                return None;
+            }
+            let n = (*twelfths.numer()? % 24) as usize;
+            let n = if twelfths < Fraction::from(0) && n != 0 { 24 - n } else { n };
+            let sine = |n: usize| -> Option<String> {
+                let n = n % 24;
+                let value = SINES[if n > 12 { n - 12 } else { n }];
+                match (value, n > 12) {
+                    ("", _) => None,
+                    (value, false) => Some(format!("({})", value)),
+                    (value, true) => Some(format!("0-({})", value)),
+                }
+            };
+            let value = match name {
+                's' => sine(n)?,
+                'c' => sine(n + 6)?,
+                _ => {
+                    let cosine = sine(n + 6)?;
+                    if cosine == "(0)" || cosine == "0-(0)" {
+                        return None;
+                    }
+                    format!("({})/({})", sine(n)?, cosine)
+                },
+            };
+            Some(simplify(&Expression::from(&value)))
+        },
+        'S' | 'C' | 'T' => {
+            let c = constant(arg)?;
+            let half = Fraction::new(1u64, 2u64);
+            let arcsin = if c.abs() == Fraction::from(1) { Fraction::new(1u64, 2u64) }
+                else if c.abs() == half { Fraction::new(1u64, 6u64) }
+                else if c == Fraction::from(0) { Fraction::from(0) }
+                else { return None };
+            let arcsin = if c < Fraction::from(0) { -arcsin } else { arcsin };
+            let k = match name {
+                'S' => arcsin,
+                'C' => half - arcsin,
+                _ if c == Fraction::from(0) => Fraction::from(0),
+                _ if c.abs() == Fraction::from(1) => if c < Fraction::from(0) { -Fraction::new(1u64, 4u64) } else { Fraction::new(1u64, 4u64) },
+                _ => return None,
+            };
+            Some(simplify(&equa(Operation::Mult, Expression::Constant(k), Expression::Variable('π'))))
+        },
+        _ => None,
+    }
+}
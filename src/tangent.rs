@@ -0,0 +1,141 @@
+use super::*;
+use derivative::derivative;
+use simplify::simplify;
+
+/// Which side of the curve a tangent line approximation lands on
+#[derive(PartialEq)]
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Estimate {
+    // concave up, the tangent line sits below the curve
+    Under,
+    // concave down, the tangent line sits above the curve
+    Over,
+    // f'' is 0 or changes sign between a and x
+    Undetermined,
+}
+
+/// A line through the plane, a graph y = m(x) or a vertical line x = c which isn't one
+#[derive(PartialEq)]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Line {
+    Graph(Expression),
+    Vertical(Expression),
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Line::Graph(y) => write!(f,"y = {}",y),
+            Line::Vertical(x) => write!(f,"x = {}",x),
+        }
+    }
+}
+
+/// L(x) for f at a, used to approximate f at x
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Linearization {
+    pub line: Expression,
+    pub approximation: Expression,
+    #[cfg_attr(feature = "serde", serde(with = "crate::schema::number"))]
+    pub value: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::schema::number"))]
+    pub actual: f64,
+    // actual - value, positive for an underestimate
+    #[cfg_attr(feature = "serde", serde(with = "crate::schema::number"))]
+    pub error: f64,
+    pub estimate: Estimate,
+}
+
+// f(a) and f'(a) exactly
+fn point_and_slope(bank: &Workspace, f: char, a: &Expression) -> (Expression, Expression, Expression) {
+    let curve = expand(bank, &Expression::from(&format!("{}(x)", f)));
+    let slope = derivative(bank, &curve);
+    (simplify(&substitute(&curve, 'x', a)), simplify(&substitute(&slope, 'x', a)), slope)
+}
+
+// how steep f is at a, infinite for a vertical tangent like cbrt(x) at 0 and NaN when
+// there's no tangent at all like |x| at 0
+fn steepness(bank: &Workspace, a: &Expression, slope: &Expression, derivative: &Expression) -> f64 {
+    // f'(x) at a before it's simplified, which could lose a 0/0, but the exact f'(a) when
+    // there is one since cos(π/2) isn't quite 0 in floats
+    let a = a.approximate_with(bank, &[]);
+    let m = derivative.approximate(bank, a);
+    if m.is_finite() {
+        return slope.approximate_with(bank, &[]);
+    }
+    if m.is_infinite() {
+        return m;
+    }
+    // it can be undefined where the one sided limits both blow up
+    let (left, right) = (limits::limit(bank, derivative, a, limits::Side::Left), limits::limit(bank, derivative, a, limits::Side::Right));
+    if left.is_infinite() && right.is_infinite() { f64::INFINITY } else { f64::NAN }
+}
+
+/// y = f(a) + f'(a)(x-a), or the vertical line x = a when f'(a) is infinite. None when f
+/// has no tangent at a, a corner or f(a) undefined
+pub fn tangent_line(bank: &Workspace, f: char, a: &Expression) -> Option<Line> {
+    let (height, slope, derivative) = point_and_slope(bank, f, a);
+    let m = steepness(bank, a, &slope, &derivative);
+    if m.is_nan() || !height.approximate_with(bank, &[]).is_finite() {
+        return None;
+    }
+    if m.is_infinite() {
+        return Some(Line::Vertical(simplify(a)));
+    }
+    Some(Line::Graph(simplify(&Expression::from(&format!("({})+(({})((x)-({})))", height, slope, a)))))
+}
+
+/// y = f(a) - (1/f'(a))(x-a), the vertical line x = a when f'(a) = 0 and the horizontal
+/// y = f(a) when the tangent is vertical. None when f has no tangent at a
+pub fn normal_line(bank: &Workspace, f: char, a: &Expression) -> Option<Line> {
+    let (height, slope, derivative) = point_and_slope(bank, f, a);
+    let m = steepness(bank, a, &slope, &derivative);
+    if m.is_nan() || !height.approximate_with(bank, &[]).is_finite() {
+        return None;
+    }
+    Some(match m {
+        _ if m == 0.0 => Line::Vertical(simplify(a)),
+        _ if m.is_infinite() => Line::Graph(height),
+        _ => Line::Graph(simplify(&Expression::from(&format!("({})-(((x)-({}))/({}))", height, a, slope)))),
+    })
+}
+
+/// The differential dy = f'(a) dx
+pub fn differential(bank: &Workspace, f: char, a: &Expression, dx: &Expression) -> Expression {
+    let (_, slope, _) = point_and_slope(bank, f, a);
+    simplify(&Expression::from(&format!("({})({})", slope, dx)))
+}
+
+/// The tangent line of f at a used to approximate f(x). Whether that's an over or under
+/// estimate comes from the sign of f'' from a to x. None when the tangent isn't a graph
+/// y = L(x) to approximate with
+pub fn linearization(bank: &Workspace, f: char, a: &Expression, x: &Expression) -> Option<Linearization> {
+    let Some(Line::Graph(line)) = tangent_line(bank, f, a) else { return None };
+    let approximation = simplify(&substitute(&line, 'x', x));
+    let value = approximation.approximate_with(bank, &[]);
+    let curve = expand(bank, &Expression::from(&format!("{}(x)", f)));
+    let actual = curve.approximate_with(bank, &[('x', x.approximate_with(bank, &[]))]);
+
+    let (_, _, slope) = point_and_slope(bank, f, a);
+    let concavity = derivative(bank, &slope);
+    let (start, end) = (a.approximate_with(bank, &[]), x.approximate_with(bank, &[]));
+    // f''(a) itself can be 0 like x^3 at 0 and still have one concavity after it
+    let signs: Vec<f64> = (1..=20)
+        .map(|i| concavity.approximate(bank, start + (end - start) * i as f64 / 20.0))
+        .collect();
+    let estimate = if signs.iter().all(|s| *s > 0.0) {
+        Estimate::Under
+    } else if signs.iter().all(|s| *s < 0.0) {
+        Estimate::Over
+    } else {
+        Estimate::Undetermined
+    };
+
+    Some(Linearization { line, approximation, value, actual, error: actual - value, estimate })
+}
@@ -0,0 +1,168 @@
+use super::*;
+use applications::Bound;
+use derivative::derivative;
+use simplify::simplify;
+
+/// The existence theorems AP asks students to justify
+#[derive(PartialEq)]
+#[derive(Debug)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Theorem {
+    MeanValue,
+    Rolle,
+    IntermediateValue,
+}
+
+/// Everything a justification needs. target is what f'(c) has to be, or f(c) for the IVT.
+/// The c values are looked for even when a hypothesis fails, the theorem just doesn't
+/// promise there are any
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Justification {
+    pub theorem: Theorem,
+    pub f: char,
+    pub a: Expression,
+    pub b: Expression,
+    pub hypotheses: Vec<(String, bool)>,
+    pub met: bool,
+    pub fa: Expression,
+    pub fb: Expression,
+    pub target: Expression,
+    pub c: Vec<Bound>,
+}
+
+impl fmt::Display for Justification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self.theorem {
+            Theorem::MeanValue => "Mean Value Theorem",
+            Theorem::Rolle => "Rolle's Theorem",
+            Theorem::IntermediateValue => "Intermediate Value Theorem",
+        };
+        writeln!(f,"{} for {} on [{}, {}]",name,self.f,self.a,self.b)?;
+        for (hypothesis, holds) in &self.hypotheses {
+            writeln!(f,"  {}: {}",hypothesis,if *holds { "yes" } else { "no" })?;
+        }
+        writeln!(f,"  {}({}) = {}, {}({}) = {}",self.f,self.a,self.fa,self.f,self.b,self.fb)?;
+        if self.theorem == Theorem::MeanValue {
+            writeln!(f,"  secant slope = {}",self.target)?;
+        }
+        let conclusion = if self.theorem == Theorem::IntermediateValue { format!("{}(c)", self.f) } else { format!("{}'(c)", self.f) };
+        let c: Vec<String> = self.c.iter().map(|c| c.to_string()).collect();
+        match (self.met, c.is_empty()) {
+            (_, false) => write!(f,"  {} = {} at c = {}",conclusion,self.target,c.join(", ")),
+            (true, true) => write!(f,"  {} = {} somewhere, but no c was found",conclusion,self.target),
+            (false, true) => write!(f,"  the hypotheses fail and no c has {} = {}",conclusion,self.target),
+        }
+    }
+}
+
+// f, f', f(a), f(b), a and b as numbers, and whether f is continuous on [a, b]
+struct Setup {
+    curve: Expression,
+    slope: Expression,
+    fa: Expression,
+    fb: Expression,
+    low: f64,
+    high: f64,
+    continuous: bool,
+}
+
+fn set_up(bank: &Workspace, f: char, a: &Expression, b: &Expression) -> Setup {
+    // simplified so x^(2/3) has a constant power and works for negative x
+    let curve = simplify(&expand(bank, &Expression::from(&format!("{}(x)", f))));
+    let slope = derivative(bank, &curve);
+    let (low, high) = (a.approximate_with(bank, &[]), b.approximate_with(bank, &[]));
+    let continuous = continuity::continuous_on(bank, &curve, low, high);
+    Setup {
+        fa: simplify(&substitute(&curve, 'x', a)),
+        fb: simplify(&substitute(&curve, 'x', b)),
+        curve, slope, low, high, continuous,
+    }
+}
+
+// the x strictly between low and high where g(x) = target, not where g jumps past it
+fn solve(bank: &Workspace, g: &Expression, target: f64, low: f64, high: f64) -> Vec<Bound> {
+    numeric::roots(|x| g.approximate(bank, x) - target, low, high).into_iter()
+        .filter(|x| *x > low + 1e-9 && *x < high - 1e-9)
+        .filter(|x| (g.approximate(bank, *x) - target).abs() < 1e-6 * target.abs().max(1.0))
+        .map(witness)
+        .collect()
+}
+
+// a c value, MVT answers are often square roots like 3^(1/2) so those are tried too
+fn witness(x: f64) -> Bound {
+    let bound = applications::bound(x);
+    if bound.exact.is_some() {
+        return bound;
+    }
+    match numeric::nice_fraction(x * x, 1e-9).filter(|square| square.denom() <= Some(&12)) {
+        Some(square) => {
+            let root = simplify(&Expression::from(&format!("({})^(1/2)", square)));
+            Bound { value: x, exact: Some(if x < 0.0 { simplify(&equa(Operation::Sub, Expression::from("0"), root)) } else { root }) }
+        },
+        None => bound,
+    }
+}
+
+fn interval(a: &Expression, b: &Expression, closed: bool) -> String {
+    if closed { format!("[{}, {}]", a, b) } else { format!("({}, {})", a, b) }
+}
+
+/// The MVT for bank function f on [a, b]: some c in (a, b) has f'(c) = (f(b)-f(a))/(b-a)
+pub fn mean_value(bank: &Workspace, f: char, a: &Expression, b: &Expression) -> Justification {
+    let setup = set_up(bank, f, a, b);
+    let differentiable = continuity::differentiable_on(bank, &setup.curve, setup.low, setup.high);
+    let target = simplify(&Expression::from(&format!("(({})-({}))/(({})-({}))", setup.fb, setup.fa, b, a)));
+    let c = solve(bank, &setup.slope, target.approximate_with(bank, &[]), setup.low, setup.high);
+    let hypotheses = vec![
+        (format!("{} is continuous on {}", f, interval(a, b, true)), setup.continuous),
+        (format!("{} is differentiable on {}", f, interval(a, b, false)), differentiable),
+    ];
+    justify(Theorem::MeanValue, f, a, b, hypotheses, setup, target, c)
+}
+
+/// Rolle's theorem for bank function f on [a, b]: when f(a) = f(b) some c in (a, b) has f'(c) = 0
+pub fn rolle(bank: &Workspace, f: char, a: &Expression, b: &Expression) -> Justification {
+    let setup = set_up(bank, f, a, b);
+    let differentiable = continuity::differentiable_on(bank, &setup.curve, setup.low, setup.high);
+    let level = (setup.fa.approximate_with(bank, &[]) - setup.fb.approximate_with(bank, &[])).abs() < 1e-9;
+    let c = solve(bank, &setup.slope, 0.0, setup.low, setup.high);
+    let hypotheses = vec![
+        (format!("{} is continuous on {}", f, interval(a, b, true)), setup.continuous),
+        (format!("{} is differentiable on {}", f, interval(a, b, false)), differentiable),
+        (format!("{}({}) = {}({})", f, a, f, b), level),
+    ];
+    justify(Theorem::Rolle, f, a, b, hypotheses, setup, Expression::from("0"), c)
+}
+
+/// The IVT for bank function f on [a, b]: when k is between f(a) and f(b) some c in (a, b)
+/// has f(c) = k
+pub fn intermediate_value(bank: &Workspace, f: char, a: &Expression, b: &Expression, k: &Expression) -> Justification {
+    let setup = set_up(bank, f, a, b);
+    let (fa, fb, level) = (setup.fa.approximate_with(bank, &[]), setup.fb.approximate_with(bank, &[]), k.approximate_with(bank, &[]));
+    let between = fa.min(fb) <= level && level <= fa.max(fb);
+    let c = solve(bank, &setup.curve, level, setup.low, setup.high);
+    let hypotheses = vec![
+        (format!("{} is continuous on {}", f, interval(a, b, true)), setup.continuous),
+        (format!("{} is between {}({}) and {}({})", k, f, a, f, b), between),
+    ];
+    justify(Theorem::IntermediateValue, f, a, b, hypotheses, setup, copy_expression(k), c)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn justify(theorem: Theorem, f: char, a: &Expression, b: &Expression, hypotheses: Vec<(String, bool)>, setup: Setup, target: Expression, c: Vec<Bound>) -> Justification {
+    Justification {
+        theorem,
+        f,
+        a: copy_expression(a),
+        b: copy_expression(b),
+        met: hypotheses.iter().all(|(_, holds)| *holds),
+        hypotheses,
+        fa: setup.fa,
+        fb: setup.fb,
+        target,
+        c,
+    }
+}
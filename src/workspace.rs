@@ -0,0 +1,353 @@
+use super::*;
+use session::Session;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A problem with a workspace file, line is None when the file couldn't be read at all
+#[derive(Debug)]
+pub struct FileError {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for FileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Why a Workspace turned down a definition or an evaluation
+#[derive(PartialEq)]
+#[derive(Debug)]
+pub struct WorkspaceError {
+    pub name: char,
+    pub message: String,
+}
+
+impl fmt::Display for WorkspaceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.message)
+    }
+}
+
+// a line of the file, rule is in x for a function
+struct Definition {
+    line: usize,
+    name: char,
+    function: bool,
+    rule: Expression,
+}
+
+impl Workspace {
+    pub fn new() -> Workspace {
+        Workspace { letters: HashMap::new() }
+    }
+
+    /// name(x) = rule, turned down when name isn't a letter of its own or rule would make
+    /// name depend on itself. The letters rule uses can be defined afterwards, evaluate
+    /// checks they are there
+    pub fn define_function(&mut self, name: char, rule: Expression) -> Result<(), WorkspaceError> {
+        self.admit(name, &rule)?;
+        self.letters.insert(name, Letter::Function(Value::Defined(rule)));
+        Ok(())
+    }
+
+    /// name = value, checked like define_function and turned down when value has x in it
+    pub fn define_variable(&mut self, name: char, value: Expression) -> Result<(), WorkspaceError> {
+        self.admit(name, &value)?;
+        if contains(&value, 'x') {
+            return Err(WorkspaceError { name, message: String::from("a variable can't depend on x") });
+        }
+        self.letters.insert(name, Letter::Variable(Value::Defined(value)));
+        Ok(())
+    }
+
+    /// name(x) = rule for a rule like int(t,a,x,f(t)), whose derivative is known by FTC
+    pub fn define_accumulation(&mut self, name: char, rule: Expression) -> Result<(), WorkspaceError> {
+        self.admit(name, &rule)?;
+        if !accumulation(&rule) {
+            return Err(WorkspaceError { name, message: String::from("an accumulation function is an integral from a to x") });
+        }
+        self.letters.insert(name, Letter::Accumulation(Value::Defined(rule)));
+        Ok(())
+    }
+
+    fn admit(&self, name: char, rule: &Expression) -> Result<(), WorkspaceError> {
+        let refuse = |message: String| Err(WorkspaceError { name, message });
+        if !name.is_alphabetic() || ['x', 'e', 'π'].contains(&name) {
+            return refuse(format!("{} can't be defined", name));
+        }
+        for r in references(rule) {
+            if r == name || self.dependencies_of(r).contains(&name) {
+                return refuse(format!("{} would depend on itself through {}", name, r));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn remove(&mut self, name: char) -> Option<Letter> {
+        self.letters.remove(&name)
+    }
+
+    pub fn get(&self, name: &char) -> Option<&Letter> {
+        self.letters.get(name)
+    }
+
+    /// Whether name is in the workspace, defined or not
+    pub fn contains(&self, name: &char) -> bool {
+        self.letters.contains_key(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.letters.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.letters.is_empty()
+    }
+
+    /// The letters in alphabetical order
+    pub fn iter(&self) -> impl Iterator<Item = (char, &Letter)> {
+        let mut letters: Vec<(char, &Letter)> = self.letters.iter().map(|(name, letter)| (*name, letter)).collect();
+        letters.sort_by_key(|(name, _)| *name);
+        letters.into_iter()
+    }
+
+    /// Every letter name's definition uses, directly or through other letters, in
+    /// alphabetical order
+    pub fn dependencies_of(&self, name: char) -> Vec<char> {
+        let mut found: Vec<char> = Vec::new();
+        let mut waiting = vec![name];
+        while let Some(next) = waiting.pop() {
+            if let Some(Letter::Function(Value::Defined(rule)) | Letter::Variable(Value::Defined(rule)) | Letter::Accumulation(Value::Defined(rule))) = self.get(&next) {
+                for r in references(rule) {
+                    if !found.contains(&r) {
+                        found.push(r);
+                        waiting.push(r);
+                    }
+                }
+            }
+        }
+        found.sort();
+        found
+    }
+
+    /// name(x) for a function, with x as the one argument, or name's value for a variable
+    /// with no arguments. Every letter name uses has to have a definition by now, one that
+    /// was never defined or was removed is an error
+    pub fn evaluate(&self, name: char, args: &[Fraction]) -> Result<Fraction, WorkspaceError> {
+        let refuse = |message: &str| Err(WorkspaceError { name, message: String::from(message) });
+        let letter = match self.get(&name) {
+            None => return refuse("isn't defined"),
+            Some(letter) if letter.get_inside() == &Value::Undefined => return refuse("has no definition yet"),
+            Some(letter) => letter,
+        };
+        for r in self.dependencies_of(name) {
+            match self.get(&r) {
+                None => return refuse(&format!("uses {}, which isn't defined", r)),
+                Some(used) if used.get_inside() == &Value::Undefined => return refuse(&format!("uses {}, which has no definition yet", r)),
+                Some(_) => (),
+            }
+        }
+        match (letter, args) {
+            (Letter::Variable(_), []) => Ok(letter.evaluate(self, Fraction::from(0))),
+            (Letter::Variable(_), _) => refuse("is a variable so it takes no arguments"),
+            (_, [x]) => Ok(letter.evaluate(self, *x)),
+            _ => refuse("is a function of x so it takes one argument"),
+        }
+    }
+
+    /// The workspace as a text file a teacher can edit, a definition a line like
+    /// f(x) = 3x^2+1 or a = 5 and # starting a comment
+    pub fn to_text(&self) -> String {
+        let defined: Vec<(char, &Letter)> = self.iter()
+            .filter(|(_, letter)| matches!(letter.get_inside(), Value::Defined(_)))
+            .collect();
+        let edges: Vec<(char, Vec<char>)> = defined.iter()
+            .map(|(name, letter)| (*name, references(letter.get_expression())))
+            .collect();
+        let lines: Vec<String> = order(&edges).iter().map(|name| {
+            let letter = &self.letters[name];
+            match letter {
+                Letter::Variable(_) => format!("{} = {}", name, letter.get_expression().pretty()),
+                _ => format!("{}(x) = {}", name, letter.get_expression().pretty()),
+            }
+        }).collect();
+        lines.iter().map(|line| format!("{}\n", line)).collect()
+    }
+
+    /// Reads what to_text writes, the definitions can come in any order. Every mistake in the
+    /// file comes back with its line, not just the first
+    pub fn from_text(text: &str) -> Result<Workspace, Vec<FileError>> {
+        let mut errors: Vec<FileError> = Vec::new();
+        let mut definitions: Vec<Definition> = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("");
+            if line.trim().is_empty() {
+                continue;
+            }
+            match definition(i + 1, line) {
+                Ok(found) => match definitions.iter().find(|d| d.name == found.name) {
+                    Some(first) => errors.push(FileError { line: Some(i + 1), message: format!("{} was already defined on line {}", found.name, first.line) }),
+                    None => definitions.push(found),
+                },
+                Err(error) => errors.push(error),
+            }
+        }
+
+        for d in &definitions {
+            for r in references(&d.rule) {
+                if !definitions.iter().any(|other| other.name == r) {
+                    errors.push(FileError { line: Some(d.line), message: format!("{} uses {}, which isn't defined", d.name, r) });
+                }
+            }
+        }
+        let edges: Vec<(char, Vec<char>)> = definitions.iter().map(|d| (d.name, references(&d.rule))).collect();
+        for d in &definitions {
+            if let Some(path) = cycle(&edges, d.name) {
+                // reported once, on the line of the first letter in the loop
+                if path.iter().all(|p| definitions.iter().find(|other| other.name == *p).is_none_or(|other| other.line >= d.line)) {
+                    let path: Vec<String> = path.iter().map(|p| p.to_string()).collect();
+                    errors.push(FileError { line: Some(d.line), message: format!("{} depends on itself, {}", d.name, path.join(" -> ")) });
+                }
+            }
+        }
+        if !errors.is_empty() {
+            errors.sort_by_key(|error| error.line);
+            return Err(errors);
+        }
+
+        let mut bank = Workspace::new();
+        for d in definitions {
+            let letter = match d.function {
+                false => Letter::Variable(Value::Defined(d.rule)),
+                true if accumulation(&d.rule) => Letter::Accumulation(Value::Defined(d.rule)),
+                true => Letter::Function(Value::Defined(d.rule)),
+            };
+            bank.letters.insert(d.name, letter);
+        }
+        Ok(bank)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_text())
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Workspace, Vec<FileError>> {
+        let path = path.as_ref();
+        match fs::read_to_string(path) {
+            Ok(text) => Workspace::from_text(&text),
+            Err(error) => Err(vec![FileError { line: None, message: format!("can't read {}: {}", path.display(), error) }]),
+        }
+    }
+}
+
+// f(t) = t^2 or a = 5, with the mistakes pointed at the way the calculator points at them
+fn definition(line: usize, text: &str) -> Result<Definition, FileError> {
+    let error = |message: String| FileError { line: Some(line), message };
+    let Some((left, right)) = text.split_once('=') else {
+        return Err(error(String::from("a definition is name(x) = ... or name = ...")));
+    };
+    let name: Vec<char> = left.chars().filter(|c| !c.is_whitespace()).collect();
+    let (name, variable) = match name.as_slice() {
+        [name] if name.is_ascii_alphabetic() => (*name, None),
+        [name, '(', variable, ')'] if name.is_ascii_alphabetic() && variable.is_ascii_alphabetic() => (*name, Some(*variable)),
+        _ => return Err(error(String::from("a definition is name(x) = ... or name = ..."))),
+    };
+    if name == 'x' || name == 'e' {
+        return Err(error(format!("{} can't be redefined", name)));
+    }
+    let column = left.chars().count() + 1;
+    let rule = Session::new().parse_symbolic(right).map_err(|mistake| {
+        let mistake = session::Error { column: mistake.column.map(|c| c + column), ..mistake };
+        error(format!("{}", mistake))
+    })?;
+    let rule = match variable {
+        Some(variable) => substitute(&rule, variable, &Expression::Variable('x')),
+        None if contains(&rule, 'x') => return Err(error(format!("{} is a variable so it can't depend on x", name))),
+        None => rule,
+    };
+    Ok(Definition { line, name, function: variable.is_some(), rule })
+}
+
+// g(x) = int(t,a,x,f(t)) is an accumulation function, the same as add_accumulation_to_bank makes
+fn accumulation(rule: &Expression) -> bool {
+    match rule {
+        Expression::Equa(equation) => match (equation.operation, &*equation.element1) {
+            (Operation::Integral(_), Expression::Equa(bounds)) => *bounds.element2 == Expression::Variable('x'),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// The letters e uses that need a definition, x, e, π and the variables of sums and
+/// integrals don't
+pub fn references(e: &Expression) -> Vec<char> {
+    let mut found = Vec::new();
+    gather(e, &[], &mut found);
+    found
+}
+
+fn gather(e: &Expression, bound: &[char], found: &mut Vec<char>) {
+    match e {
+        Expression::Variable(v) if !(bound.contains(v) || ['x', 'e', 'π'].contains(v) || found.contains(v)) => found.push(*v),
+        Expression::Equa(equation) => match equation.operation {
+            Operation::Trig => gather(&equation.element2, bound, found),
+            Operation::Deriv => gather(&equation.element1, bound, found),
+            Operation::Sum(n) | Operation::Integral(n) => {
+                gather(&equation.element1, bound, found);
+                gather(&equation.element2, &[bound, &[n]].concat(), found);
+            },
+            _ => {
+                gather(&equation.element1, bound, found);
+                gather(&equation.element2, bound, found);
+            },
+        },
+        _ => {},
+    }
+}
+
+// the loop back to start through the letters each uses, if there is one
+fn cycle(edges: &[(char, Vec<char>)], start: char) -> Option<Vec<char>> {
+    fn walk(edges: &[(char, Vec<char>)], path: &mut Vec<char>, start: char) -> bool {
+        let last = *path.last().unwrap();
+        let next = edges.iter().find(|(name, _)| *name == last).map_or(&[][..], |(_, uses)| uses);
+        for n in next {
+            if *n == start {
+                path.push(*n);
+                return true;
+            }
+            if !path.contains(n) {
+                path.push(*n);
+                if walk(edges, path, start) {
+                    return true;
+                }
+                path.pop();
+            }
+        }
+        false
+    }
+    let mut path = vec![start];
+    if walk(edges, &mut path, start) { Some(path) } else { None }
+}
+
+// every letter after the ones it uses, alphabetical otherwise so saving is repeatable
+fn order(edges: &[(char, Vec<char>)]) -> Vec<char> {
+    let mut names: Vec<char> = edges.iter().map(|(name, _)| *name).collect();
+    names.sort();
+    let mut placed: Vec<char> = Vec::new();
+    while placed.len() < names.len() {
+        let ready = names.iter().find(|name| !placed.contains(name) && edges.iter()
+            .find(|(n, _)| n == *name)
+            .is_none_or(|(_, uses)| uses.iter().all(|u| placed.contains(u) || !names.contains(u) || u == *name)));
+        // a loop can't be put in order, so it goes in as it is
+        let next = ready.or_else(|| names.iter().find(|name| !placed.contains(name)));
+        placed.push(*next.unwrap());
+    }
+    placed
+}
@@ -0,0 +1,109 @@
+use ap_calc::Expression;
+use ap_calc::applications::{self, Axis, Kind, Respect, Shape};
+use ap_calc::simplify::simplify;
+
+#[test]
+fn area_between_curves() {
+    let mut bank = ap_calc::new_bank('f', "x");
+    ap_calc::add_func_to_bank(&mut bank, 'g', "x^2");
+    ap_calc::add_func_to_bank(&mut bank, 'c', "cos(x)");
+    let region = applications::region(&bank, 'f', Some('g'), Respect::X).unwrap();
+    let area = region.area(&bank);
+
+    assert_eq!(area.kind, Kind::Area);
+    assert_eq!(area.value.exact, Some(simplify(&Expression::from("1/6"))));
+    assert_eq!(area.text, "∫_0^1 (x)-(((x)^(2))) dx");
+    // the integral it set up gives the same number
+    assert!((area.integral.approximate(&bank, 0.0) - 1.0 / 6.0).abs() < 1e-9);
+
+    // with respect to y the functions are x = f(y)
+    let sideways = applications::region(&bank, 'g', Some('f'), Respect::Y).unwrap().area(&bank);
+    assert_eq!(sideways.text, "∫_0^1 (y)-(((y)^(2))) dy");
+
+    // cos(x) and x cross at an x that isn't nice, so that piece is numeric
+    let crossing = applications::region_on(&bank, 'c', Some('f'), Respect::X, &Expression::from("0"), &Expression::from("pi/2"));
+    let area = crossing.area(&bank);
+    assert_eq!(crossing.points.len(), 3);
+    assert!(area.value.exact.is_none());
+    assert!((area.value.value - 1.0346777743629276).abs() < 1e-6);
+
+    // meeting once encloses nothing, three times makes two pieces
+    assert!(applications::region(&bank, 'f', Some('c'), Respect::X).is_none());
+    ap_calc::add_func_to_bank(&mut bank, 'h', "x^3");
+    let pieces = applications::region(&bank, 'f', Some('h'), Respect::X).unwrap();
+    assert_eq!(pieces.points.len(), 3);
+    assert_eq!(pieces.area(&bank).value.exact, Some(simplify(&Expression::from("1/2"))));
+}
+
+#[test]
+fn solids_of_revolution() {
+    let mut bank = ap_calc::new_bank('f', "x");
+    ap_calc::add_func_to_bank(&mut bank, 'g', "x^2");
+    ap_calc::add_func_to_bank(&mut bank, 'r', "x^(1/2)");
+    let region = applications::region(&bank, 'f', Some('g'), Respect::X).unwrap();
+    let exact = |e: &str| Some(simplify(&Expression::from(e)));
+
+    let washer = region.volume(&bank, &Axis::Horizontal(Expression::from("0"))).unwrap();
+    assert_eq!(washer.kind, Kind::Washer);
+    assert_eq!(washer.value.exact, exact("(2/15)π"));
+
+    let volume = |axis| region.volume(&bank, &axis).map(|v| v.value.exact);
+    assert_eq!(volume(Axis::Horizontal(Expression::from("-1"))), Some(exact("(7/15)π")));
+    assert_eq!(volume(Axis::Horizontal(Expression::from("2"))), Some(exact("(8/15)π")));
+    // an axis through the region doesn't make a solid, across the slices or along them
+    assert_eq!(volume(Axis::Horizontal(Expression::from("1/8"))), None);
+    assert_eq!(volume(Axis::Vertical(Expression::from("1/2"))), None);
+
+    let shell = region.volume(&bank, &Axis::Vertical(Expression::from("1"))).unwrap();
+    assert_eq!(shell.kind, Kind::Shell);
+    assert_eq!(shell.text, "2π ∫_0^1 ((1)-(x))((x)-(((x)^(2)))) dx");
+    assert_eq!(shell.value.exact, exact("(1/6)π"));
+
+    let disk = applications::region_on(&bank, 'r', None, Respect::X, &Expression::from("0"), &Expression::from("4"))
+        .volume(&bank, &Axis::Horizontal(Expression::from("0"))).unwrap();
+    assert_eq!(disk.kind, Kind::Disk);
+    assert_eq!(disk.value.exact, exact("8π"));
+}
+
+#[test]
+fn cross_sections() {
+    let mut bank = ap_calc::new_bank('f', "x");
+    ap_calc::add_func_to_bank(&mut bank, 'g', "x^2");
+    let region = applications::region(&bank, 'f', Some('g'), Respect::X).unwrap();
+    let volume = |shape| region.cross_section(&bank, shape).value.value;
+
+    assert!((volume(Shape::Square) - 1.0 / 30.0).abs() < 1e-12);
+    assert!((volume(Shape::Semicircle) - std::f64::consts::PI / 240.0).abs() < 1e-12);
+    assert!((volume(Shape::EquilateralTriangle) - 3f64.sqrt() / 120.0).abs() < 1e-12);
+}
+
+#[test]
+fn lengths_and_averages() {
+    let mut bank = ap_calc::new_bank('f', "x");
+    ap_calc::add_func_to_bank(&mut bank, 'g', "x^2");
+    ap_calc::add_func_to_bank(&mut bank, 'h', "x^(3/2)");
+    ap_calc::add_func_to_bank(&mut bank, 's', "2sin(x)");
+    ap_calc::add_func_to_bank(&mut bank, 'p', "2cos(x)");
+    let (zero, one) = (Expression::from("0"), Expression::from("1"));
+
+    let length = applications::arc_length(&bank, 'h', &zero, &Expression::from("4"));
+    assert!(length.exact.is_some());
+    assert!((length.value - 8.0 * (10f64.powf(1.5) - 1.0) / 27.0).abs() < 1e-9);
+    // (1+4x^2)^(1/2) has no antiderivative here so it's numeric
+    let length = applications::arc_length(&bank, 'g', &zero, &one);
+    assert_eq!(length.method, ap_calc::integral::Method::Numeric);
+    assert!((length.value - 1.4789428575445975).abs() < 1e-9);
+
+    // a circle of radius 2
+    let circle = applications::parametric_arc_length(&bank, 'p', 's', &zero, &Expression::from("2pi"));
+    assert_eq!(circle.exact, Some(simplify(&Expression::from("4π"))));
+
+    assert_eq!(applications::average_value(&bank, 'g', &zero, &Expression::from("3")).exact, Some(Expression::from("3")));
+    assert_eq!(applications::average_value(&bank, 'p', &zero, &Expression::from("pi/2")).exact, Some(simplify(&Expression::from("4/π"))));
+
+    // a cone with slant height 2^(1/2)
+    let cone = applications::surface_area(&bank, 'f', &zero, &one, &Axis::Horizontal(Expression::from("0")));
+    assert_eq!(cone.exact, Some(simplify(&Expression::from("π*(2^(1/2))"))));
+    let bowl = applications::surface_area(&bank, 'g', &zero, &one, &Axis::Vertical(Expression::from("0")));
+    assert!((bowl.value - std::f64::consts::PI * (5f64.powf(1.5) - 1.0) / 6.0).abs() < 1e-6);
+}
@@ -0,0 +1,58 @@
+use ap_calc::Expression;
+use ap_calc::continuity::{asymptotes, classify, continuous_at, discontinuities, Discontinuity};
+
+#[test]
+fn classifying() {
+    let bank = ap_calc::new_bank('f', "(x^2-1)/(x-1)");
+    let points = discontinuities(&bank, &Expression::from("f(x)"), -5.0, 5.0);
+    assert_eq!(points.len(), 1);
+    assert_eq!(points[0].x.to_string(), "1");
+    assert_eq!(points[0].kind, Discontinuity::Removable);
+    assert!((points[0].left - 2.0).abs() < 1e-9 && (points[0].right - 2.0).abs() < 1e-9);
+    assert!(points[0].value.is_nan());
+
+    assert_eq!(classify(&bank, &Expression::from("((x^2)^(1/2))/x"), 0.0), Some(Discontinuity::Jump));
+    assert_eq!(classify(&bank, &Expression::from("1/x"), 0.0), Some(Discontinuity::Infinite));
+    assert_eq!(classify(&bank, &Expression::from("sin(1/x)"), 0.0), Some(Discontinuity::Oscillating));
+    assert!(continuous_at(&bank, &Expression::from("x^2"), 1.0));
+    assert!(!continuous_at(&bank, &Expression::from("f(x)"), 1.0));
+}
+
+#[test]
+fn scanning() {
+    let bank = ap_calc::Workspace::new();
+    // every asymptote of tan on the window, exact in terms of π
+    let tan: Vec<String> = discontinuities(&bank, &Expression::from("tan(x)"), -5.0, 5.0).iter().map(|p| p.x.to_string()).collect();
+    assert_eq!(tan, vec!["(-3/2)(π)", "(-1/2)(π)", "(1/2)(π)", "(3/2)(π)"]);
+
+    // ln(x) is undefined left of 0 but only 0 itself is a discontinuity
+    let log = discontinuities(&bank, &Expression::from("ln(x)"), -5.0, 5.0);
+    assert_eq!(log.len(), 1);
+    assert_eq!(log[0].kind, Discontinuity::Infinite);
+    assert_eq!(log[0].right, f64::NEG_INFINITY);
+
+    // a piecewise function can jump where it switches pieces without blowing up
+    let step = discontinuities(&bank, &Expression::from("{x if x<1; x+1 if x>=1}"), -5.0, 5.0);
+    assert_eq!(step.len(), 1);
+    assert_eq!((step[0].x.to_string(), step[0].kind), (String::from("1"), Discontinuity::Jump));
+    assert!(discontinuities(&bank, &Expression::from("{x if x<1; 2x-1 if x>=1}"), -5.0, 5.0).is_empty());
+
+    // and floor steps at every whole number
+    let floor: Vec<String> = discontinuities(&bank, &Expression::from("floor(x)"), -0.5, 2.5).iter().map(|p| p.x.to_string()).collect();
+    assert_eq!(floor, vec!["0", "1", "2"]);
+    assert!(discontinuities(&bank, &Expression::from("floor(x)"), 0.0, 2.0).iter().all(|p| p.kind == Discontinuity::Jump));
+    assert_eq!(discontinuities(&bank, &Expression::from("ceil(x/2)"), 0.0, 5.0).len(), 2);
+}
+
+#[test]
+fn asymptote_lines() {
+    let mut bank = ap_calc::new_bank('f', "(x^2-1)/(x-1)");
+    ap_calc::add_func_to_bank(&mut bank, 'g', "(2x^2+1)/(x^2-4)");
+    let lines = |s: &str| asymptotes(&bank, &Expression::from(s), -10.0, 10.0).iter().map(|a| a.to_string()).collect::<Vec<_>>();
+    assert_eq!(lines("g(x)"), vec!["x = -2", "x = 2", "y = 2"]);
+    assert_eq!(lines("(x^2+1)/x"), vec!["x = 0", "y = x"]);
+    assert_eq!(lines("(2x^2-3x)/(x+1)"), vec!["x = -1", "y = 2x - 5"]);
+    assert_eq!(lines("e^(x)"), vec!["y = 0"]);
+    // a hole isn't an asymptote and neither is the line the graph already is
+    assert!(lines("f(x)").is_empty());
+}
@@ -0,0 +1,66 @@
+use ap_calc::Expression;
+use ap_calc::curves::{ParametricCurve, PolarCurve};
+use ap_calc::simplify::simplify;
+
+#[test]
+fn parametric_derivatives() {
+    let mut bank = ap_calc::new_bank('f', "x^2");
+    ap_calc::add_func_to_bank(&mut bank, 'g', "x^3-3x");
+    let curve = ParametricCurve::new(&bank, 'f', 'g');
+    // dy/dx = (3t^2-3)/(2t), at t = 2 that's 9/4
+    assert_eq!(curve.dydx(&bank).approximate(&bank, 2.0), 2.25);
+    // d/dt (dy/dx) = 3/2 + 3/(2t^2), over dx/dt = 2t
+    assert!((curve.second_derivative(&bank).approximate(&bank, 1.0) - 1.5).abs() < 1e-12);
+    assert_eq!(curve.point(&bank, 2.0), (4.0, 2.0));
+
+    let horizontal = curve.horizontal_tangents(&bank, -3.0, 3.0);
+    assert_eq!(horizontal.iter().map(|t| t.point).collect::<Vec<_>>(), vec![(1.0, 2.0), (1.0, -2.0)]);
+    let vertical = curve.vertical_tangents(&bank, -3.0, 3.0);
+    assert_eq!(vertical.len(), 1);
+    assert_eq!(vertical[0].t.exact, Some(Expression::from("0")));
+}
+
+#[test]
+fn speed_and_length() {
+    let mut bank = ap_calc::new_bank('c', "2cos(x)");
+    ap_calc::add_func_to_bank(&mut bank, 's', "2sin(x)");
+    ap_calc::add_func_to_bank(&mut bank, 'r', "1+cos(x)");
+    let circle = ParametricCurve::new(&bank, 'c', 's');
+    assert_eq!(circle.speed(&bank), Expression::from("2"));
+    assert_eq!(circle.arc_length(&bank, &Expression::from("0"), &Expression::from("2pi")).exact, Some(simplify(&Expression::from("4π"))));
+
+    // the cardioid is 8 long all the way around
+    let cardioid = PolarCurve::new(&bank, 'r');
+    assert!((cardioid.arc_length(&bank, &Expression::from("0"), &Expression::from("2pi")).value - 8.0).abs() < 1e-6);
+}
+
+#[test]
+fn polar_curves() {
+    let mut bank = ap_calc::new_bank('r', "1+cos(x)");
+    ap_calc::add_func_to_bank(&mut bank, 'o', "3cos(x)");
+    let cardioid = PolarCurve::new(&bank, 'r');
+    let circle = PolarCurve::new(&bank, 'o');
+
+    // r' = 0 at θ = 0 so the tangent there is vertical
+    assert!(cardioid.dydx(&bank).approximate(&bank, 0.0).is_infinite());
+    // the cusp at θ = π has both derivatives 0 and isn't counted
+    let horizontal = cardioid.horizontal_tangents(&bank, 0.0, 2.0 * std::f64::consts::PI);
+    let angles: Vec<String> = horizontal.iter().map(|t| t.t.to_string()).collect();
+    assert_eq!(angles, vec!["(1/3)(π)", "(5/3)(π)"]);
+
+    let area = cardioid.area(&bank, &Expression::from("0"), &Expression::from("2pi"));
+    assert_eq!(area.exact, Some(simplify(&Expression::from("(3/2)π"))));
+
+    let meet = circle.intersections(&bank, &cardioid, -1.6, 1.6);
+    assert_eq!(meet.iter().map(|b| b.to_string()).collect::<Vec<_>>(), vec!["(-1/3)(π)", "(1/3)(π)"]);
+    let between = circle.area_between(&bank, &cardioid, &Expression::from("-pi/3"), &Expression::from("pi/3"));
+    assert_eq!(between.exact, Some(Expression::from("π")));
+    assert!((between.value - std::f64::consts::PI).abs() < 1e-9);
+
+    // the π and root 3 pieces of each end cancel down to π
+    let mut bank = ap_calc::new_bank('r', "3sin(x)");
+    ap_calc::add_func_to_bank(&mut bank, 'q', "1+sin(x)");
+    let outer = PolarCurve::new(&bank, 'r');
+    let between = outer.area_between(&bank, &PolarCurve::new(&bank, 'q'), &Expression::from("pi/6"), &Expression::from("5pi/6"));
+    assert_eq!(between.exact, Some(Expression::from("π")));
+}
@@ -0,0 +1,86 @@
+use ap_calc::Expression;
+use ap_calc::diffeq;
+use fraction::Fraction;
+use ap_calc::simplify::simplify;
+
+#[test]
+fn euler_table() {
+    let bank = ap_calc::new_bank('f', "x");
+    let dydx = Expression::from("x+y");
+    let steps = diffeq::euler(&bank, &dydx, 0.0, 1.0, 0.5, 2);
+
+    println!("{}", diffeq::table(&steps));
+    assert_eq!(steps[1].y, 1.5);
+    assert_eq!(steps[2].y, 2.5);
+    assert_eq!(steps[1].slope, 2.0);
+}
+
+#[test]
+fn runge_kutta() {
+    let bank = ap_calc::new_bank('f', "x");
+    let dydx = Expression::from("y");
+
+    let rk4 = diffeq::rk4(&bank, &dydx, 0.0, 1.0, 0.1, 10);
+    assert!((rk4[10].y - std::f64::consts::E).abs() < 1e-5);
+
+    let rk45 = diffeq::rk45(&bank, &dydx, 0.0, 1.0, 1.0, 1e-8);
+    let last = rk45.last().unwrap();
+    assert!((last.x - 1.0).abs() < 1e-12);
+    assert!((last.y - std::f64::consts::E).abs() < 1e-6);
+    println!("{}", diffeq::table(&rk45));
+}
+
+#[test]
+fn slope_field() {
+    let bank = ap_calc::new_bank('f', "x");
+    let field = diffeq::slope_field(&bank, &Expression::from("x-y"), (-1.0, 1.0), (-1.0, 1.0), 1.0);
+
+    assert_eq!(field.len(), 9);
+    for slope in &field {
+        assert_eq!(slope.slope, slope.x - slope.y);
+    }
+}
+
+#[test]
+fn separable() {
+    let bank = ap_calc::new_bank('f', "x");
+
+    // dy/dx = x/y, y(0) = -3 takes the negative root
+    let solution = diffeq::solve_separable(&bank, &Expression::from("x/y"), Fraction::from(0), Fraction::from(-3)).unwrap();
+    assert_eq!(solution.constant, Expression::Constant(Fraction::new(9u64, 2u64)));
+    let y = solution.explicit.unwrap();
+    assert!((y.approximate(&bank, 4.0) + 5.0).abs() < 1e-9);
+
+    // dy/dx = cos(x)y, y(0) = 1
+    let solution = diffeq::solve_separable(&bank, &Expression::from("cos(x)y"), Fraction::from(0), Fraction::from(1)).unwrap();
+    assert_eq!(solution.explicit.unwrap(), Expression::from("e^(sin(x))"));
+
+    assert!(diffeq::solve_separable(&bank, &Expression::from("x+y"), Fraction::from(0), Fraction::from(1)).is_none());
+}
+
+#[test]
+fn exponential_and_logistic() {
+    let bank = ap_calc::new_bank('f', "x");
+
+    let decay = diffeq::solve_separable_in(&bank, &Expression::from("-0.2y"), ('t', 'y'), Fraction::from(0), Fraction::from(5)).unwrap();
+    assert_eq!(decay.explicit, Some(simplify(&Expression::from("5e^((-1/5)t)"))));
+    assert_eq!(decay.is_growth(&bank), Some(false));
+
+    let logistic = diffeq::solve_separable_in(&bank, &Expression::from("0.5P(1-P/10)"), ('t', 'P'), Fraction::from(0), Fraction::from(2)).unwrap();
+    assert_eq!(logistic.explicit, Some(simplify(&Expression::from("10/(1+4e^((-1/2)t))"))));
+    let analysis = logistic.logistic_analysis(&bank).unwrap();
+    assert_eq!(analysis.limit, 10.0);
+    assert_eq!(analysis.fastest_at, 5.0);
+    assert_eq!(analysis.max_rate, 1.25);
+    assert!((analysis.time_of_fastest.unwrap() - 2.0 * 4f64.ln()).abs() < 1e-12);
+
+    // kP(L-P) has rate kL
+    let logistic = diffeq::solve_separable_in(&bank, &Expression::from("2P(100-P)"), ('t', 'P'), Fraction::from(0), Fraction::from(10)).unwrap();
+    match logistic.family {
+        diffeq::Family::Logistic { rate, capacity } => {
+            assert_eq!(rate, Expression::from("200"));
+            assert_eq!(capacity, Expression::from("100"));
+        },
+        _ => panic!("not logistic"),
+    }
+}
@@ -0,0 +1,65 @@
+use ap_calc::Expression;
+use ap_calc::domain::{domain, range};
+
+#[test]
+fn domains() {
+    let mut bank = ap_calc::new_bank('f', "(x^2-1)/(x-1)");
+    ap_calc::add_func_to_bank(&mut bank, 'g', "ln(x^2-4)");
+    let d = |s: &str| domain(&bank, &Expression::from(s)).to_string();
+    assert_eq!(d("x^2"), "(-∞, ∞)");
+    assert_eq!(d("f(x)"), "(-∞, 1) ∪ (1, ∞)");
+    assert_eq!(d("g(x)"), "(-∞, -2) ∪ (2, ∞)");
+    assert_eq!(d("(4-x^2)^(1/2)"), "[-2, 2]");
+    assert_eq!(d("x^(-1/2)"), "(0, ∞)");
+    assert_eq!(d("x^(1/3)"), "(-∞, ∞)");
+    assert_eq!(d("arcsin(x/2)"), "[-2, 2]");
+    assert_eq!(d("ln(x)/(x-2)"), "(0, 2) ∪ (2, ∞)");
+    // x/x is 1 once simplified but still undefined at 0
+    assert_eq!(d("x/x"), "(-∞, 0) ∪ (0, ∞)");
+    assert_eq!(d("(0-x^2)^(1/2)"), "{0}");
+    // only where a piece holds, and a piece's own conditions only count there
+    assert_eq!(d("{x if x<1}"), "(-∞, 1)");
+    assert_eq!(d("{x^2 if x<1; 2x if x>2}"), "(-∞, 1) ∪ (2, ∞)");
+    assert_eq!(d("{1/x if x<1; ln(x) if x>=1}"), "(-∞, 0) ∪ (0, ∞)");
+
+    let tan = domain(&bank, &Expression::from("tan(x)"));
+    assert!(tan.contains(0.0) && tan.contains(3.0));
+    assert!(!tan.contains(std::f64::consts::FRAC_PI_2));
+}
+
+#[test]
+fn domains_that_repeat() {
+    let bank = ap_calc::Workspace::new();
+    let d = |s: &str| domain(&bank, &Expression::from(s));
+    let pi = std::f64::consts::PI;
+
+    // the asymptotes go on past any window
+    let tan = d("tan(x)");
+    assert_eq!(tan.to_string(), "(-∞, ∞), x ≠ (1/2)(π) + kπ");
+    assert!(tan.contains(1000.0) && !tan.contains(65.0 * pi / 2.0) && !tan.contains(-201.0 * pi / 2.0));
+    assert_eq!(d("1/sin(x)").to_string(), "(-∞, ∞), x ≠ kπ");
+    assert_eq!(range(&bank, &Expression::from("tan(x)")).to_string(), "(-∞, ∞)");
+    assert_eq!(range(&bank, &Expression::from("1/cos(x)")).to_string(), "(-∞, -1] ∪ [1, ∞)");
+
+    // sign changes that keep going only get worked out on the window, and say so
+    let log = d("ln(sin(x))");
+    assert_eq!(log.approximate, Some((-100.0, 100.0)));
+    assert!(log.contains(1.0) && !log.contains(4.0));
+    assert!(log.to_string().ends_with(" on [-100, 100]"));
+    assert_eq!(d("ln(x)").approximate, None);
+}
+
+#[test]
+fn ranges() {
+    let bank = ap_calc::new_bank('f', "(x^2-1)/(x-1)");
+    let r = |s: &str| range(&bank, &Expression::from(s)).to_string();
+    assert_eq!(r("x^2"), "[0, ∞)");
+    assert_eq!(r("f(x)"), "(-∞, 2) ∪ (2, ∞)");
+    assert_eq!(r("1/x"), "(-∞, 0) ∪ (0, ∞)");
+    assert_eq!(r("(4-x^2)^(1/2)"), "[0, 2]");
+    assert_eq!(r("1/(x^2+1)"), "(0, 1]");
+    assert_eq!(r("e^(x)"), "(0, ∞)");
+    assert_eq!(r("sin(x)"), "[-1, 1]");
+    assert_eq!(r("arccos(x)"), "[0, π]");
+    assert_eq!(r("(x^2)^(1/2)"), "[0, ∞)");
+}
@@ -0,0 +1,145 @@
+use ap_calc::Expression;
+use ap_calc::derivative::derivative;
+use ap_calc::integral::{antiderivative, improper, integrate, Improper, Method};
+use ap_calc::simplify::simplify;
+use fraction::Fraction;
+
+#[test]
+fn simplifying() {
+    assert_eq!(simplify(&Expression::from("0+1x")), Expression::from("x"));
+    assert_eq!(simplify(&Expression::from("3(x^3/3)")), Expression::from("x^3"));
+    assert_eq!(simplify(&Expression::from("e^(ln(x))-0")), Expression::from("x"));
+    assert_eq!(simplify(&Expression::from("1/2")), simplify(&simplify(&Expression::from("1/2"))));
+}
+
+#[test]
+fn power_rule() {
+    let anti = |s: &str| antiderivative(&Expression::from(s), 'x').unwrap();
+    assert_eq!(anti("3x^2"), Expression::from("x^3"));
+    assert_eq!(anti("5"), Expression::from("5x"));
+    assert_eq!(anti("1/x"), Expression::from("ln(abs(x))"));
+    assert_eq!(anti("1/(2x+1)"), Expression::from("ln(abs(2x+1))/2"));
+    assert_eq!(anti("kx"), Expression::from("k*(x^2/2)"));
+}
+
+#[test]
+fn transcendental() {
+    let anti = |s: &str| antiderivative(&Expression::from(s), 'x').unwrap();
+    assert_eq!(anti("cos(x)"), Expression::from("sin(x)"));
+    assert_eq!(anti("1/(cos(x))^2"), Expression::from("tan(x)"));
+    assert_eq!(anti("1/(1+x^2)"), Expression::from("arctan(x)"));
+    assert_eq!(anti("1/(1-x^2)^(1/2)"), Expression::from("arcsin(x)"));
+    assert_eq!(anti("2^x"), Expression::from("2^x/ln(2)"));
+    assert!(antiderivative(&Expression::from("e^(x^2)"), 'x').is_none());
+}
+
+#[test]
+fn definite_integrals() {
+    let mut bank = ap_calc::new_bank('f', "x^2");
+    ap_calc::add_func_to_bank(&mut bank, 'h', "sin(x)");
+    let integral = |f: &str, a: &str, b: &str| integrate(&bank, &Expression::from(f), &Expression::from(a), &Expression::from(b));
+
+    let area = integral("f(x)", "0", "3");
+    assert_eq!(area.exact, Some(Expression::from("9")));
+    assert_eq!(area.method, Method::FundamentalTheorem);
+
+    assert_eq!(integral("sin(x)", "0", "pi").exact, Some(Expression::from("2")));
+    assert_eq!(integral("e^x", "0", "1").exact, Some(Expression::from("e-1")));
+    assert_eq!(integral("1/(1+x^2)", "0", "1").exact, Some(simplify(&Expression::from("(1/4)pi"))));
+    assert_eq!(format!("{}", integral("h(2x)", "0", "pi/4")), "1/2");
+    assert_eq!(integral("kx", "0", "2").exact, Some(Expression::from("2k")));
+
+    let numeric = integral("e^(x^2)", "0", "1");
+    assert_eq!(numeric.method, Method::Numeric);
+    assert!((numeric.value - 1.4626517459071816).abs() < 1e-9);
+}
+
+#[test]
+fn logs_jumps_and_letters() {
+    let bank = ap_calc::new_bank('f', "e^x");
+    let integral = |f: &str, a: &str, b: &str| integrate(&bank, &Expression::from(f), &Expression::from(a), &Expression::from(b));
+
+    // ln|x| so the integral works left of 0 too
+    let left = integral("1/x", "-2", "-1");
+    assert_eq!(left.method, Method::FundamentalTheorem);
+    assert!((left.value + 2f64.ln()).abs() < 1e-12);
+    assert_eq!(integral("1/x", "1", "e").exact, Some(Expression::from("1")));
+
+    // e is still e when the bank has a letter e without a value
+    assert_eq!(bank.get(&'f').unwrap().evaluate(&bank, Fraction::from(0)), Fraction::from(1));
+
+    // a letter and no antiderivative leaves nothing to give
+    let unknown = integral("k*e^(x^2)", "0", "1");
+    assert!(unknown.exact.is_none() && unknown.value.is_nan());
+
+    // floor jumps at 0, 1 and 2 without blowing up, each step is integrated on its own
+    let steps = integral("floor(x)", "-1", "2");
+    assert_eq!(steps.method, Method::Numeric);
+    assert!(steps.value.abs() < 1e-12);
+    assert_eq!(integral("floor(x)", "0", "2.5").value, 2.0);
+    assert_eq!(integral("floor(x)", "2.5", "0").value, -2.0);
+    // a blow up inside isn't a number
+    assert!(integral("1/x", "-1", "1").value.is_nan());
+}
+
+#[test]
+fn accumulation_functions() {
+    let mut bank = ap_calc::new_bank('f', "x^2");
+    ap_calc::add_accumulation_to_bank(&mut bank, 'g', "t^2", 't', "1");
+    assert_eq!(bank.get(&'g').unwrap().evaluate(&bank, Fraction::from(2)), Fraction::new(7u64, 3u64));
+
+    // FTC part 1 with the chain rule
+    assert_eq!(derivative(&bank, &Expression::from("g(x)")), Expression::from("x^2"));
+    assert_eq!(derivative(&bank, &Expression::from("g(x^2)")), simplify(&Expression::from("x^4(2x)")));
+    assert_eq!(derivative(&bank, &Expression::from("int(t,x,x^2,sin(t))")), simplify(&Expression::from("sin(x^2)(2x)-sin(x)")));
+
+    ap_calc::add_func_to_bank(&mut bank, 'd', "g'(x)");
+    assert_eq!(bank.get(&'d').unwrap().evaluate(&bank, Fraction::from(3)), Fraction::from(9));
+}
+
+#[test]
+fn improper_integrals() {
+    let bank = ap_calc::new_bank('f', "x");
+    let verdict = |f: &str, a: &str, b: &str| improper(&bank, &Expression::from(f), &Expression::from(a), &Expression::from(b));
+    let exact = |verdict: Improper| match verdict {
+        Improper::Converges(definite) => definite.exact.unwrap(),
+        Improper::Diverges => panic!("should converge"),
+    };
+
+    assert_eq!(exact(verdict("1/x^2", "1", "inf")), Expression::from("1"));
+    assert_eq!(exact(verdict("1/x^(1/2)", "0", "1")), Expression::from("2"));
+    assert_eq!(exact(verdict("e^(-x)", "0", "inf")), Expression::from("1"));
+    assert_eq!(exact(verdict("1/(1+x^2)", "-inf", "inf")), Expression::from("π"));
+    assert_eq!(exact(verdict("ln(x)", "0", "1")), Expression::from("-1"));
+
+    assert!(matches!(verdict("1/x", "1", "inf"), Improper::Diverges));
+    assert!(matches!(verdict("1/x", "0", "1"), Improper::Diverges));
+    assert!(matches!(verdict("sin(x)", "0", "inf"), Improper::Diverges));
+    assert_eq!(format!("{}", verdict("1/x^3", "1", "inf")), "converges to 1/2");
+}
+
+#[test]
+fn interior_singularities() {
+    let bank = ap_calc::new_bank('f', "x");
+    let verdict = |f: &str, a: &str, b: &str| improper(&bank, &Expression::from(f), &Expression::from(a), &Expression::from(b));
+
+    // looks like -2 if the blow up at 0 is missed
+    assert!(matches!(verdict("1/x^2", "-1", "1"), Improper::Diverges));
+
+    match verdict("1/(x-1)^(2/3)", "0", "3") {
+        Improper::Converges(definite) => {
+            assert_eq!(definite.exact, Some(simplify(&Expression::from("3+3(2^(1/3))"))));
+            assert!((definite.value - 6.779763149684619).abs() < 1e-9);
+        },
+        Improper::Diverges => panic!("should converge"),
+    }
+
+    // no antiderivative, the tails are done numerically
+    match verdict("e^(-x^2)", "-inf", "inf") {
+        Improper::Converges(definite) => {
+            assert_eq!(definite.method, Method::Numeric);
+            assert!((definite.value - std::f64::consts::PI.sqrt()).abs() < 1e-6);
+        },
+        Improper::Diverges => panic!("should converge"),
+    }
+}
@@ -0,0 +1,54 @@
+use ap_calc::Expression;
+use ap_calc::ParseError;
+use ap_calc::simplify::simplify;
+
+fn same(latex: &str, ascii: &str) {
+    assert_eq!(Expression::from_latex(latex).unwrap(), Expression::from(ascii), "{}", latex);
+}
+
+#[test]
+fn same_trees_as_ascii() {
+    same("\\frac{x+1}{x-1}", "(x+1)/(x-1)");
+    same("\\sqrt[3]{x}+\\sqrt{x^2+1}", "cbrt(x)+sqrt(x^2+1)");
+    same("\\sin^{-1}(x) + \\sin^2 x", "arcsin(x)+(sin(x))^2");
+    same("\\ln x+\\log_{2}(x) - \\log(x)", "ln(x)+log_2(x)-log(x)");
+    same("e^{x}\\cdot 3", "e^x*3");
+    same("\\left(x+1\\right)^{2}", "(x+1)^2");
+    same("\\pi r^2", "πr^2");
+    same("2\\sin 2x", "2sin(2x)");
+    same("\\left|x-1\\right| + \\lfloor x \\rfloor", "|x-1|+floor(x)");
+}
+
+#[test]
+fn integrals_and_derivatives() {
+    same("\\int_0^1 x^2\\,dx", "int(x,0,1,x^2)");
+    same("\\int_{0}^{x} t^{2} \\, dt", "int(t,0,x,t^2)");
+    same("\\frac{d}{dx} f(x)", "f'(x)");
+    same("f''(x)", "f''(x)");
+    assert_eq!(simplify(&Expression::from_latex("\\frac{d}{dx} x^3 + 1").unwrap()), simplify(&Expression::from("3x^2+1")));
+    assert_eq!(simplify(&Expression::from_latex("\\frac{d}{dx}\\left(\\sin(x)\\right)").unwrap()), Expression::from("cos(x)"));
+}
+
+#[test]
+fn round_trips() {
+    for ascii in ["3x+2^x", "{x^2 if x<1; 2x-1 if x>=1}", "sum(n,1,inf,1/n^2)", "log_3(x)+floor(x)+ceil(x)+root(4,x)",
+                  "e^(-x^2)+arctan(x)", "x^(2/3)", "(-2)x", "x-(-2)x", "(-2/3)x+(-1)", "-2^2", "3-2x"] {
+        let e = Expression::from(ascii);
+        assert_eq!(Expression::from_latex(&e.to_latex()).unwrap(), e, "{}", ascii);
+    }
+    same("x^{\\frac{2}{3}}", "x^(2/3)");
+    same("-2x", "(-2)x");
+    same("-\\frac{1}{2}\\sin x", "(-1/2)sin(x)");
+}
+
+#[test]
+fn malformed_latex() {
+    let error = |latex: &str| Expression::from_latex(latex).unwrap_err();
+    assert_eq!(error("\\frac{x"), ParseError { column: 7, message: String::from("the LaTeX ends before the } that closes this") });
+    assert_eq!(error("\\sqrt{").column, 6);
+    assert_eq!(error("1+\\frac{x}").message, "a group is missing at the end");
+    assert_eq!(error("x+\\foo").to_string(), "column 2: \\foo isn't LaTeX that can be read");
+    assert_eq!(error("\\int x \\, dx").message, "only definite integrals can be read, \\int_a^b");
+    assert_eq!(error("\\int_0^1 x^2").message, "an integral needs a dx at the end");
+    assert_eq!(error("  ").column, 2);
+}
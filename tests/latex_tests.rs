@@ -0,0 +1,35 @@
+use ap_calc::Expression;
+use ap_calc::derivative;
+
+fn latex(s: &str) -> String {
+    Expression::from(s).to_latex()
+}
+
+#[test]
+fn minimal_brackets() {
+    assert_eq!(latex("3x+2^x"), "3x + 2^{x}");
+    assert_eq!(latex("(x+1)^3"), "\\left(x + 1\\right)^{3}");
+    assert_eq!(latex("x(x+1)-(x-2)"), "x\\left(x + 1\\right) - \\left(x - 2\\right)");
+    assert_eq!(latex("-x^2"), "-x^{2}");
+    assert_eq!(latex("2*3"), "2 \\cdot 3");
+    assert_eq!(latex("π*r^2"), "\\pi r^{2}");
+}
+
+#[test]
+fn functions_and_fractions() {
+    assert_eq!(latex("(x+1)/(x-1)"), "\\frac{x + 1}{x - 1}");
+    assert_eq!(latex("x^(1/2)"), "x^{\\frac{1}{2}}");
+    assert_eq!(latex("sin(x)^2+cos(2x)"), "\\sin^{2}\\left(x\\right) + \\cos\\left(2x\\right)");
+    assert_eq!(latex("ln(x)-log(x)+log_2(x)"), "\\ln\\left(x\\right) - \\log\\left(x\\right) + \\log_{2}\\left(x\\right)");
+    assert_eq!(latex("f'(x)+f''(x)"), "f'\\left(x\\right) + f''\\left(x\\right)");
+    assert_eq!(latex("sum(n,1,inf,1/n^2)"), "\\sum_{n=1}^{\\infty} \\frac{1}{n^{2}}");
+    assert_eq!(latex("|x-1|+sqrt(x)"), "\\left|x - 1\\right| + \\sqrt{x}");
+}
+
+#[test]
+fn derivation_results() {
+    let bank = ap_calc::new_bank('f', "ln(x)/x");
+    assert_eq!(derivative::derivative(&bank, &Expression::from("x^3-3x")).to_latex(), "3x^{2} - 3");
+    assert_eq!(derivative::derivative(&bank, &Expression::from("f(x)")).to_latex(), "\\frac{1 - \\ln\\left(x\\right)}{x^{2}}");
+    assert_eq!(latex("{x^2 if x<1; 2x-1 if x>=1}"), "\\begin{cases} x^{2} & \\text{if } x < 1 \\\\ 2x - 1 & \\text{if } x \\ge 1 \\end{cases}");
+}
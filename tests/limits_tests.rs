@@ -0,0 +1,31 @@
+use ap_calc::Expression;
+use ap_calc::limits::{self, Side};
+use ap_calc::numeric;
+
+#[test]
+fn limits() {
+    let bank = ap_calc::new_bank('f', "x");
+    let limit = |e: &str, a: f64, side: Side| limits::limit(&bank, &Expression::from(e), a, side);
+
+    assert!((limit("sin(x)/x", 0.0, Side::Both) - 1.0).abs() < 1e-9);
+    assert!((limit("(x^2-1)/(x-1)", 1.0, Side::Both) - 2.0).abs() < 1e-9);
+    assert!((limit("(3x^2+1)/(x^2-5)", f64::INFINITY, Side::Both) - 3.0).abs() < 1e-9);
+    assert_eq!(limit("1/x", 0.0, Side::Left), f64::NEG_INFINITY);
+    assert_eq!(limit("1/x", 0.0, Side::Right), f64::INFINITY);
+    assert!(limit("1/x", 0.0, Side::Both).is_nan());
+}
+
+#[test]
+fn roots_and_exact_values() {
+    let roots = numeric::roots(|x| x * x - 2.0, -2.0, 2.0);
+    assert_eq!(roots.len(), 2);
+    assert!((roots[1] - 2f64.sqrt()).abs() < 1e-12);
+
+    // touching 0 counts, crossing a pole doesn't
+    assert_eq!(numeric::roots(|x| (x - 1.0) * (x - 1.0), 0.0, 3.0).len(), 1);
+    assert!(numeric::roots(|x| 1.0 / x, -1.0, 1.5).is_empty());
+
+    assert_eq!(limits::exact_value(std::f64::consts::FRAC_PI_2, 1e-9), Some(ap_calc::simplify::simplify(&Expression::from("(1/2)π"))));
+    assert_eq!(limits::exact_value(0.75, 1e-9), Some(ap_calc::simplify::simplify(&Expression::from("3/4"))));
+    assert_eq!(limits::exact_value(2f64.sqrt(), 1e-9), None);
+}
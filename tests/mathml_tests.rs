@@ -0,0 +1,52 @@
+use ap_calc::Expression;
+
+fn inner(mathml: String) -> String {
+    let start = "<math xmlns=\"http://www.w3.org/1998/Math/MathML\">";
+    assert!(mathml.starts_with(start) && mathml.ends_with("</math>"));
+    mathml[start.len()..mathml.len() - "</math>".len()].to_string()
+}
+
+// every tag that opens closes, in order
+fn balanced(xml: &str) -> bool {
+    let mut open = Vec::new();
+    for tag in xml.split('<').skip(1).map(|t| &t[..t.find('>').unwrap()]) {
+        if tag.ends_with('/') {
+            continue;
+        }
+        match tag.strip_prefix('/') {
+            Some(name) => if open.pop() != Some(name.to_string()) { return false },
+            None => open.push(tag.split(' ').next().unwrap().to_string()),
+        }
+    }
+    open.is_empty()
+}
+
+#[test]
+fn presentation() {
+    let mathml = |s: &str| inner(Expression::from(s).to_mathml());
+    assert_eq!(mathml("3x+2^x"), "<mrow><mrow><mn>3</mn><mo>&#x2062;</mo><mi>x</mi></mrow><mo>+</mo><msup><mn>2</mn><mi>x</mi></msup></mrow>");
+    assert_eq!(mathml("(x+1)/2"), "<mfrac><mrow><mi>x</mi><mo>+</mo><mn>1</mn></mrow><mn>2</mn></mfrac>");
+    assert_eq!(mathml("f''(x)"), "<mrow><msup><mi>f</mi><mo>″</mo></msup><mo>&#x2061;</mo><mrow><mo>(</mo><mi>x</mi><mo>)</mo></mrow></mrow>");
+    assert_eq!(mathml("log_2(x)"), "<mrow><msub><mi>log</mi><mn>2</mn></msub><mo>&#x2061;</mo><mrow><mo>(</mo><mi>x</mi><mo>)</mo></mrow></mrow>");
+    assert_eq!(mathml("(x+1)^2"), "<msup><mrow><mo>(</mo><mrow><mi>x</mi><mo>+</mo><mn>1</mn></mrow><mo>)</mo></mrow><mn>2</mn></msup>");
+}
+
+#[test]
+fn content() {
+    let mathml = |s: &str| inner(Expression::from(s).to_content_mathml());
+    assert_eq!(mathml("3x+sin(x)"), "<apply><plus/><apply><times/><cn type=\"integer\">3</cn><ci>x</ci></apply><apply><sin/><ci>x</ci></apply></apply>");
+    assert_eq!(mathml("ln(x)-log_b(x)"), "<apply><minus/><apply><ln/><ci>x</ci></apply><apply><log/><logbase><ci>b</ci></logbase><ci>x</ci></apply></apply>");
+    assert_eq!(mathml("f'(x)"), "<apply><apply><diff/><ci type=\"function\">f</ci></apply><ci>x</ci></apply>");
+    assert_eq!(mathml("1/2"), "<cn type=\"rational\">1<sep/>2</cn>");
+    assert_eq!(mathml("-x"), "<apply><minus/><ci>x</ci></apply>");
+}
+
+#[test]
+fn every_operation() {
+    let all = "x+y-z*w/2+(x^2)^π+f(x)+g'(x)+sin(x)+arctan(x)+ln(x)+log(x)+log_3(x)+sum(n,1,inf,1/n^2)+int(t,0,x,t^2)
+        +{x if 0<=x<2; 5 if x=2}+|x|+floor(x)+ceil(x)+sqrt(x)+cbrt(x)+root(4,x)+e^x+1/2+(0-3)".replace(char::is_whitespace, "");
+    let e = Expression::from(&all);
+    assert!(balanced(&e.to_mathml()));
+    assert!(balanced(&e.to_content_mathml()));
+    assert!(e.to_content_mathml().contains("<piecewise><piece><ci>x</ci><apply><and/>"));
+}
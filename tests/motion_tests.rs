@@ -0,0 +1,61 @@
+use ap_calc::Expression;
+use ap_calc::motion::{Motion, Speed};
+use ap_calc::simplify::simplify;
+
+#[test]
+fn direction_and_speed() {
+    let mut bank = ap_calc::new_bank('v', "x^2-4x+3");
+    ap_calc::add_func_to_bank(&mut bank, 'w', "cos(x)");
+    let particle = Motion::from_velocity(&bank, 'v', &Expression::from("0"), &Expression::from("2"));
+    assert_eq!(particle.acceleration, Expression::from("2x-4"));
+
+    let turns: Vec<String> = particle.direction_changes(&bank, 0.0, 5.0).iter().map(|t| t.to_string()).collect();
+    assert_eq!(turns, vec!["1", "3"]);
+
+    let speed: Vec<(String, String, Speed)> = particle.speed(&bank, 0.0, 5.0).into_iter()
+        .map(|i| (i.start.to_string(), i.end.to_string(), i.speed))
+        .collect();
+    assert_eq!(speed, vec![
+        (String::from("0"), String::from("1"), Speed::SlowingDown),
+        (String::from("1"), String::from("2"), Speed::SpeedingUp),
+        (String::from("2"), String::from("3"), Speed::SlowingDown),
+        (String::from("3"), String::from("5"), Speed::SpeedingUp),
+    ]);
+    assert!(particle.speeding_up_at(&bank, 4.0));
+    assert!(!particle.speeding_up_at(&bank, 0.5));
+
+    // cos(t) turns around at π/2 and 3π/2
+    let wave = Motion::from_velocity(&bank, 'w', &Expression::from("0"), &Expression::from("0"));
+    assert_eq!(wave.direction_changes(&bank, 0.0, 2.0 * std::f64::consts::PI).len(), 2);
+}
+
+#[test]
+fn displacement_and_distance() {
+    let mut bank = ap_calc::new_bank('v', "x^2-4x+3");
+    ap_calc::add_func_to_bank(&mut bank, 'w', "cos(x)");
+    let particle = Motion::from_velocity(&bank, 'v', &Expression::from("0"), &Expression::from("2"));
+    let (start, end) = (Expression::from("0"), Expression::from("5"));
+
+    assert_eq!(particle.displacement(&bank, &start, &end).exact, Some(simplify(&Expression::from("20/3"))));
+    let distance = particle.total_distance(&bank, &start, &end);
+    assert_eq!(distance.exact, Some(simplify(&Expression::from("28/3"))));
+    assert!((distance.value - 28.0 / 3.0).abs() < 1e-12);
+
+    let wave = Motion::from_velocity(&bank, 'w', &Expression::from("0"), &Expression::from("0"));
+    assert_eq!(wave.total_distance(&bank, &Expression::from("0"), &Expression::from("2pi")).exact, Some(Expression::from("4")));
+}
+
+#[test]
+fn position() {
+    let mut bank = ap_calc::new_bank('v', "x^2-4x+3");
+    ap_calc::add_func_to_bank(&mut bank, 's', "x^3-6x^2+9x");
+    let particle = Motion::from_velocity(&bank, 'v', &Expression::from("0"), &Expression::from("2"));
+    // 2 + 9 - 18 + 9
+    assert_eq!(particle.position_at(&bank, &Expression::from("3")).exact, Some(Expression::from("2")));
+    assert!((particle.position.approximate(&bank, 3.0) - 2.0).abs() < 1e-9);
+
+    let particle = Motion::from_position(&bank, 's');
+    assert_eq!(particle.velocity.approximate(&bank, 1.0), 0.0);
+    assert_eq!(particle.position_at(&bank, &Expression::from("1")).exact, Some(Expression::from("4")));
+    assert_eq!(particle.total_distance(&bank, &Expression::from("0"), &Expression::from("4")).exact, Some(Expression::from("12")));
+}
@@ -1,3 +1,5 @@
+#![allow(unused_imports, unused_variables, unused_mut)]
+
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
@@ -150,3 +152,32 @@ mod derivative_tests {
         println!("f''(5) = {}", bank.get(&'d').unwrap().evaluate(&bank, Fraction::from(5)));
     }
 }
+
+// what the parser picked up alongside sums: 3x^2 is 3(x^2), a leading minus, inf and
+// brackets around brackets, and Display writing logs and powers so they read back the same
+#[test]
+fn parsing_for_sums() {
+    use ap_calc::Expression;
+    assert_eq!(Expression::from("3x^2"), Expression::from("3(x^2)"));
+    assert_eq!(Expression::from("-x^2"), Expression::from("0-(x^2)"));
+    assert_eq!(Expression::from("((x+1))"), Expression::from("x+1"));
+    assert_eq!(Expression::from("inf"), Expression::Constant(Fraction::infinity()));
+    for text in ["log_2(x)", "(x+1)^2", "2^(x-1)"] {
+        let e = Expression::from(text);
+        assert_eq!(e, Expression::from(&e.to_string()), "{}", text);
+    }
+}
+
+#[test]
+fn parse_errors_point_at_the_character() {
+    use ap_calc::{Expression, ParseError};
+    let error = |text: &str| Expression::parse(text).unwrap_err();
+    assert_eq!(error("x*-1"), ParseError { column: 2, message: String::from("a negative after * needs brackets, like *(-1)") });
+    assert_eq!(error("2 ^ -x").column, 4);
+    assert_eq!(error("1+sum(n,1,n^2)").message, "sum needs an index, two bounds and a summand, sum(n,a,b,...)");
+    assert_eq!(error("1+sum(n,1,n^2)").column, 2);
+    assert_eq!(error("root(x)").column, 0);
+    assert_eq!(error("{x if x<; 1 if x>=1}"), ParseError { column: 7, message: String::from("< needs something after it") });
+    assert_eq!(Expression::parse("x*(-1)"), Ok(Expression::from("x*(-1)")));
+    assert_eq!(Expression::parse(" 2 x "), Ok(Expression::from("2x")));
+}
@@ -0,0 +1,59 @@
+use ap_calc::Expression;
+use ap_calc::{derivative, integral, piecewise, simplify};
+use fraction::Fraction;
+
+#[test]
+fn parsing_and_evaluating() {
+    let mut bank = ap_calc::new_bank('f', "{x^2 if x<1; 2x-1 if x>=1}");
+    ap_calc::add_func_to_bank(&mut bank, 'h', "{x if -1<=x<2; 5 if x=2}");
+    let f = Expression::from("{ x^2 if x<1; 2x-1 if x>=1 }");
+    assert_eq!(Expression::from(&format!("{}", f)), f);
+    assert_eq!(Expression::from("f(x)").evaluate(&bank, Fraction::new(1u64, 2u64)), Fraction::new(1u64, 4u64));
+    assert_eq!(Expression::from("f(x)").evaluate(&bank, Fraction::from(3)), Fraction::from(5));
+    assert_eq!(Expression::from("h(x)").evaluate(&bank, Fraction::from(2)), Fraction::from(5));
+    assert!(Expression::from("h(x)").approximate(&bank, 3.0).is_nan());
+}
+
+#[test]
+fn derivatives_and_breakpoints() {
+    let mut bank = ap_calc::new_bank('f', "{x^2 if x<1; 2x-1 if x>=1}");
+    ap_calc::add_func_to_bank(&mut bank, 'g', "{x^2 if x<1; x if x>=1}");
+    ap_calc::add_func_to_bank(&mut bank, 'h', "{x if -1<=x<2; 5 if x=2}");
+    let slope = derivative::derivative(&bank, &Expression::from("f(x)"));
+    assert_eq!(slope, simplify::simplify(&Expression::from("{2x if x<1; 2 if x>=1}")));
+    let f = piecewise::breakpoints(&bank, &Expression::from("f(x)"));
+    assert_eq!(f.len(), 1);
+    assert!(f[0].continuous && f[0].differentiable);
+
+    // a corner: the derivative leaves out x = 1
+    let slope = derivative::derivative(&bank, &Expression::from("g(x)"));
+    assert_eq!(slope, simplify::simplify(&Expression::from("{2x if x<1; 1 if x>1}")));
+    assert!(slope.approximate(&bank, 1.0).is_nan());
+    let g = piecewise::breakpoints(&bank, &Expression::from("g(x)"));
+    assert!(g[0].continuous && !g[0].differentiable);
+    assert_eq!((g[0].left_slope, g[0].right_slope), (2.0, 1.0));
+
+    // a jump
+    let h = piecewise::breakpoints(&bank, &Expression::from("h(x)"));
+    assert_eq!(h[1].x.value, 2.0);
+    assert_eq!((h[1].left, h[1].value), (2.0, 5.0));
+    assert!(!h[1].continuous);
+}
+
+#[test]
+fn integrating_across_breakpoints() {
+    let mut bank = ap_calc::new_bank('f', "{x^2 if x<1; 2x-1 if x>=1}");
+    ap_calc::add_func_to_bank(&mut bank, 'g', "{x^2 if x<1; x if x>=1}");
+    let area = integral::integrate(&bank, &Expression::from("f(x)"), &Expression::from("0"), &Expression::from("2"));
+    assert_eq!(area.exact, Some(Expression::from("7/3")).map(|e| simplify::simplify(&e)));
+    let area = integral::integrate(&bank, &Expression::from("g(x)"), &Expression::from("2"), &Expression::from("0"));
+    assert_eq!(area.exact, Some(Expression::from("-11/6")).map(|e| simplify::simplify(&e)));
+
+    // pieces that leave a stretch uncovered give NaN rather than a panic
+    let empty = ap_calc::Workspace::new();
+    for (e, b) in [("{x if x<1}", "2"), ("{x^2 if x<1; 2x if x>2}", "3")] {
+        let area = integral::integrate(&empty, &Expression::from(e), &Expression::from("0"), &Expression::from(b));
+        assert!(area.value.is_nan() && area.exact.is_none(), "{}", e);
+    }
+    assert!(Expression::from("{x if x<1}").evaluate(&empty, Fraction::from(2)).is_nan());
+}
@@ -0,0 +1,40 @@
+use ap_calc::Expression;
+use ap_calc::derivative;
+
+#[test]
+fn minimal_brackets() {
+    let pretty = |s: &str| Expression::from(s).pretty();
+    assert_eq!(pretty("((3)(x))+((2)^(x))"), "3x+2^x");
+    assert_eq!(pretty("x-(y-z)"), "x-(y-z)");
+    assert_eq!(pretty("(x+1)/(x-1)"), "(x+1)/(x-1)");
+    assert_eq!(pretty("x^(-2)"), "x^(-2)");
+    assert_eq!(pretty("x*(1/2)"), "x*1/2");
+    assert_eq!(pretty("{x^2 if x<1; 2x-1 if x>=1}"), "{x^2 if x<1; 2x-1 if x>=1}");
+}
+
+#[test]
+fn unicode() {
+    let unicode = |s: &str| Expression::from(s).unicode();
+    assert_eq!(unicode("2x^3-3x+1"), "2x³-3x+1");
+    assert_eq!(unicode("sqrt(x)+cbrt(x+1)"), "√x+∛(x+1)");
+    assert_eq!(unicode("π*r^2+x^(-1)"), "πr²+x⁻¹");
+    assert_eq!(unicode("f'(x)+2*3"), "f′(x)+2·3");
+    assert_eq!(Expression::from("√x+x²"), Expression::from("sqrt(x)+x^2"));
+}
+
+#[test]
+fn round_trips() {
+    let bank = ap_calc::new_bank('f', "x^2e^x");
+    let mut all: Vec<Expression> = ["x-y-z", "x+y-z", "(x+y)-z", "-x^2", "0-3", "(0-x)y", "x(x+1)", "(1/2)x", "x/(2y)", "(x/2)y",
+        "2/3", "(2)/(3)", "x^2^3", "x^(2^3)", "sin(x)^2+cos(2x)", "log_2(x)", "f''(x)", "sum(n,1,inf,1/n^2)", "int(t,0,x,t^2)",
+        "{x if 0<=x<2; 5 if x=2}", "|x-1|+floor(x)+root(4,x)", "sqrt(2)x"].iter().map(|s| Expression::from(s)).collect();
+    all.push(derivative::derivative(&bank, &Expression::from("f(x)")));
+    for e in all {
+        assert_eq!(Expression::from(&e.pretty()), e, "{}", e.pretty());
+        assert_eq!(Expression::from(&e.unicode()), e, "{}", e.unicode());
+    }
+    assert_eq!(Expression::from("(x^2+1)/(x-1)").layout(), "  2\n x  + 1\n--------\n x - 1");
+    // a power in a numerator is centered as one piece, the exponent stays over its base
+    assert_eq!(Expression::from("x^2/(x+1)").layout(), "   2\n  x\n-------\n x + 1");
+    assert_eq!(Expression::from("e^(x^2)/2").layout(), "   2\n  x\n e\n-----\n  2");
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "serde")]
+use ap_calc::*;
+use fraction::Fraction;
+
+#[test]
+fn the_json_schema() {
+    let json = serde_json::to_string(&Expression::from("3x^2")).unwrap();
+    assert_eq!(r#"{"type":"operation","operation":"mult","children":[{"type":"constant","numerator":"3","denominator":"1"},{"type":"operation","operation":"exp","children":[{"type":"variable","name":"x"},{"type":"constant","numerator":"2","denominator":"1"}]}]}"#, json);
+    let half = serde_json::to_string(&Expression::Constant(Fraction::new_neg(1u64, 2u64))).unwrap();
+    assert_eq!(r#"{"type":"constant","numerator":"-1","denominator":"2"}"#, half);
+    let sum = serde_json::to_value(Expression::from("sum(n,1,10,n^2)")).unwrap();
+    assert_eq!("sum", sum["operation"]);
+    assert_eq!("n", sum["variable"]);
+    assert_eq!("bounds", sum["children"][0]["operation"]);
+}
+
+#[test]
+fn expressions_come_back_the_same() {
+    for text in ["3x^2+1", "sin(x)^2-arctan(1/x)", "f'(x)", "log_2(x)+ln(x)", "int(t,0,x,t^2)", "sum(n,1,10,1/n)",
+        "{x^2 if x<0; x if 0<=x<1; 1 if x>=1}", "|x-1|+floor(x)+ceil(x)", "root(4,x)+sqrt(x)", "1/0", "-2.5x"] {
+        let e = Expression::from(text);
+        let back: Expression = serde_json::from_str(&serde_json::to_string(&e).unwrap()).unwrap();
+        assert_eq!(e, back, "{}", text);
+    }
+    let bad: Result<Expression, _> = serde_json::from_str(r#"{"type":"operation","operation":"add","children":[]}"#);
+    assert!(bad.is_err());
+    let bad: Result<Expression, _> = serde_json::from_str(r#"{"type":"operation","operation":"sum","children":[{"type":"variable","name":"x"},{"type":"variable","name":"x"}]}"#);
+    assert!(bad.is_err());
+}
+
+#[test]
+fn banks_and_results() {
+    let mut bank = new_bank('f', "3x^2+1");
+    add_var_to_bank(&mut bank, 'a', "5");
+    let json = serde_json::to_string(&bank).unwrap();
+    let back: Workspace = serde_json::from_str(&json).unwrap();
+    assert_eq!(Fraction::from(13), back.get(&'f').unwrap().evaluate(&back, Fraction::from(2)));
+    let a = serde_json::to_string(back.get(&'a').unwrap()).unwrap();
+    assert_eq!(r#"{"kind":"variable","value":{"type":"constant","numerator":"5","denominator":"1"}}"#, a);
+
+    let definite = integral::integrate(&bank, &Expression::from("f(x)"), &Expression::from("0"), &Expression::from("1"));
+    let json = serde_json::to_value(&definite).unwrap();
+    assert_eq!(2.0, json["value"]);
+    assert_eq!("fundamental_theorem", json["method"]);
+    assert_eq!("2", json["exact"]["numerator"]);
+}
+
+#[test]
+fn banks_are_checked_when_read() {
+    let function = r#"{"kind":"function","value":{"type":"operation","operation":"func","children":[{"type":"variable","name":"f"},{"type":"variable","name":"x"}]}}"#;
+    let cyclic: Result<Workspace, _> = serde_json::from_str(&format!(r#"{{"f":{}}}"#, function));
+    assert!(cyclic.unwrap_err().to_string().contains("f would depend on itself"));
+    let missing: Result<Workspace, _> = serde_json::from_str(&format!(r#"{{"g":{}}}"#, function));
+    assert!(missing.unwrap_err().to_string().contains("g uses f, which isn't defined"));
+    let x: Result<Workspace, _> = serde_json::from_str(r#"{"x":{"kind":"variable","value":{"type":"constant","numerator":"5","denominator":"1"}}}"#);
+    assert!(x.is_err());
+}
+
+#[test]
+fn analysis_results() {
+    let mut bank = new_bank('f', "x^2");
+    add_func_to_bank(&mut bank, 'r', "1/x");
+
+    // infinite ends and NaN values have no JSON numbers and go as strings
+    let tan = domain::domain(&bank, &Expression::from("tan(x)"));
+    let json = serde_json::to_value(&tan).unwrap();
+    assert_eq!("-inf", json["intervals"][0]["low"]["value"]);
+    assert_eq!(false, json["intervals"][0]["closed_low"]);
+    assert!(json["approximate"].is_null());
+    let back: domain::IntervalSet = serde_json::from_value(json).unwrap();
+    assert_eq!(tan.to_string(), back.to_string());
+
+    let holes = continuity::discontinuities(&bank, &Expression::from("r(x)"), -1.0, 1.0);
+    let json = serde_json::to_value(&holes).unwrap();
+    assert_eq!("infinite", json[0]["kind"]);
+    assert_eq!("-inf", json[0]["left"]);
+    let back: Vec<continuity::Point> = serde_json::from_value(json).unwrap();
+    assert!(back[0].left == f64::NEG_INFINITY && back[0].right == f64::INFINITY);
+    let unknown = integral::integrate(&bank, &Expression::from("k*e^(x^2)"), &Expression::from("0"), &Expression::from("1"));
+    let json = serde_json::to_value(&unknown).unwrap();
+    assert_eq!("nan", json["value"]);
+    assert!(serde_json::from_value::<integral::Definite>(json).unwrap().value.is_nan());
+    let asymptotes = serde_json::to_value(continuity::asymptotes(&bank, &Expression::from("r(x)"), -1.0, 1.0)).unwrap();
+    assert_eq!(0.0, asymptotes[0]["vertical"]["value"]);
+
+    let mvt = theorems::mean_value(&bank, 'f', &Expression::from("0"), &Expression::from("2"));
+    let json = serde_json::to_value(&mvt).unwrap();
+    assert_eq!("mean_value", json["theorem"]);
+    assert_eq!(true, json["hypotheses"][0][1]);
+    let back: theorems::Justification = serde_json::from_value(json).unwrap();
+    assert_eq!(mvt.to_string(), back.to_string());
+
+    let line = tangent::linearization(&bank, 'f', &Expression::from("1"), &Expression::from("1.1")).unwrap();
+    let json = serde_json::to_value(&line).unwrap();
+    assert_eq!("under", json["estimate"]);
+    assert_eq!(json, serde_json::to_value(serde_json::from_value::<tangent::Linearization>(json.clone()).unwrap()).unwrap());
+
+    let tests = serde_json::to_value(series::convergence_tests(&bank, &Expression::from("sum(n,1,inf,1/n^2)"))).unwrap();
+    assert!(tests.as_array().unwrap().iter().any(|test| test["test"] == "p_series" && test["verdict"] == "converges"));
+    let interval = series::interval_of_convergence(&bank, &Expression::from("sum(n,1,inf,x^n/n^n)")).unwrap();
+    let json = serde_json::to_value(&interval).unwrap();
+    assert_eq!("inf", json["radius"]);
+    assert!(json["left"].is_null());
+}
@@ -0,0 +1,39 @@
+use ap_calc::session::*;
+
+const KEY: &str = "\
+# answer key for the quiz
+f(x) = 3x^2+1
+
+f(2)
+f(x) = 3x^+1
+integrate f 0 1
+";
+
+#[test]
+fn lines_run_in_order() {
+    let steps = Session::new().script(KEY);
+    let lines: Vec<usize> = steps.iter().map(|step| step.line).collect();
+    assert_eq!(vec![2, 4, 5, 6], lines);
+    assert_eq!("13", steps[1].result.as_ref().unwrap());
+    assert!(steps[2].result.is_err());
+    // the broken redefinition left f alone
+    assert_eq!("2", steps[3].result.as_ref().unwrap());
+}
+
+#[test]
+fn text_output() {
+    let steps = Session::new().script(KEY);
+    assert_eq!("> f(2)\n13", steps[1].text());
+    assert_eq!("> f(x) = 3x^+1\n           ^ error on line 5: ^ needs something after it", steps[2].text());
+}
+
+#[test]
+#[cfg(feature = "cli")]
+fn json_output() {
+    let steps = Session::new().script(KEY);
+    assert_eq!(r#"{"line":4,"input":"f(2)","output":"13"}"#, format!("{}", steps[1].json()));
+    assert_eq!(r#"{"line":5,"input":"f(x) = 3x^+1","error":{"message":"^ needs something after it","column":10}}"#,
+        format!("{}", steps[2].json()));
+    let table = Session::new().script("table x^2 0 1 1");
+    assert_eq!(r#"{"line":1,"input":"table x^2 0 1 1","output":"x | y\n0 | 0\n1 | 1"}"#, format!("{}", table[0].json()));
+}
@@ -0,0 +1,122 @@
+use fraction::Fraction;
+use ap_calc::Expression;
+use ap_calc::series::{self, Convergence, SeriesTest};
+
+#[test]
+fn sum_round_trips() {
+    let sum = Expression::from("sum(n,1,10,1/n^2)");
+    println!("sum = {}", sum);
+    assert_eq!(sum, ap_calc::copy_expression(&sum));
+}
+
+#[test]
+fn exact_partial_sum() {
+    let bank = ap_calc::new_bank('f', "x");
+    let sum = Expression::from("sum(n,1,inf,1/(n(n+1)))");
+
+    // telescopes to 1 - 1/(N+1)
+    assert_eq!(series::partial_sum(&bank, &sum, 9), Fraction::new(9u64, 10u64));
+    let finite = Expression::from("sum(k,1,4,k^2)");
+    assert_eq!(finite.evaluate(&bank, Fraction::from(0)), Fraction::from(30));
+}
+
+#[test]
+fn sum_of_x() {
+    let bank = ap_calc::new_bank('f', "sum(n,0,3,x^n)");
+    println!("f(x) = {}", bank.get(&'f').unwrap());
+    assert_eq!(bank.get(&'f').unwrap().evaluate(&bank, Fraction::from(2)), Fraction::from(15));
+}
+
+#[test]
+fn basic_tests() {
+    let bank = ap_calc::new_bank('f', "x");
+    let geometric = Expression::from("sum(n,0,inf,3(1/2)^n)");
+    let p = Expression::from("sum(n,1,inf,1/n^2)");
+    let harmonic = Expression::from("sum(n,1,inf,1/n)");
+
+    assert_eq!(series::geometric_test(&bank, &geometric).verdict, Convergence::Converges);
+    assert!((geometric.approximate(&bank, 0.0) - 6.0).abs() < 1e-9);
+    assert_eq!(series::p_series_test(&bank, &p).verdict, Convergence::Converges);
+    assert_eq!(series::p_series_test(&bank, &harmonic).verdict, Convergence::Diverges);
+    assert_eq!(series::integral_test(&bank, &harmonic).verdict, Convergence::Diverges);
+    assert_eq!(series::integral_test(&bank, &p).verdict, Convergence::Converges);
+}
+
+#[test]
+fn limit_tests() {
+    let bank = ap_calc::new_bank('f', "x");
+    let ratio = Expression::from("sum(n,1,inf,n/3^n)");
+    let alternating = Expression::from("sum(n,1,inf,(-1)^n/n)");
+    let nth = Expression::from("sum(n,1,inf,n/(n+1))");
+
+    assert_eq!(series::ratio_test(&bank, &ratio).verdict, Convergence::Converges);
+    assert_eq!(series::root_test(&bank, &ratio).verdict, Convergence::Converges);
+    assert_eq!(series::alternating_series_test(&bank, &alternating).verdict, Convergence::Converges);
+    assert_eq!(series::nth_term_test(&bank, &nth).verdict, Convergence::Diverges);
+    for result in series::convergence_tests(&bank, &alternating) {
+        println!("{:?}: {}", result.test, result.reason);
+    }
+}
+
+#[test]
+fn comparisons() {
+    let bank = ap_calc::new_bank('f', "x");
+    let a = Expression::from("sum(n,1,inf,1/(n^2+1))");
+    let b = Expression::from("sum(n,1,inf,1/n^2)");
+    let c = Expression::from("sum(n,1,inf,1/(2n+3))");
+
+    assert_eq!(series::comparison_test(&bank, &a, &b).verdict, Convergence::Converges);
+    assert_eq!(series::limit_comparison_test(&bank, &a, &b).verdict, Convergence::Converges);
+    assert_eq!(series::converges(&bank, &c), Convergence::Diverges);
+}
+
+#[test]
+fn power_series() {
+    let bank = ap_calc::new_bank('f', "x");
+    let ln_like = Expression::from("sum(n,1,inf,(x-2)^n/(n3^n))");
+    let interval = series::interval_of_convergence(&bank, &ln_like).unwrap();
+
+    println!("converges on {}", interval);
+    assert!((interval.center - 2.0).abs() < 1e-9);
+    assert!((interval.radius - 3.0).abs() < 1e-6);
+    assert_eq!(interval.left, Some(Convergence::Converges));
+    assert_eq!(interval.right, Some(Convergence::Diverges));
+
+    let exp_like = Expression::from("sum(n,1,inf,x^n/n^n)");
+    assert!(series::interval_of_convergence(&bank, &exp_like).unwrap().radius.is_infinite());
+    assert!(series::interval_of_convergence(&bank, &Expression::from("sum(n,1,inf,1/n^2)")).is_none());
+}
+
+#[test]
+fn terms_that_settle_on_nothing() {
+    let bank = ap_calc::new_bank('f', "x");
+    let wobbly = Expression::from("sum(n,1,inf,sin(n)/n^2)");
+    assert_eq!(series::nth_term_test(&bank, &wobbly).verdict, Convergence::Inconclusive);
+    assert_eq!(series::ratio_test(&bank, &wobbly).verdict, Convergence::Inconclusive);
+    assert_eq!(series::absolute_comparison_test(&bank, &wobbly).verdict, Convergence::Converges);
+    assert_eq!(series::converges(&bank, &wobbly), Convergence::Converges);
+    assert_eq!(series::converges(&bank, &Expression::from("sum(n,1,inf,sin(n))")), Convergence::Inconclusive);
+    assert_eq!(series::converges(&bank, &Expression::from("sum(n,1,inf,0)")), Convergence::Converges);
+}
+
+#[test]
+fn alternating_and_limit_comparison_agree() {
+    let bank = ap_calc::new_bank('f', "x");
+    // 1/ln(n) goes to 0 too slowly for the extrapolation to see
+    let slow = Expression::from("sum(n,2,inf,(-1)^n/ln(n))");
+    assert_eq!(series::alternating_series_test(&bank, &slow).verdict, Convergence::Converges);
+    assert_eq!(series::converges(&bank, &slow), Convergence::Converges);
+    // the alternating series test never says diverges, the nth term test does
+    let big = Expression::from("sum(n,1,inf,(-1)^n*n/(n+1))");
+    assert_eq!(series::alternating_series_test(&bank, &big).verdict, Convergence::Inconclusive);
+    assert_eq!(series::converges(&bank, &big), Convergence::Diverges);
+
+    // (-1)^n/(1/n) = (-1)^n(n) has no limit, and a_n isn't positive anyway
+    let harmonic = Expression::from("sum(n,1,inf,1/n)");
+    let alternating = Expression::from("sum(n,1,inf,(-1)^n/n)");
+    assert_eq!(series::limit_comparison_test(&bank, &alternating, &harmonic).verdict, Convergence::Inconclusive);
+    assert_eq!(series::converges(&bank, &alternating), Convergence::Converges);
+    let results = series::convergence_tests(&bank, &Expression::from("sum(n,1,inf,n/(n+1))"));
+    let limit = results.iter().find(|result| result.test == SeriesTest::LimitComparison).unwrap();
+    assert!(limit.reason.ends_with("with b_n = 1 which diverges"), "{}", limit.reason);
+}
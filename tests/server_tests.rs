@@ -0,0 +1,77 @@
+#![cfg(feature = "cli")]
+use ap_calc::server::Server;
+use serde_json::{json, Value};
+
+fn call(server: &mut Server, method: &str, params: &str) -> Value {
+    let request = format!(r#"{{"jsonrpc":"2.0","method":"{}","params":{},"id":7}}"#, method, params);
+    let response = serde_json::from_str::<Value>(&server.handle(&request).unwrap()).unwrap();
+    assert_eq!(Some(&json!(7)), response.get("id"));
+    response
+}
+
+fn result(server: &mut Server, method: &str, params: &str) -> String {
+    format!("{}", call(server, method, params).get("result").unwrap())
+}
+
+#[test]
+fn methods() {
+    let mut server = Server::new();
+    assert_eq!(r#"{"display":"f(x) = 3x^2+1"}"#, result(&mut server, "define", r#"{"definition":"f(x) = 3x^2+1"}"#));
+    assert_eq!(r#"{"expression":"13","value":13}"#, result(&mut server, "evaluate", r#"{"expression":"f(2)"}"#));
+    assert_eq!(r#"{"expression":"7/4","value":1.75}"#, result(&mut server, "evaluate", r#"{"expression":"f","x":0.5}"#));
+    assert_eq!(r#"{"expression":"6x"}"#, result(&mut server, "differentiate", r#"{"expression":"f"}"#));
+    assert_eq!(r#"{"value":2,"exact":"2"}"#, result(&mut server, "integrate", r#"{"expression":"f","from":0,"to":1}"#));
+    assert_eq!(r#"{"expression":"x^3+x"}"#, result(&mut server, "integrate", r#"{"expression":"f"}"#));
+    assert_eq!(r#"{"expression":"2x"}"#, result(&mut server, "simplify", r#"{"expression":"x+x+0y"}"#));
+    assert_eq!(r#"{"latex":"\\frac{1}{x}"}"#, result(&mut server, "render_latex", r#"{"expression":"1/x"}"#));
+    assert_eq!(r#"{"points":[[-1,-1],[0,null],[1,1]]}"#,
+        result(&mut server, "plot_samples", r#"{"expression":"1/x","from":-1,"to":1,"samples":3}"#));
+}
+
+#[test]
+fn sessions_keep_their_own_bank() {
+    let mut server = Server::new();
+    result(&mut server, "define", r#"{"definition":"a = 2","session":"one"}"#);
+    result(&mut server, "define", r#"{"definition":"a = 3","session":"two"}"#);
+    assert_eq!(r#"{"expression":"2","value":2}"#, result(&mut server, "evaluate", r#"{"expression":"a","session":"one"}"#));
+    assert_eq!(r#"{"expression":"3","value":3}"#, result(&mut server, "evaluate", r#"{"expression":"a","session":"two"}"#));
+    assert_eq!(r#"{"closed":true}"#, result(&mut server, "close", r#"{"session":"one"}"#));
+    assert!(call(&mut server, "evaluate", r#"{"expression":"a","session":"one"}"#).get("error").is_some());
+}
+
+#[test]
+fn errors_are_structured() {
+    let mut server = Server::new();
+    let error = format!("{}", call(&mut server, "evaluate", r#"{"expression":"3x^+1"}"#).get("error").unwrap());
+    assert_eq!(r#"{"code":-32000,"message":"^ needs something after it","data":{"message":"^ needs something after it","column":3,"param":"expression"}}"#, error);
+    let error = call(&mut server, "solve", "{}");
+    assert_eq!(Some(&json!(-32601)), error.get("error").unwrap().get("code"));
+    let error = call(&mut server, "integrate", r#"{"expression":"x","from":0}"#);
+    assert_eq!(Some(&json!(-32602)), error.get("error").unwrap().get("code"));
+
+    assert_eq!(Some(String::from(r#"{"jsonrpc":"2.0","error":{"code":-32700,"message":"key must be a string at line 1 column 2"},"id":null}"#)), server.handle("{oops"));
+    let lone = server.handle(r#"{"jsonrpc":"2.0","method":"evaluate","params":{"expression":"\ud800"},"id":1}"#).unwrap();
+    assert!(lone.contains("-32700"));
+    // notifications get no answer, a batch gets one for each of its requests
+    assert_eq!(None, server.handle(r#"{"jsonrpc":"2.0","method":"evaluate","params":{"expression":"1"}}"#));
+    let batch = server.handle(r#"[{"jsonrpc":"2.0","method":"evaluate","params":{"expression":"1"},"id":1},{"jsonrpc":"2.0","method":"evaluate","params":{"expression":"2"}}]"#);
+    assert_eq!(Some(String::from(r#"[{"jsonrpc":"2.0","result":{"expression":"1","value":1},"id":1}]"#)), batch);
+}
+
+#[test]
+fn a_failed_request_leaves_the_server_running() {
+    let mut server = Server::new();
+    for bad in ["sum(n,1,3,k)", "int(t,0,1,k)"] {
+        let error = call(&mut server, "evaluate", &format!(r#"{{"expression":"{}"}}"#, bad));
+        assert_eq!(Some(&json!(-32000)), error.get("error").unwrap().get("code"));
+    }
+    assert_eq!(r#"{"expression":"sum(n,1,3,n^2)","value":14}"#, result(&mut server, "evaluate", r#"{"expression":"sum(n,1,3,n^2)"}"#));
+    assert_eq!(r#"{"expression":"2","value":2}"#, result(&mut server, "evaluate", r#"{"expression":"1+1"}"#));
+
+    // a mistake the parser finds is pointed at, not an internal message
+    let error = format!("{}", call(&mut server, "simplify", r#"{"expression":"x*-1"}"#).get("error").unwrap());
+    assert_eq!(r#"{"code":-32000,"message":"a negative after * needs brackets, like *(-1)","data":{"message":"a negative after * needs brackets, like *(-1)","column":3,"param":"expression"}}"#, error);
+    let error = call(&mut server, "evaluate", r#"{"expression":"x","x":1e300}"#);
+    assert_eq!(Some(&json!(-32602)), error.get("error").unwrap().get("code"));
+    assert_eq!(r#"{"expression":"1/10","value":0.1}"#, result(&mut server, "evaluate", r#"{"expression":"x","x":0.1}"#));
+}
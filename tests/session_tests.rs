@@ -0,0 +1,47 @@
+use ap_calc::session::*;
+
+#[test]
+fn definitions_and_evaluation() {
+    let mut session = Session::new();
+    assert_eq!("f(x) = 3x^2+1", session.run("f(x) = 3x^2+1").unwrap());
+    assert_eq!("a = 5", session.run("a = 5").unwrap());
+    assert_eq!("13", session.run("f(2)").unwrap());
+    assert_eq!("76", session.run("f(a)").unwrap());
+    assert_eq!("6x", session.run("f'(x)").unwrap());
+    assert_eq!("2(sin(x)cos(x))", session.run("d/dx sin(x)^2").unwrap());
+    // any letter can be the variable
+    session.run("g(t) = t^2").unwrap();
+    assert_eq!("9", session.run("g(3)").unwrap());
+}
+
+#[test]
+fn commands() {
+    let mut session = Session::new();
+    session.run("f(x) = 3x^2+1").unwrap();
+    assert_eq!("2", session.run("integrate f 0 1").unwrap());
+    assert_eq!("x | y\n0 | 1\n1 | 4\n2 | 13", session.run("table f 0 2 1").unwrap());
+    assert_eq!("1", session.run("limit sin(x)/x 0").unwrap());
+    assert_eq!("-inf", session.run("limit 1/x 0 left").unwrap());
+    assert_eq!("-2, 2", session.run("roots x^2 - 4 -5 5").unwrap());
+    let extrema = session.run("extrema x^3-3x -3 3").unwrap();
+    assert!(extrema.contains("local max 2 at x = -1"));
+    assert!(extrema.contains("absolute min -18 at x = -3"));
+}
+
+#[test]
+fn errors_point_at_the_character() {
+    let mut session = Session::new();
+    let error = session.run("f(x) = 3x^+1").unwrap_err();
+    assert_eq!(Some(9), error.column);
+    assert_eq!("           ^ ^ needs something after it", error.pointer(2));
+    assert_eq!(Some(1), session.run("f(2").unwrap_err().column);
+    assert_eq!(Some(4), session.run("1 + g(1)").unwrap_err().column);
+    assert_eq!(Some(2), session.run("2 3").unwrap_err().column);
+    // the parser's own mistakes get a caret too rather than an assertion message
+    let error = session.run("h(x) = x*-1").unwrap_err();
+    assert_eq!("         ^ a negative after * needs brackets, like *(-1)", error.pointer(0));
+    let error = session.run("m(x) = {x if x<; 1 if x>=1}").unwrap_err();
+    assert_eq!("              ^ < needs something after it", error.pointer(0));
+    // nothing was defined by the lines that failed
+    assert!(session.bank.is_empty());
+}
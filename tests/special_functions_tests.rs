@@ -0,0 +1,57 @@
+use ap_calc::Expression;
+use ap_calc::{derivative, simplify};
+use fraction::Fraction;
+
+#[test]
+fn parsing_and_display() {
+    assert_eq!(Expression::from("|x|"), Expression::from("abs(x)"));
+    assert_eq!(Expression::from("2|x-3|+|x|"), Expression::from("2abs(x-3)+abs(x)"));
+    assert_eq!(Expression::from("||x|-1|"), Expression::from("abs(abs(x)-1)"));
+    for s in ["|x|", "sqrt(x+1)", "cbrt(x)", "root(4,x)", "floor(x)", "ceil(x/2)"] {
+        let e = Expression::from(s);
+        assert_eq!(Expression::from(&format!("{}", e)), e);
+    }
+    assert_eq!(format!("{}", Expression::from("sqrt(x)")), "(sqrt(x))");
+    assert_eq!(format!("{}", Expression::from("root(4,x)")), "(root(4,x))");
+}
+
+#[test]
+fn exact_values() {
+    let mut bank = ap_calc::new_bank('f', "|x-3|");
+    ap_calc::add_func_to_bank(&mut bank, 'g', "sqrt(x)");
+    assert_eq!(simplify::simplify(&Expression::from("sqrt(9/4)")), simplify::simplify(&Expression::from("3/2")));
+    assert_eq!(Expression::from("g(x)").evaluate(&bank, Fraction::new(9u64, 4u64)), Fraction::new(3u64, 2u64));
+    assert_eq!(Expression::from("cbrt(x)").evaluate(&bank, Fraction::from(-27)), Fraction::from(-3));
+    assert_eq!(Expression::from("f(x)").evaluate(&bank, Fraction::from(1)), Fraction::from(2));
+    assert_eq!(Expression::from("floor(x)").evaluate(&bank, Fraction::new(9u64, 4u64)), Fraction::from(2));
+    assert_eq!(Expression::from("ceil(x)").evaluate(&bank, Fraction::new(9u64, 4u64)), Fraction::from(3));
+    assert_eq!(Expression::from("floor(x)").approximate(&bank, -2.25), -3.0);
+    assert!(Expression::from("sqrt(x)").approximate(&bank, -1.0).is_nan());
+}
+
+#[test]
+fn derivatives() {
+    let mut bank = ap_calc::new_bank('f', "|x-3|");
+    ap_calc::add_func_to_bank(&mut bank, 'g', "sqrt(x)");
+    // sign(x - 3), which isn't defined at 3
+    let slope = derivative::derivative(&bank, &Expression::from("f(x)"));
+    assert_eq!(slope.approximate(&bank, 5.0), 1.0);
+    assert_eq!(slope.approximate(&bank, 1.0), -1.0);
+    assert!(slope.approximate(&bank, 3.0).is_nan());
+
+    let slope = derivative::derivative(&bank, &Expression::from("g(x)"));
+    assert_eq!(slope, simplify::simplify(&Expression::from("(1/2)x^(-1/2)")));
+
+    // odd roots of negatives stay exact, d/dx cbrt(x) at -8 is 1/12
+    let slope = derivative::derivative(&bank, &Expression::from("cbrt(x)"));
+    assert_eq!(slope.evaluate(&bank, Fraction::from(-8)), Fraction::new(1u64, 12u64));
+    assert_eq!(Expression::from("x^(-2/3)").evaluate(&bank, Fraction::from(-8)), Fraction::new(1u64, 4u64));
+
+    // flat between the jumps and undefined on them, like continuity::steps
+    let slope = derivative::derivative(&bank, &Expression::from("floor(x)"));
+    assert_eq!(slope.evaluate(&bank, Fraction::new(5u64, 2u64)), Fraction::from(0));
+    assert!(slope.evaluate(&bank, Fraction::from(2)).is_nan());
+    let slope = derivative::derivative(&bank, &Expression::from("ceil(x/2)"));
+    assert_eq!(slope.approximate(&bank, 1.0), 0.0);
+    assert!(slope.approximate(&bank, 4.0).is_nan());
+}
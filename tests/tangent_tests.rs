@@ -0,0 +1,59 @@
+use ap_calc::Expression;
+use ap_calc::simplify::simplify;
+use ap_calc::tangent::{differential, linearization, normal_line, tangent_line, Estimate, Line};
+
+#[test]
+fn lines() {
+    let mut bank = ap_calc::new_bank('f', "x^(1/2)");
+    ap_calc::add_func_to_bank(&mut bank, 'g', "x^3");
+    ap_calc::add_func_to_bank(&mut bank, 's', "sin(x)");
+    assert_eq!(tangent_line(&bank, 'f', &Expression::from("4")), Some(Line::Graph(simplify(&Expression::from("2+(1/4)(x-4)")))));
+    assert_eq!(normal_line(&bank, 'f', &Expression::from("4")), Some(Line::Graph(Expression::from("2-4(x-4)"))));
+    assert_eq!(tangent_line(&bank, 'g', &Expression::from("2")), Some(Line::Graph(Expression::from("8+12(x-2)"))));
+    // sin(π/6) = 1/2 and cos(π/6) = √3/2 come out exact
+    let line = tangent_line(&bank, 's', &Expression::from("pi/6")).unwrap();
+    assert_eq!(line.to_string(), "y = (1/2)+(((((3)^(1/2)))/(2))((x)-((π)/(6))))");
+    assert_eq!(differential(&bank, 'g', &Expression::from("2"), &Expression::from("1/10")), simplify(&Expression::from("6/5")));
+}
+
+#[test]
+fn horizontal_tangent_has_vertical_normal() {
+    let mut bank = ap_calc::new_bank('g', "x^3");
+    ap_calc::add_func_to_bank(&mut bank, 's', "sin(x)");
+    ap_calc::add_func_to_bank(&mut bank, 'h', "x^2");
+    assert_eq!(normal_line(&bank, 'g', &Expression::from("0")), Some(Line::Vertical(Expression::from("0"))));
+    let normal = normal_line(&bank, 'h', &Expression::from("0")).unwrap();
+    assert_eq!(normal.to_string(), "x = 0");
+    assert_eq!(normal_line(&bank, 's', &Expression::from("pi/2")), Some(Line::Vertical(simplify(&Expression::from("pi/2")))));
+
+    // and the other way round, cbrt(x) goes straight up through 0
+    ap_calc::add_func_to_bank(&mut bank, 'c', "cbrt(x)");
+    let zero = Expression::from("0");
+    assert_eq!(tangent_line(&bank, 'c', &zero), Some(Line::Vertical(Expression::from("0"))));
+    assert_eq!(normal_line(&bank, 'c', &zero), Some(Line::Graph(Expression::from("0"))));
+    assert!(linearization(&bank, 'c', &zero, &Expression::from("0.1")).is_none());
+    // a corner has no tangent at all
+    ap_calc::add_func_to_bank(&mut bank, 'a', "|x|");
+    assert_eq!(tangent_line(&bank, 'a', &zero), None);
+    assert_eq!(normal_line(&bank, 'a', &zero), None);
+}
+
+#[test]
+fn approximations() {
+    let mut bank = ap_calc::new_bank('f', "x^(1/2)");
+    ap_calc::add_func_to_bank(&mut bank, 'g', "x^3");
+    // √4.1 ≈ 2.025, too big since √x is concave down
+    let root = linearization(&bank, 'f', &Expression::from("4"), &Expression::from("4.1")).unwrap();
+    assert_eq!(root.approximation, simplify(&Expression::from("81/40")));
+    assert_eq!(root.estimate, Estimate::Over);
+    assert!(root.error < 0.0 && root.error.abs() < 2e-4);
+
+    let cube = linearization(&bank, 'g', &Expression::from("2"), &Expression::from("1.9")).unwrap();
+    assert_eq!(cube.value, 6.8);
+    assert_eq!(cube.estimate, Estimate::Under);
+    assert!((cube.error - 0.059).abs() < 1e-12);
+
+    // f''(0) = 0 but x^3 is concave up right after it
+    assert_eq!(linearization(&bank, 'g', &Expression::from("0"), &Expression::from("0.1")).unwrap().estimate, Estimate::Under);
+    assert_eq!(linearization(&bank, 'g', &Expression::from("-0.1"), &Expression::from("0.1")).unwrap().estimate, Estimate::Undetermined);
+}
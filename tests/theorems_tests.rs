@@ -0,0 +1,82 @@
+use ap_calc::Expression;
+use ap_calc::simplify::simplify;
+use ap_calc::theorems::{intermediate_value, mean_value, rolle, Theorem};
+
+#[test]
+fn mean_value_theorem() {
+    let mut bank = ap_calc::new_bank('f', "x^3");
+    ap_calc::add_func_to_bank(&mut bank, 'h', "x^(2/3)");
+    let mvt = mean_value(&bank, 'f', &Expression::from("0"), &Expression::from("3"));
+    assert_eq!(mvt.theorem, Theorem::MeanValue);
+    assert!(mvt.met);
+    assert_eq!((mvt.fa.to_string(), mvt.fb.to_string()), (String::from("0"), String::from("27")));
+    assert_eq!(mvt.target, Expression::from("9"));
+    assert_eq!(mvt.c.len(), 1);
+    assert_eq!(mvt.c[0].exact, Some(simplify(&Expression::from("3^(1/2)"))));
+    assert_eq!(mvt.to_string(), "Mean Value Theorem for f on [0, 3]
+  f is continuous on [0, 3]: yes
+  f is differentiable on (0, 3): yes
+  f(0) = 0, f(3) = 27
+  secant slope = 9
+  f'(c) = 9 at c = ((3)^(1/2))");
+
+    // the cusp at 0 breaks differentiability but not continuity
+    let cusp = mean_value(&bank, 'h', &Expression::from("-1"), &Expression::from("8"));
+    assert_eq!(cusp.hypotheses.iter().map(|(_, holds)| *holds).collect::<Vec<_>>(), vec![true, false]);
+    assert!(!cusp.met);
+    assert!(cusp.c.is_empty());
+}
+
+#[test]
+fn rolles_theorem() {
+    let mut bank = ap_calc::new_bank('g', "x^2-4x");
+    ap_calc::add_func_to_bank(&mut bank, 's', "sin(x)");
+    ap_calc::add_func_to_bank(&mut bank, 'f', "x^3");
+    let parabola = rolle(&bank, 'g', &Expression::from("0"), &Expression::from("4"));
+    assert!(parabola.met);
+    assert_eq!(parabola.c.iter().map(|c| c.to_string()).collect::<Vec<_>>(), vec!["2"]);
+
+    let wave = rolle(&bank, 's', &Expression::from("0"), &Expression::from("2pi"));
+    assert!(wave.met);
+    assert_eq!(wave.c.iter().map(|c| c.to_string()).collect::<Vec<_>>(), vec!["(1/2)(π)", "(3/2)(π)"]);
+
+    let uneven = rolle(&bank, 'f', &Expression::from("0"), &Expression::from("1"));
+    assert_eq!(uneven.hypotheses[2], (String::from("f(0) = f(1)"), false));
+}
+
+#[test]
+fn intermediate_value_theorem() {
+    let mut bank = ap_calc::new_bank('f', "x^3");
+    ap_calc::add_func_to_bank(&mut bank, 'r', "1/x");
+    let cube = intermediate_value(&bank, 'f', &Expression::from("0"), &Expression::from("3"), &Expression::from("8"));
+    assert!(cube.met);
+    assert_eq!(cube.c[0].exact, Some(Expression::from("2")));
+
+    // 1/x skips over 0 because it isn't continuous
+    let jump = intermediate_value(&bank, 'r', &Expression::from("-1"), &Expression::from("1"), &Expression::from("0"));
+    assert!(!jump.hypotheses[0].1);
+    assert!(jump.c.is_empty());
+    assert!(jump.to_string().ends_with("the hypotheses fail and no c has r(c) = 0"));
+}
+
+#[test]
+fn hypotheses_that_fail_without_blowing_up() {
+    let mut bank = ap_calc::new_bank('p', "floor(x)");
+    ap_calc::add_func_to_bank(&mut bank, 'j', "{x if x<1; x+1 if x>=1}");
+    ap_calc::add_func_to_bank(&mut bank, 'a', "|x|");
+    // floor steps over 1/2 and the piecewise j jumps over 3/2
+    let step = intermediate_value(&bank, 'p', &Expression::from("0"), &Expression::from("2"), &Expression::from("1/2"));
+    assert!(!step.hypotheses[0].1);
+    assert!(step.c.is_empty());
+    let jump = intermediate_value(&bank, 'j', &Expression::from("0"), &Expression::from("2"), &Expression::from("3/2"));
+    assert!(!jump.hypotheses[0].1);
+    assert!(jump.c.is_empty());
+    assert!(!mean_value(&bank, 'j', &Expression::from("0"), &Expression::from("2")).met);
+
+    // |x| is continuous but has a corner at 0
+    let corner = rolle(&bank, 'a', &Expression::from("-1"), &Expression::from("1"));
+    assert_eq!(corner.hypotheses.iter().map(|(_, holds)| *holds).collect::<Vec<_>>(), vec![true, false, true]);
+    assert!(corner.c.is_empty());
+    let right = mean_value(&bank, 'a', &Expression::from("0"), &Expression::from("2"));
+    assert!(right.met);
+}
@@ -0,0 +1,83 @@
+use ap_calc::*;
+use ap_calc::session::Session;
+use fraction::Fraction;
+
+const LESSON: &str = "\
+# lesson 3, g uses things defined after it
+g(x) = f(x) + a
+f(t) = 3t^2+1   # t works as well as x
+a = 5
+h(x) = int(t,0,x,f(t))
+";
+
+#[test]
+fn loading_in_any_order() {
+    let bank = Workspace::from_text(LESSON).unwrap();
+    assert_eq!(Fraction::from(18), Expression::from("g(2)").evaluate(&bank, Fraction::from(0)));
+    assert!(matches!(bank.get(&'h'), Some(Letter::Accumulation(_))));
+    // saved with every letter after the ones it uses
+    assert_eq!("a = 5\nf(x) = 3x^2+1\ng(x) = f(x)+a\nh(x) = int(t,0,x,f(t))\n", bank.to_text());
+}
+
+#[test]
+fn saving_and_loading_files() {
+    let path = std::env::temp_dir().join(format!("ap_calc_workspace_{}.txt", std::process::id()));
+    let bank = Workspace::from_text(LESSON).unwrap();
+    bank.save(&path).unwrap();
+    let loaded = Workspace::load(&path).unwrap();
+    assert_eq!(bank.to_text(), loaded.to_text());
+
+    // the REPL's save and load go through the same file format
+    let mut session = Session::new();
+    assert_eq!("loaded 4 definitions", session.run(&format!("load {}", path.display())).unwrap());
+    assert_eq!("18", session.run("g(2)").unwrap());
+    std::fs::remove_file(&path).unwrap();
+    assert!(Workspace::load(&path).unwrap_err()[0].line.is_none());
+}
+
+#[test]
+fn mistakes_are_reported_by_line() {
+    let errors = Workspace::from_text("f(x) = g(x)\ng(x) = f(x) + y\nf(x) = 2\n\na = 3x^+1\nc = c + 1\n").unwrap_err();
+    let errors: Vec<String> = errors.iter().map(|error| format!("{}", error)).collect();
+    assert_eq!(vec![
+        "line 1: f depends on itself, f -> g -> f",
+        "line 2: g uses y, which isn't defined",
+        "line 3: f was already defined on line 1",
+        "line 5: column 7: ^ needs something after it",
+        "line 6: c depends on itself, c -> c",
+    ], errors);
+}
+
+#[test]
+fn workspace_methods() {
+    let mut workspace = Workspace::new();
+    workspace.define_function('f', Expression::from("3x^2+1")).unwrap();
+    workspace.define_function('g', Expression::from("f(x)+a")).unwrap();
+    workspace.define_variable('a', Expression::from("b+1")).unwrap();
+    workspace.define_variable('b', Expression::from("4")).unwrap();
+    assert_eq!(Ok(Fraction::from(18)), workspace.evaluate('g', &[Fraction::from(2)]));
+    assert_eq!(Ok(Fraction::from(5)), workspace.evaluate('a', &[]));
+    assert_eq!(vec!['a', 'b', 'f'], workspace.dependencies_of('g'));
+    let names: Vec<char> = workspace.iter().map(|(name, _)| name).collect();
+    assert_eq!(vec!['a', 'b', 'f', 'g'], names);
+
+    // nothing that would loop or can't be a name gets in
+    assert!(workspace.define_variable('b', Expression::from("a")).is_err());
+    assert!(workspace.define_function('f', Expression::from("g(x)")).is_err());
+    assert!(workspace.define_function('x', Expression::from("2")).is_err());
+    assert!(workspace.define_variable('c', Expression::from("x")).is_err());
+    assert!(workspace.evaluate('f', &[]).is_err());
+    assert!(workspace.evaluate('h', &[Fraction::from(1)]).is_err());
+
+    assert!(workspace.remove('b').is_some());
+    assert_eq!(None, workspace.get(&'b'));
+    assert_eq!(3, workspace.len());
+
+    // letters used before they're defined, or after they're removed, are errors not panics
+    let missing = workspace.evaluate('g', &[Fraction::from(3)]).unwrap_err();
+    assert_eq!("g: uses b, which isn't defined", missing.to_string());
+    workspace.define_function('h', Expression::from("k(x)+1")).unwrap();
+    assert_eq!("h: uses k, which isn't defined", workspace.evaluate('h', &[Fraction::from(3)]).unwrap_err().to_string());
+    workspace.define_function('k', Expression::from("2x")).unwrap();
+    assert_eq!(Ok(Fraction::from(7)), workspace.evaluate('h', &[Fraction::from(3)]));
+}